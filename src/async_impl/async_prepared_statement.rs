@@ -0,0 +1,70 @@
+use super::{drop_blocking, run_blocking};
+use crate::{HdbError, HdbResponse, HdbResult, ParameterDescriptor, PreparedStatement};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Async variant of [`PreparedStatement`](../struct.PreparedStatement.html).
+#[derive(Debug)]
+pub struct AsyncPreparedStatement {
+    // Option only for being able to move the statement out in drop()
+    o_stmt: Option<Arc<Mutex<PreparedStatement>>>,
+}
+
+impl AsyncPreparedStatement {
+    pub(crate) fn new(stmt: PreparedStatement) -> AsyncPreparedStatement {
+        AsyncPreparedStatement {
+            o_stmt: Some(Arc::new(Mutex::new(stmt))),
+        }
+    }
+
+    fn am_stmt(&self) -> HdbResult<Arc<Mutex<PreparedStatement>>> {
+        self.o_stmt
+            .as_ref()
+            .map(Arc::clone)
+            .ok_or_else(|| HdbError::impl_("AsyncPreparedStatement is already dropped"))
+    }
+
+    /// Converts the input into a row of parameters,
+    /// if it is consistent with the metadata, and executes the statement.
+    ///
+    /// The conversion is done on the calling task, only the roundtrip is moved to
+    /// the blocking thread pool, so the input does not need to be `Send`.
+    pub async fn execute<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<HdbResponse> {
+        let mut stmt = self.am_stmt()?.lock_owned().await;
+        let o_rows = stmt.input_rows(input)?;
+        run_blocking(move || stmt.execute_parameter_rows(o_rows)).await
+    }
+
+    /// Converts the input into a row of parameters and adds it to the batch,
+    /// if it is consistent with the metadata.
+    ///
+    /// Does not talk to the database.
+    pub async fn add_batch<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<()> {
+        self.am_stmt()?.lock().await.add_batch(input)
+    }
+
+    /// Executes the statement with the collected batch, and clears the batch.
+    pub async fn execute_batch(&mut self) -> HdbResult<HdbResponse> {
+        let mut stmt = self.am_stmt()?.lock_owned().await;
+        run_blocking(move || stmt.execute_batch()).await
+    }
+
+    /// Descriptors of all parameters of the prepared statement (in, out, inout), if any.
+    pub async fn parameter_descriptors(&self) -> HdbResult<Option<Vec<ParameterDescriptor>>> {
+        Ok(self
+            .am_stmt()?
+            .lock()
+            .await
+            .parameter_descriptors()
+            .cloned())
+    }
+}
+
+impl Drop for AsyncPreparedStatement {
+    // Dropping the statement frees server-side resources with a roundtrip.
+    fn drop(&mut self) {
+        if let Some(am_stmt) = self.o_stmt.take() {
+            drop_blocking(am_stmt);
+        }
+    }
+}
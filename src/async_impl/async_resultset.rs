@@ -0,0 +1,151 @@
+use super::{drop_blocking, run_blocking};
+use crate::{HdbError, HdbResult, ResultSet, ResultSetMetadata, Row};
+
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::task::JoinHandle;
+
+type FetchResult = (ResultSet, HdbResult<Option<Row>>);
+
+/// Async variant of [`ResultSet`](../struct.ResultSet.html).
+///
+/// `AsyncResultSet` is a `Stream` of rows. Rows that were already transported to the
+/// client are handed out directly; further rows are fetched lazily from the database,
+/// with one `FetchNext` roundtrip whenever the client side buffer is exhausted.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use hdbconnect::{AsyncConnection, HdbResult, IntoConnectParams};
+/// use futures::StreamExt;
+/// # async fn foo() -> HdbResult<()> {
+/// # let mut connection = AsyncConnection::new("".into_connect_params()?).await?;
+/// let mut rs = connection.query("select * from my_table").await?;
+/// while let Some(row) = rs.next().await {
+///     let row = row?;
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncResultSet {
+    state: State,
+}
+
+#[derive(Debug)]
+enum State {
+    Idle(ResultSet),
+    Fetching(JoinHandle<FetchResult>),
+    Done,
+}
+
+impl AsyncResultSet {
+    pub(crate) fn new(rs: ResultSet) -> AsyncResultSet {
+        AsyncResultSet {
+            state: State::Idle(rs),
+        }
+    }
+
+    fn take_resultset(&mut self) -> HdbResult<ResultSet> {
+        match std::mem::replace(&mut self.state, State::Done) {
+            State::Idle(rs) => Ok(rs),
+            State::Fetching(_) | State::Done => Err(HdbError::Usage(
+                "AsyncResultSet is already partially consumed as stream".to_owned(),
+            )),
+        }
+    }
+
+    /// Translates the resultset into a given rust type,
+    /// see [`ResultSet::try_into`](../struct.ResultSet.html#method.try_into).
+    ///
+    /// Outstanding rows are fetched from the database before the conversion.
+    pub async fn try_into<T>(mut self) -> HdbResult<T>
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        let rs = self.take_resultset()?;
+        run_blocking(move || rs.try_into()).await
+    }
+
+    /// Fetches all not yet transported result lines from the server.
+    pub async fn fetch_all(&mut self) -> HdbResult<()> {
+        let mut rs = self.take_resultset()?;
+        let (rs, result) = run_blocking(move || {
+            let result = rs.fetch_all();
+            Ok((rs, result))
+        })
+        .await?;
+        self.state = State::Idle(rs);
+        result
+    }
+
+    /// Access to metadata.
+    ///
+    /// Returns `None` while a fetch is in progress or after the stream is exhausted.
+    pub fn metadata(&self) -> Option<&ResultSetMetadata> {
+        match self.state {
+            State::Idle(ref rs) => Some(rs.metadata()),
+            State::Fetching(_) | State::Done => None,
+        }
+    }
+}
+
+impl Stream for AsyncResultSet {
+    type Item = HdbResult<Row>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match std::mem::replace(&mut self.state, State::Done) {
+                State::Idle(mut rs) => match rs.next_buffered_row() {
+                    Some(row) => {
+                        self.state = State::Idle(rs);
+                        return Poll::Ready(Some(Ok(row)));
+                    }
+                    None => {
+                        self.state = State::Fetching(tokio::task::spawn_blocking(move || {
+                            let result = rs.next_row();
+                            (rs, result)
+                        }));
+                    }
+                },
+                State::Fetching(mut handle) => match Pin::new(&mut handle).poll(cx) {
+                    Poll::Pending => {
+                        self.state = State::Fetching(handle);
+                        return Poll::Pending;
+                    }
+                    Poll::Ready(Ok((rs, Ok(Some(row))))) => {
+                        self.state = State::Idle(rs);
+                        return Poll::Ready(Some(Ok(row)));
+                    }
+                    Poll::Ready(Ok((rs, Ok(None)))) => {
+                        drop_blocking(rs);
+                        return Poll::Ready(None);
+                    }
+                    Poll::Ready(Ok((rs, Err(e)))) => {
+                        drop_blocking(rs);
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    Poll::Ready(Err(e)) => {
+                        return Poll::Ready(Some(Err(HdbError::Impl(format!(
+                            "blocking fetch task failed: {}",
+                            e
+                        )))));
+                    }
+                },
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl Drop for AsyncResultSet {
+    // Closing an incomplete resultset on the server needs a roundtrip.
+    fn drop(&mut self) {
+        if let State::Idle(rs) = std::mem::replace(&mut self.state, State::Done) {
+            drop_blocking(rs);
+        }
+    }
+}
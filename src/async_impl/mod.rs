@@ -0,0 +1,40 @@
+//! Async front end, based on tokio.
+//!
+//! The async types are thin wrappers around their synchronous counterparts:
+//! all database roundtrips are executed via `tokio::task::spawn_blocking`,
+//! so the wire code (`Request::emit`, `Reply::parse`) is shared with the
+//! synchronous API, and the worker threads of the async runtime are never blocked.
+//!
+//! All methods must be called from within a tokio runtime.
+
+mod async_connection;
+mod async_prepared_statement;
+mod async_resultset;
+
+pub use self::async_connection::AsyncConnection;
+pub use self::async_prepared_statement::AsyncPreparedStatement;
+pub use self::async_resultset::AsyncResultSet;
+
+use crate::{HdbError, HdbResult};
+
+// Runs a blocking database interaction on tokio's blocking thread pool.
+async fn run_blocking<T, F>(f: F) -> HdbResult<T>
+where
+    F: FnOnce() -> HdbResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| HdbError::Impl(format!("blocking database task failed: {}", e)))?
+}
+
+// Moves the drop of an object that talks to the database in its Drop implementation
+// to the blocking thread pool, if a runtime is available.
+fn drop_blocking<T: Send + 'static>(t: T) {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn_blocking(move || drop(t));
+        }
+        Err(_) => drop(t),
+    }
+}
@@ -1,6 +1,6 @@
 use super::authenticator::Authenticator;
 
-use crate::conn_core::AmConnCore;
+use crate::conn_core::ConnectionCore;
 use crate::hdb_error::{HdbError, HdbResult};
 use crate::protocol::argument::Argument;
 use crate::protocol::part::Part;
@@ -11,11 +11,10 @@ use crate::protocol::parts::connect_options::ConnectOptions;
 use crate::protocol::reply_type::ReplyType;
 use crate::protocol::request::Request;
 use crate::protocol::request_type::RequestType;
-use secstr::SecStr;
 use username;
 
 pub(crate) fn first_auth_request(
-    am_conn_core: &mut AmConnCore,
+    conn_core: &mut ConnectionCore,
     db_user: &str,
     authenticators: &[Box<dyn Authenticator>],
 ) -> HdbResult<(String, Vec<u8>)> {
@@ -37,7 +36,7 @@ pub(crate) fn first_auth_request(
         Argument::Auth(auth_fields),
     ));
 
-    let mut reply = am_conn_core.send(request1)?;
+    let mut reply = conn_core.roundtrip(request1, None, None, None, &mut None)?;
     reply.assert_expected_reply_type(&ReplyType::Nil)?;
 
     match reply.parts.pop_arg_if_kind(PartKind::Authentication) {
@@ -61,37 +60,41 @@ pub(crate) fn first_auth_request(
 }
 
 pub(crate) fn second_auth_request(
-    am_conn_core: &mut AmConnCore,
+    conn_core: &mut ConnectionCore,
     db_user: &str,
-    password: &SecStr,
     clientlocale: &Option<String>,
     mut chosen_authenticator: Box<dyn Authenticator>,
     server_challenge_data: &[u8],
-) -> HdbResult<()> {
+) -> HdbResult<Option<Vec<u8>>> {
     let mut request2 = Request::new(RequestType::Connect, 0);
 
     debug!("authenticating with {}", chosen_authenticator.name());
 
+    let client_proof = chosen_authenticator.client_proof(server_challenge_data)?;
+    let db_user = chosen_authenticator.server_user().unwrap_or(db_user);
     let mut auth_fields = AuthFields::with_capacity(3);
     auth_fields.push(db_user.as_bytes().to_vec());
     auth_fields.push(chosen_authenticator.name_as_bytes());
-    auth_fields.push(chosen_authenticator.client_proof(server_challenge_data, password)?);
+    auth_fields.push(client_proof);
     request2.push(Part::new(
         PartKind::Authentication,
         Argument::Auth(auth_fields),
     ));
 
     // how about e.g. TABLEOUTPUTPARAMETER and DESCRIBETABLEOUTPUTPARAMETER?
-    let sent_co = ConnectOptions::for_server(clientlocale, get_os_user());
+    let sent_co = ConnectOptions::for_server(
+        clientlocale,
+        get_os_user(),
+        conn_core.connect_params().compression(),
+    );
     request2.push(Part::new(
         PartKind::ConnectOptions,
         Argument::ConnectOptions(sent_co.clone()),
     ));
 
-    let mut reply = am_conn_core.send(request2)?;
+    let mut reply = conn_core.roundtrip(request2, None, None, None, &mut None)?;
     reply.assert_expected_reply_type(&ReplyType::Nil)?;
 
-    let mut conn_core = am_conn_core.lock()?;
     conn_core.set_session_id(reply.session_id());
 
     match reply.parts.pop_arg_if_kind(PartKind::TopologyInformation) {
@@ -116,13 +119,16 @@ pub(crate) fn second_auth_request(
 
     match reply.parts.pop_arg_if_kind(PartKind::Authentication) {
         Some(Argument::Auth(mut af)) => {
-            if af.len() == 2 {
+            // the server can append a session cookie
+            if af.len() == 2 || af.len() == 3 {
+                let o_session_cookie = if af.len() == 3 { af.pop() } else { None };
                 let server_proof = af.pop().unwrap();
                 let method = af.pop().unwrap();
-                chosen_authenticator.evaluate_second_response(&method, &server_proof)
+                chosen_authenticator.evaluate_second_response(&method, &server_proof)?;
+                Ok(o_session_cookie)
             } else {
                 Err(HdbError::Impl(format!(
-                    "second_auth_request(): got {} authfields, expected 2",
+                    "second_auth_request(): got {} authfields, expected 2 or 3",
                     af.len()
                 )))
             }
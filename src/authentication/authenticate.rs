@@ -1,53 +1,103 @@
 use crate::authentication::auth_requests::{first_auth_request, second_auth_request};
 use crate::authentication::authenticator::Authenticator;
+use crate::authentication::bearer_token::BearerToken;
+#[cfg(feature = "ldap")]
+use crate::authentication::ldap::Ldap;
 use crate::authentication::scram_pbkdf2_sha256::ScramPbkdf2Sha256;
 use crate::authentication::scram_sha256::ScramSha256;
-use crate::conn_core::AmConnCore;
-use crate::hdb_error::HdbResult;
+use crate::authentication::session_cookie::SessionCookie;
+use crate::authentication::token_provider::ProvidedToken;
+#[cfg(feature = "tls")]
+use crate::authentication::x509::X509;
+use crate::conn_core::ConnectionCore;
+use crate::hdb_error::{HdbError, HdbResult};
 use secstr::SecStr;
+use std::sync::Arc;
 
 // Do the authentication.
 //
-// Manages a list of supported authenticators.
-// So far we only support two; if more are implemented, the password might
-// become optional; if then the password is not given, the pw-related
-// authenticators mut not be added to the list.
-pub(crate) fn authenticate(
-    am_conn_core: &mut AmConnCore,
-    db_user: &str,
-    password: &SecStr,
-    clientlocale: &Option<String>,
-) -> HdbResult<()> {
+// Manages a list of supported authenticators, and proposes those for which
+// the connect params contain the necessary credentials; the server chooses one of them.
+pub(crate) fn authenticate(conn_core: &mut ConnectionCore) -> HdbResult<()> {
     trace!("authenticate()");
+    let params = conn_core.connect_params().clone();
 
     // Propose some authenticators...
-    let authenticators: Vec<Box<dyn Authenticator>> = vec![
-        // Cookie,  Gss, Saml, SapLogon, Jwt, Ldap,
-        ScramSha256::boxed_authenticator(),
-        ScramPbkdf2Sha256::boxed_authenticator(),
-    ];
+    // (not supported directly: Gss, SapLogon; such methods can be plugged in
+    // with a TokenProvider)
+    let mut authenticators = Vec::<Box<dyn Authenticator>>::new();
+    // a cookie from an earlier logon of this connection is preferred over one from the params
+    let o_session_cookie = conn_core
+        .session_cookie()
+        .or_else(|| params.session_cookie())
+        .cloned();
+    if let Some(ref session_cookie) = o_session_cookie {
+        authenticators.push(SessionCookie::boxed_authenticator(session_cookie));
+    }
+    #[cfg(feature = "tls")]
+    {
+        if let Some(client_cert) = params.client_cert() {
+            authenticators.push(X509::boxed_authenticator(client_cert)?);
+        }
+    }
+    if let Some(token) = params.jwt() {
+        authenticators.push(BearerToken::boxed_authenticator("JWT", token.clone()));
+    }
+    if let Some(assertion) = params.saml_assertion() {
+        authenticators.push(BearerToken::boxed_authenticator("SAML", assertion.clone()));
+    }
+    for token_provider in params.token_providers() {
+        authenticators.push(ProvidedToken::boxed_authenticator(
+            Arc::clone(token_provider),
+            params.dbuser(),
+        )?);
+    }
+    match params.password() {
+        // with LDAP, the password must not be used otherwise
+        #[cfg(feature = "ldap")]
+        Some(password) if params.ldap() => {
+            authenticators.push(Ldap::boxed_authenticator(password.clone()));
+        }
+        Some(password) => {
+            authenticators.push(ScramSha256::boxed_authenticator(password.clone()));
+            authenticators.push(ScramPbkdf2Sha256::boxed_authenticator(password.clone()));
+        }
+        None => {}
+    }
+    if authenticators.is_empty() {
+        return Err(HdbError::Usage(
+            "No credentials given: neither password nor token nor client certificate".to_owned(),
+        ));
+    }
 
     // ...with the first request.
     let (selected, server_challenge_data) =
-        first_auth_request(am_conn_core, db_user, &authenticators)?;
+        first_auth_request(conn_core, params.dbuser(), &authenticators)?;
 
     // Find the selected authenticator ...
     let chosen_authenticator: Box<dyn Authenticator> = authenticators
         .into_iter()
         .find(|a11r| a11r.name() == selected)
-        .unwrap();
+        .ok_or_else(|| {
+            HdbError::Impl(format!(
+                "Server selected an authentication method that was not proposed: {}",
+                selected
+            ))
+        })?;
 
     // ...and use it for the second request
-    second_auth_request(
-        am_conn_core,
-        db_user,
-        password,
-        clientlocale,
+    let o_new_session_cookie = second_auth_request(
+        conn_core,
+        params.dbuser(),
+        params.clientlocale(),
         chosen_authenticator,
         &server_challenge_data,
     )?;
 
-    let mut conn_core = am_conn_core.lock()?;
+    if let Some(session_cookie) = o_new_session_cookie {
+        debug!("got a session cookie");
+        conn_core.set_session_cookie(SecStr::new(session_cookie));
+    }
     conn_core.set_authenticated(true);
 
     Ok(())
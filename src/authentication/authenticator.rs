@@ -1,17 +1,17 @@
 use crate::{HdbError, HdbResult};
-use secstr::SecStr;
 
 pub trait Authenticator {
     fn name(&self) -> &str;
     fn name_as_bytes(&self) -> Vec<u8>;
     fn client_challenge(&self) -> &[u8];
-    fn client_proof(
-        &mut self,
-        server_challenge_data: &[u8],
-        password: &SecStr,
-    ) -> HdbResult<Vec<u8>>;
-    fn verify_server(&self, server_proof: &[u8]) -> HdbResult<()>;
-    fn evaluate_second_response(&self, method: &[u8], server_proof: &[u8]) -> HdbResult<()> {
+    fn client_proof(&mut self, server_challenge_data: &[u8]) -> HdbResult<Vec<u8>>;
+    fn verify_server(&mut self, server_proof: &[u8]) -> HdbResult<()>;
+    // The database user, if it is determined by the server (from a token) rather
+    // than given by the client; is available after client_proof() was called.
+    fn server_user(&self) -> Option<&str> {
+        None
+    }
+    fn evaluate_second_response(&mut self, method: &[u8], server_proof: &[u8]) -> HdbResult<()> {
         if method != self.name().as_bytes() {
             Err(HdbError::Impl(format!(
                 "Wrong method name detected: {}",
@@ -0,0 +1,53 @@
+use super::authenticator::Authenticator;
+use crate::HdbResult;
+use secstr::SecStr;
+
+// Authentication with a bearer token, i.e. a JSON Web Token (method "JWT")
+// or a SAML bearer assertion (method "SAML").
+//
+// The token is sent as client challenge with the first request; the server validates it
+// and answers with the name of the database user that is mapped to the token's subject.
+// The second request then only confirms the logon of this user.
+pub struct BearerToken {
+    method: &'static str,
+    token: SecStr,
+    server_user: Option<String>,
+}
+impl BearerToken {
+    pub fn boxed_authenticator(method: &'static str, token: SecStr) -> Box<dyn Authenticator> {
+        Box::new(BearerToken {
+            method,
+            token,
+            server_user: None,
+        })
+    }
+}
+impl Authenticator for BearerToken {
+    fn name(&self) -> &str {
+        self.method
+    }
+
+    fn name_as_bytes(&self) -> Vec<u8> {
+        self.name().as_bytes().to_owned()
+    }
+
+    fn client_challenge(&self) -> &[u8] {
+        self.token.unsecure()
+    }
+
+    fn client_proof(&mut self, server_data: &[u8]) -> HdbResult<Vec<u8>> {
+        let server_user = String::from_utf8_lossy(server_data).to_string();
+        debug!("{} is mapped to database user {}", self.method, server_user);
+        self.server_user = Some(server_user);
+        Ok(Vec::new())
+    }
+
+    fn verify_server(&mut self, _server_proof: &[u8]) -> HdbResult<()> {
+        // the server has nothing to prove
+        Ok(())
+    }
+
+    fn server_user(&self) -> Option<&str> {
+        self.server_user.as_deref()
+    }
+}
@@ -0,0 +1,205 @@
+use super::authenticator::Authenticator;
+use crate::protocol::parts::authfields::AuthFields;
+use crate::{HdbError, HdbResult};
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockEncryptMut, KeyIvInit};
+use rand::{thread_rng, RngCore};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::rand_core::OsRng;
+use rsa::{Oaep, RsaPublicKey};
+use secstr::SecStr;
+use std::io;
+
+const CLIENT_NONCE_SIZE: usize = 64;
+const SESSION_KEY_SIZE: usize = 32; // AES-256
+const IV_SIZE: usize = 16;
+const LDAP_CAPABILITIES: u8 = 1; // the default
+
+// Authentication against an LDAP server, to which the database delegates the
+// password check.
+//
+// The client sends a nonce, the server answers with its own nonce and an RSA public key.
+// The client then sends a random session key, encrypted with the public key, and the
+// password, encrypted with the session key, so that the database can forward the
+// cleartext password to the LDAP server.
+pub struct Ldap {
+    client_nonce: Vec<u8>,
+    client_challenge: Vec<u8>,
+    password: SecStr,
+}
+impl Ldap {
+    pub fn boxed_authenticator(password: SecStr) -> Box<dyn Authenticator> {
+        let mut client_nonce = vec![0_u8; CLIENT_NONCE_SIZE];
+        thread_rng().fill_bytes(&mut client_nonce);
+
+        let mut auth_fields = AuthFields::with_capacity(2);
+        auth_fields.push(client_nonce.clone());
+        auth_fields.push(vec![LDAP_CAPABILITIES]);
+        let mut client_challenge = Vec::<u8>::with_capacity(auth_fields.size());
+        auth_fields
+            .emit(&mut client_challenge)
+            .unwrap(/* cannot fail for small fields */);
+
+        Box::new(Ldap {
+            client_nonce,
+            client_challenge,
+            password,
+        })
+    }
+}
+impl Authenticator for Ldap {
+    fn name(&self) -> &str {
+        "LDAP"
+    }
+
+    fn name_as_bytes(&self) -> Vec<u8> {
+        self.name().as_bytes().to_owned()
+    }
+
+    fn client_challenge(&self) -> &[u8] {
+        &self.client_challenge
+    }
+
+    fn client_proof(&mut self, server_data: &[u8]) -> HdbResult<Vec<u8>> {
+        let (server_nonce, public_key) = parse_first_server_data(server_data, &self.client_nonce)?;
+
+        let mut session_key = SecStr::new(vec![0_u8; SESSION_KEY_SIZE]);
+        thread_rng().fill_bytes(session_key.unsecure_mut());
+
+        // the session key, followed by the server nonce, is encrypted with RSA-OAEP
+        let mut key_and_nonce = session_key.unsecure().to_vec();
+        key_and_nonce.extend_from_slice(&server_nonce);
+        let encrypted_session_key = public_key
+            .encrypt(&mut OsRng, Oaep::new::<sha1::Sha1>(), &key_and_nonce)
+            .map_err(|e| {
+                HdbError::Impl(format!("LDAP: encryption of session key failed: {}", e))
+            })?;
+
+        // the password, terminated with 0 and followed by the server nonce,
+        // is encrypted with AES-256-CBC
+        let mut plaintext = self.password.unsecure().to_vec();
+        plaintext.push(0);
+        plaintext.extend_from_slice(&server_nonce);
+        let plaintext = SecStr::new(plaintext); // is zeroed out when dropped
+        let encrypted_password = cbc::Encryptor::<aes::Aes256>::new_from_slices(
+            session_key.unsecure(),
+            &server_nonce[..IV_SIZE],
+        )
+        .map_err(|e| HdbError::Impl(format!("LDAP: invalid key or iv: {}", e)))?
+        .encrypt_padded_vec_mut::<Pkcs7>(plaintext.unsecure());
+
+        let mut auth_fields = AuthFields::with_capacity(2);
+        auth_fields.push(encrypted_session_key);
+        auth_fields.push(encrypted_password);
+        let mut buf = Vec::<u8>::with_capacity(auth_fields.size());
+        auth_fields.emit(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn verify_server(&mut self, _server_proof: &[u8]) -> HdbResult<()> {
+        // the LDAP server has checked the password, there is nothing to prove
+        Ok(())
+    }
+}
+
+// `server_data` is again an AuthFields; contains the client nonce (echoed),
+// the server nonce, the server's public key (PEM), and the capabilities
+fn parse_first_server_data(
+    server_data: &[u8],
+    client_nonce: &[u8],
+) -> HdbResult<(Vec<u8>, RsaPublicKey)> {
+    let mut rdr = io::Cursor::new(server_data);
+    let mut af = AuthFields::parse(&mut rdr)?;
+    if af.len() != 4 {
+        return Err(HdbError::Impl(format!(
+            "LDAP: got {} auth fields instead of 4",
+            af.len()
+        )));
+    }
+    let capabilities = af.pop().unwrap();
+    let public_key_pem = af.pop().unwrap();
+    let server_nonce = af.pop().unwrap();
+    let echoed_client_nonce = af.pop().unwrap();
+
+    if echoed_client_nonce != client_nonce {
+        return Err(HdbError::Impl(
+            "LDAP: server returned a wrong client nonce".to_owned(),
+        ));
+    }
+    if server_nonce.len() < IV_SIZE {
+        return Err(HdbError::Impl(format!(
+            "LDAP: server nonce is too short ({} bytes)",
+            server_nonce.len()
+        )));
+    }
+    if capabilities != [LDAP_CAPABILITIES] {
+        return Err(HdbError::Impl(format!(
+            "LDAP: unsupported capabilities {:?}",
+            capabilities
+        )));
+    }
+
+    let public_key_pem = String::from_utf8_lossy(&public_key_pem);
+    let public_key = RsaPublicKey::from_public_key_pem(&public_key_pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(&public_key_pem))
+        .map_err(|e| HdbError::Impl(format!("LDAP: cannot read server's public key: {}", e)))?;
+    Ok((server_nonce, public_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ldap, LDAP_CAPABILITIES, SESSION_KEY_SIZE};
+    use crate::protocol::parts::authfields::AuthFields;
+    use aes::cipher::block_padding::Pkcs7;
+    use aes::cipher::{BlockDecryptMut, KeyIvInit};
+    use rsa::pkcs8::{EncodePublicKey, LineEnding};
+    use rsa::rand_core::OsRng;
+    use rsa::{Oaep, RsaPrivateKey};
+    use secstr::SecStr;
+    use std::io;
+
+    // the server side of the exchange is simulated
+    #[test]
+    fn test_client_proof() {
+        let private_key = RsaPrivateKey::new(&mut OsRng, 2048).unwrap();
+        let public_key_pem = private_key
+            .to_public_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap();
+        let server_nonce: Vec<u8> = (0..64).collect();
+
+        let mut ldap = Ldap::boxed_authenticator(SecStr::from("manager"));
+        let mut client_challenge =
+            AuthFields::parse(&mut io::Cursor::new(ldap.client_challenge())).unwrap();
+        assert_eq!(client_challenge.pop().unwrap(), vec![LDAP_CAPABILITIES]);
+        let client_nonce = client_challenge.pop().unwrap();
+
+        let mut server_data = AuthFields::with_capacity(4);
+        server_data.push(client_nonce);
+        server_data.push(server_nonce.clone());
+        server_data.push(public_key_pem.into_bytes());
+        server_data.push(vec![LDAP_CAPABILITIES]);
+        let mut buf = Vec::<u8>::new();
+        server_data.emit(&mut buf).unwrap();
+
+        let client_proof = ldap.client_proof(&buf).unwrap();
+        let mut client_proof = AuthFields::parse(&mut io::Cursor::new(client_proof)).unwrap();
+        let encrypted_password = client_proof.pop().unwrap();
+        let encrypted_session_key = client_proof.pop().unwrap();
+
+        let key_and_nonce = private_key
+            .decrypt(Oaep::new::<sha1::Sha1>(), &encrypted_session_key)
+            .unwrap();
+        assert_eq!(&key_and_nonce[SESSION_KEY_SIZE..], &server_nonce[..]);
+        let password = cbc::Decryptor::<aes::Aes256>::new_from_slices(
+            &key_and_nonce[..SESSION_KEY_SIZE],
+            &server_nonce[..16],
+        )
+        .unwrap()
+        .decrypt_padded_vec_mut::<Pkcs7>(&encrypted_password)
+        .unwrap();
+        assert_eq!(&password[..8], b"manager\0");
+        assert_eq!(&password[8..], &server_nonce[..]);
+    }
+}
@@ -1,8 +1,16 @@
 mod auth_requests;
 mod authenticate;
 mod authenticator;
-mod crypto_util;
+mod bearer_token;
+pub(crate) mod crypto_util;
+#[cfg(feature = "ldap")]
+mod ldap;
 mod scram_pbkdf2_sha256;
 mod scram_sha256;
+mod session_cookie;
+mod token_provider;
+#[cfg(feature = "tls")]
+mod x509;
 
 pub(crate) use self::authenticate::authenticate;
+pub use self::token_provider::{TokenProvider, TokenSession};
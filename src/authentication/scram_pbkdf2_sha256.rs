@@ -13,15 +13,17 @@ const CLIENT_PROOF_SIZE: usize = 32;
 
 pub struct ScramPbkdf2Sha256 {
     client_challenge: Vec<u8>,
+    password: SecStr,
     server_proof: Option<Vec<u8>>,
 }
 impl ScramPbkdf2Sha256 {
-    pub fn boxed_authenticator() -> Box<Authenticator> {
+    pub fn boxed_authenticator(password: SecStr) -> Box<dyn Authenticator> {
         let mut client_challenge = [0u8; 64];
         let mut rng = thread_rng();
         rng.fill_bytes(&mut client_challenge);
         Box::new(ScramPbkdf2Sha256 {
             client_challenge: client_challenge.to_vec(),
+            password,
             server_proof: None,
         })
     }
@@ -39,7 +41,7 @@ impl Authenticator for ScramPbkdf2Sha256 {
         &(self.client_challenge)
     }
 
-    fn client_proof(&mut self, server_data: &[u8], password: &SecStr) -> HdbResult<Vec<u8>> {
+    fn client_proof(&mut self, server_data: &[u8]) -> HdbResult<Vec<u8>> {
         let (salt, server_nonce, iterations) = parse_first_server_data(server_data).unwrap();
 
         let start = Local::now();
@@ -47,7 +49,7 @@ impl Authenticator for ScramPbkdf2Sha256 {
             &salt,
             &server_nonce,
             &self.client_challenge,
-            password,
+            &self.password,
             iterations,
         )?;
         debug!(
@@ -69,7 +71,7 @@ impl Authenticator for ScramPbkdf2Sha256 {
         Ok(buf)
     }
 
-    fn verify_server(&self, server_data: &[u8]) -> HdbResult<()> {
+    fn verify_server(&mut self, server_data: &[u8]) -> HdbResult<()> {
         let mut af = AuthFields::parse(&mut io::Cursor::new(server_data))?;
         let srv_proof = af.pop().unwrap();
 
@@ -12,15 +12,17 @@ const CLIENT_PROOF_SIZE: usize = 32;
 
 pub struct ScramSha256 {
     client_challenge: Vec<u8>,
+    password: SecStr,
     server_proof: Option<Vec<u8>>,
 }
 impl ScramSha256 {
-    pub fn boxed_authenticator() -> Box<Authenticator> {
+    pub fn boxed_authenticator(password: SecStr) -> Box<dyn Authenticator> {
         let mut client_challenge = [0u8; 64];
         let mut rng = thread_rng();
         rng.fill_bytes(&mut client_challenge);
         Box::new(ScramSha256 {
             client_challenge: client_challenge.to_vec(),
+            password,
             server_proof: None,
         })
     }
@@ -38,11 +40,11 @@ impl Authenticator for ScramSha256 {
         &(self.client_challenge)
     }
 
-    fn client_proof(&mut self, server_data: &[u8], password: &SecStr) -> HdbResult<Vec<u8>> {
+    fn client_proof(&mut self, server_data: &[u8]) -> HdbResult<Vec<u8>> {
         let (salt, server_nonce) = parse_first_server_data(server_data).unwrap();
 
         let (client_proof, server_proof) =
-            scram_sha256(&salt, &server_nonce, &self.client_challenge, password)?;
+            scram_sha256(&salt, &server_nonce, &self.client_challenge, &self.password)?;
 
         self.client_challenge.clear();
         self.server_proof = Some(server_proof);
@@ -55,7 +57,7 @@ impl Authenticator for ScramSha256 {
         Ok(buf)
     }
 
-    fn verify_server(&self, server_proof: &[u8]) -> HdbResult<()> {
+    fn verify_server(&mut self, server_proof: &[u8]) -> HdbResult<()> {
         if server_proof.is_empty() {
             Ok(())
         } else {
@@ -119,9 +121,10 @@ mod tests {
 
         let mut a = ScramSha256 {
             client_challenge,
+            password,
             server_proof: None,
         };
-        let my_client_proof = a.client_proof(&server_challenge, &password).unwrap();
+        let my_client_proof = a.client_proof(&server_challenge).unwrap();
 
         assert_eq!(my_client_proof, correct_client_proof);
     }
@@ -0,0 +1,51 @@
+use super::authenticator::Authenticator;
+use crate::HdbResult;
+use secstr::SecStr;
+
+// Authentication with a session cookie that the server handed out with an earlier logon
+// of the same database user.
+//
+// The cookie, followed by an identification of the client process, is sent as client
+// challenge with the first request; the second request only confirms the logon.
+pub struct SessionCookie {
+    client_challenge: SecStr,
+}
+impl SessionCookie {
+    pub fn boxed_authenticator(session_cookie: &SecStr) -> Box<dyn Authenticator> {
+        let mut client_challenge = session_cookie.unsecure().to_vec();
+        client_challenge.extend_from_slice(client_id().as_bytes());
+        Box::new(SessionCookie {
+            client_challenge: SecStr::new(client_challenge),
+        })
+    }
+}
+impl Authenticator for SessionCookie {
+    fn name(&self) -> &str {
+        "SessionCookie"
+    }
+
+    fn name_as_bytes(&self) -> Vec<u8> {
+        self.name().as_bytes().to_owned()
+    }
+
+    fn client_challenge(&self) -> &[u8] {
+        self.client_challenge.unsecure()
+    }
+
+    fn client_proof(&mut self, _server_data: &[u8]) -> HdbResult<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn verify_server(&mut self, _server_proof: &[u8]) -> HdbResult<()> {
+        // the server has nothing to prove
+        Ok(())
+    }
+}
+
+fn client_id() -> String {
+    format!(
+        "{}@{}",
+        std::process::id(),
+        username::get_user_name().unwrap_or_default()
+    )
+}
@@ -0,0 +1,178 @@
+use super::authenticator::Authenticator;
+use crate::HdbResult;
+use std::fmt;
+use std::sync::Arc;
+
+/// A pluggable authentication method.
+///
+/// Applications can use this trait to log on with authentication methods that
+/// `hdbconnect` does not implement itself, like Kerberos (method "GSS") or a custom
+/// single sign-on. Providers are registered with
+/// [`ConnectParamsBuilder::token_provider()`](struct.ConnectParamsBuilder.html#method.token_provider);
+/// their methods are proposed to the server together with the built-in ones,
+/// and the server chooses one of the proposed methods.
+///
+/// The same provider is used for all logons that are done with the `ConnectParams`
+/// (including reconnects and pooled connections), possibly concurrently.
+/// It is thus only a factory: for each logon it creates a new
+/// [`TokenSession`](trait.TokenSession.html), which holds the state of this one exchange.
+pub trait TokenProvider: fmt::Debug + Send + Sync {
+    /// The name of the authentication method, as it is known to the server, e.g. "GSS".
+    fn method_name(&self) -> &str;
+
+    /// Starts a new logon of the given database user.
+    fn new_session(&self, dbuser: &str) -> HdbResult<Box<dyn TokenSession>>;
+}
+
+/// The client side of a single logon with a [`TokenProvider`](trait.TokenProvider.html).
+///
+/// The logon consists of two request-reply roundtrips:
+///
+/// - the first request contains the [`initial_token()`](#tymethod.initial_token),
+/// - the server answers with a challenge, which is evaluated by
+///   [`final_token()`](#tymethod.final_token),
+/// - the second request contains the final token,
+/// - the server's answer is checked with [`verify_server()`](#method.verify_server).
+pub trait TokenSession {
+    /// Produces the data that are sent with the first request.
+    fn initial_token(&mut self) -> HdbResult<Vec<u8>>;
+
+    /// Evaluates the server's answer to the first request, and produces the data
+    /// that are sent with the second request.
+    fn final_token(&mut self, server_challenge: &[u8]) -> HdbResult<Vec<u8>>;
+
+    /// Evaluates the server's answer to the second request.
+    ///
+    /// The default implementation accepts every answer.
+    fn verify_server(&mut self, _server_proof: &[u8]) -> HdbResult<()> {
+        Ok(())
+    }
+}
+
+// Makes a TokenSession usable as Authenticator.
+pub struct ProvidedToken {
+    provider: Arc<dyn TokenProvider>,
+    session: Box<dyn TokenSession>,
+    initial_token: Vec<u8>,
+}
+impl ProvidedToken {
+    pub fn boxed_authenticator(
+        provider: Arc<dyn TokenProvider>,
+        dbuser: &str,
+    ) -> HdbResult<Box<dyn Authenticator>> {
+        let mut session = provider.new_session(dbuser)?;
+        let initial_token = session.initial_token()?;
+        Ok(Box::new(ProvidedToken {
+            provider,
+            session,
+            initial_token,
+        }))
+    }
+}
+impl Authenticator for ProvidedToken {
+    fn name(&self) -> &str {
+        self.provider.method_name()
+    }
+
+    fn name_as_bytes(&self) -> Vec<u8> {
+        self.name().as_bytes().to_owned()
+    }
+
+    fn client_challenge(&self) -> &[u8] {
+        &self.initial_token
+    }
+
+    fn client_proof(&mut self, server_challenge_data: &[u8]) -> HdbResult<Vec<u8>> {
+        self.session.final_token(server_challenge_data)
+    }
+
+    fn verify_server(&mut self, server_proof: &[u8]) -> HdbResult<()> {
+        self.session.verify_server(server_proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProvidedToken, TokenProvider, TokenSession};
+    use crate::{ConnectParams, HdbError, HdbResult};
+    use std::sync::Arc;
+
+    // Answers the challenge "ping" with "pong", and expects the server to confirm
+    // with "ok" - but only once per logon.
+    #[derive(Debug)]
+    struct PingPong;
+    impl TokenProvider for PingPong {
+        fn method_name(&self) -> &str {
+            "PINGPONG"
+        }
+        fn new_session(&self, dbuser: &str) -> HdbResult<Box<dyn TokenSession>> {
+            Ok(Box::new(PingPongSession {
+                dbuser: dbuser.to_owned(),
+                answered: false,
+            }))
+        }
+    }
+
+    struct PingPongSession {
+        dbuser: String,
+        answered: bool,
+    }
+    impl TokenSession for PingPongSession {
+        fn initial_token(&mut self) -> HdbResult<Vec<u8>> {
+            Ok(format!("hello {}", self.dbuser).into_bytes())
+        }
+        fn final_token(&mut self, server_challenge: &[u8]) -> HdbResult<Vec<u8>> {
+            match server_challenge {
+                b"ping" if !self.answered => {
+                    self.answered = true;
+                    Ok(b"pong".to_vec())
+                }
+                _ => Err(HdbError::Usage("unexpected challenge".to_owned())),
+            }
+        }
+        fn verify_server(&mut self, server_proof: &[u8]) -> HdbResult<()> {
+            match server_proof {
+                b"ok" if self.answered => Ok(()),
+                _ => Err(HdbError::Usage("server not verified".to_owned())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_provided_token() {
+        let mut a11r = ProvidedToken::boxed_authenticator(Arc::new(PingPong), "MEIER").unwrap();
+        assert_eq!(a11r.name(), "PINGPONG");
+        assert_eq!(a11r.name_as_bytes(), b"PINGPONG".to_vec());
+        assert_eq!(a11r.client_challenge(), b"hello MEIER");
+
+        assert_eq!(a11r.client_proof(b"ping").unwrap(), b"pong".to_vec());
+        assert!(a11r.client_proof(b"ping").is_err());
+
+        assert!(a11r.evaluate_second_response(b"PINGPONG", b"ok").is_ok());
+        assert!(a11r.evaluate_second_response(b"PINGPONG", b"nok").is_err());
+        assert!(a11r
+            .evaluate_second_response(b"SCRAMSHA256", b"ok")
+            .is_err());
+
+        // a new logon starts from scratch
+        let provider: Arc<dyn TokenProvider> = Arc::new(PingPong);
+        let mut a11r = ProvidedToken::boxed_authenticator(Arc::clone(&provider), "MEIER").unwrap();
+        assert!(a11r.evaluate_second_response(b"PINGPONG", b"ok").is_err());
+        let mut a11r = ProvidedToken::boxed_authenticator(provider, "MUELLER").unwrap();
+        assert_eq!(a11r.client_challenge(), b"hello MUELLER");
+        assert_eq!(a11r.client_proof(b"ping").unwrap(), b"pong".to_vec());
+    }
+
+    #[test]
+    fn test_token_provider_params() {
+        // no password is needed
+        let params = ConnectParams::builder()
+            .hostname("abcd123")
+            .port(2222)
+            .token_provider(PingPong)
+            .build()
+            .unwrap();
+        assert_eq!(params.token_providers().len(), 1);
+        assert_eq!(params.token_providers()[0].method_name(), "PINGPONG");
+    }
+}
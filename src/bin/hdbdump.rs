@@ -0,0 +1,31 @@
+//! Writes captured HANA wire protocol data in readable form to stdout.
+//!
+//! Usage: `hdbdump <file>`
+//!
+//! The file can be a trace file that was recorded by `hdbconnect`,
+//! or the raw TCP payload of one direction of a connection, as it can be extracted
+//! from a tcpdump capture (e.g. with Wireshark's "Follow TCP Stream", saved as raw data).
+//! See `hdbconnect::dump_packets()`.
+use std::{env, fs, io, process};
+
+fn main() {
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: hdbdump <file>");
+            process::exit(2);
+        }
+    };
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("cannot read {}: {}", path, e);
+            process::exit(1);
+        }
+    };
+    let stdout = io::stdout();
+    if let Err(e) = hdbconnect::dump_packets(&data, &mut stdout.lock()) {
+        eprintln!("decoding failed: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::conn_core::connect_params::ConnectParams;
+use crate::connection::Connection;
+use crate::HdbResult;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A thread-safe handle for cancelling the statement that is currently executed
+/// on a connection.
+///
+/// The cancellation is done from a separate connection with the same connect parameters,
+/// which issues `ALTER SYSTEM CANCEL SESSION` with the id of the original connection.
+/// The separate connection is opened with the first call to `cancel()` and then reused.
+/// The database user needs the privilege to cancel the session.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+/// # use std::thread;
+/// # fn foo() -> HdbResult<()> {
+/// # let mut connection = Connection::new("".into_connect_params()?)?;
+/// let cancel_handle = connection.cancel_handle()?;
+/// thread::spawn(move || cancel_handle.cancel());
+/// let result = connection.query("select * from A_VERY_LARGE_TABLE");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CancelHandle {
+    params: ConnectParams,
+    connection_id: Arc<AtomicI32>,
+    o_side_connection: Arc<Mutex<Option<Connection>>>,
+}
+
+impl CancelHandle {
+    pub(crate) fn new(params: ConnectParams, connection_id: Arc<AtomicI32>) -> CancelHandle {
+        CancelHandle {
+            params,
+            connection_id,
+            o_side_connection: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Cancels the statement that is currently executed on the connection, if any.
+    ///
+    /// The cancelled statement returns with an error from the database server.
+    pub fn cancel(&self) -> HdbResult<()> {
+        let connection_id = self.connection_id.load(Ordering::SeqCst);
+        debug!("CancelHandle::cancel() for connection {}", connection_id);
+        let mut o_side_connection = self.o_side_connection.lock()?;
+        if o_side_connection.is_none() {
+            *o_side_connection = Some(Connection::new(self.params.clone())?);
+        }
+        if let Some(ref mut side_connection) = *o_side_connection {
+            side_connection.exec(format!("ALTER SYSTEM CANCEL SESSION '{}'", connection_id))?;
+        }
+        Ok(())
+    }
+}
@@ -14,16 +14,33 @@ use std::sync::{Arc, LockResult, Mutex, MutexGuard};
 
 // A thread-safe encapsulation of the ConnectionCore.
 #[derive(Debug)]
-pub(crate) struct AmConnCore(Arc<Mutex<ConnectionCore>>);
+pub(crate) struct AmConnCore {
+    am_conn_core: Arc<Mutex<ConnectionCore>>,
+    // read while parsing replies, when the ConnectionCore is locked
+    #[cfg(feature = "rust_decimal")]
+    rust_decimal: bool,
+}
 
 impl AmConnCore {
     pub fn try_new(conn_params: ConnectParams) -> HdbResult<AmConnCore> {
+        #[cfg(feature = "rust_decimal")]
+        let rust_decimal = conn_params.rust_decimal();
         let conn_core = ConnectionCore::try_new(conn_params)?;
-        Ok(AmConnCore(Arc::new(Mutex::new(conn_core))))
+        Ok(AmConnCore {
+            am_conn_core: Arc::new(Mutex::new(conn_core)),
+            #[cfg(feature = "rust_decimal")]
+            rust_decimal,
+        })
     }
 
     pub fn lock(&self) -> LockResult<MutexGuard<ConnectionCore>> {
-        self.0.lock()
+        self.am_conn_core.lock()
+    }
+
+    // Whether decimal values are to be parsed into HdbValue::RUST_DECIMAL.
+    #[cfg(feature = "rust_decimal")]
+    pub fn is_rust_decimal(&self) -> bool {
+        self.rust_decimal
     }
 
     pub fn send(&mut self, request: Request) -> HdbResult<Reply> {
@@ -44,9 +61,15 @@ impl AmConnCore {
         let _start = Local::now();
         let mut conn_core = self.lock()?;
 
-        match conn_core.statement_sequence() {
-            None => {}
-            Some(ssi_value) => request.add_statement_context(*ssi_value),
+        let o_query_timeout = if request.request_type.is_execution() {
+            request
+                .query_timeout()
+                .or_else(|| conn_core.query_timeout())
+        } else {
+            None
+        };
+        if conn_core.statement_sequence().is_some() || o_query_timeout.is_some() {
+            request.add_statement_context(*conn_core.statement_sequence(), o_query_timeout);
         }
 
         if conn_core.is_client_info_touched() {
@@ -56,7 +79,7 @@ impl AmConnCore {
             ));
         }
 
-        let reply = conn_core.roundtrip(request, &self, o_rs_md, o_par_md, o_rs)?;
+        let reply = conn_core.roundtrip(request, Some(self), o_rs_md, o_par_md, o_rs)?;
 
         debug!(
             "AmConnCore::full_send() took {} ms",
@@ -68,6 +91,10 @@ impl AmConnCore {
 
 impl Clone for AmConnCore {
     fn clone(&self) -> AmConnCore {
-        AmConnCore(self.0.clone())
+        AmConnCore {
+            am_conn_core: self.am_conn_core.clone(),
+            #[cfg(feature = "rust_decimal")]
+            rust_decimal: self.rust_decimal,
+        }
     }
 }
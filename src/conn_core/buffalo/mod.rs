@@ -8,59 +8,98 @@ use crate::conn_core::buffalo::plain_connection::PlainConnection;
 #[cfg(feature = "tls")]
 use crate::conn_core::buffalo::tls_connection::TlsConnection;
 use crate::conn_core::connect_params::ConnectParams;
+use crate::conn_core::trace::TracingTransport;
+use crate::conn_core::transport::Transport;
 use chrono::Local;
+use socket2::{SockRef, TcpKeepalive};
 use std::io;
+use std::net::{TcpStream, ToSocketAddrs};
 
-/// A buffered tcp connection, with or without TLS.
-#[derive(Debug)]
-pub enum Buffalo {
-    /// A buffered tcp connection without TLS.
-    Plain(PlainConnection),
-    /// A buffered tcp connection with TLS.
-    #[cfg(feature = "tls")]
-    Secure(TlsConnection),
-}
-impl Buffalo {
-    /// Constructs a buffered tcp connection, with or without TLS,
-    /// depending on the given connect parameters.
-    pub fn try_new(params: ConnectParams) -> io::Result<Buffalo> {
-        let start = Local::now();
-        trace!("Connecting to {:?})", params.addr());
+/// Opens a buffered connection, depending on the given connect parameters either
+/// with the application-provided transport, or with plain tcp or TLS;
+/// if a trace file is configured, the traffic is recorded.
+pub(crate) fn try_new(params: ConnectParams) -> io::Result<Box<dyn Transport>> {
+    let start = Local::now();
+    trace!("Connecting to {:?})", params.addr());
 
+    let o_tracer = params.tracer().cloned();
+    let mut transport: Box<dyn Transport> = if let Some(connector) = params.transport_connector() {
+        (connector.0)(&params)?
+    } else if params.use_tls() {
         #[cfg(feature = "tls")]
-        let buffalo = if params.use_tls() {
-            Buffalo::Secure(TlsConnection::try_new(params)?)
-        } else {
-            Buffalo::Plain(PlainConnection::try_new(params)?)
-        };
+        {
+            Box::new(TlsConnection::try_new(params)?)
+        }
 
         #[cfg(not(feature = "tls"))]
-        let buffalo = if params.use_tls() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "In order to use TLS connections, please compile hdbconnect with feature TLS",
-            ));
-        } else {
-            Buffalo::Plain(PlainConnection::try_new(params)?)
-        };
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "In order to use TLS connections, please compile hdbconnect with feature TLS",
+        ));
+    } else {
+        Box::new(PlainConnection::try_new(params)?)
+    };
+    if let Some(tracer) = o_tracer {
+        transport = Box::new(TracingTransport::new(transport, tracer));
+    }
 
-        trace!(
-            "Connection of type {} is initialized ({} µs)",
-            buffalo.s_type(),
-            Local::now()
-                .signed_duration_since(start)
-                .num_microseconds()
-                .unwrap_or(-1)
-        );
-        Ok(buffalo)
+    trace!(
+        "Connection of type {} is initialized ({} µs)",
+        transport.s_type(),
+        Local::now()
+            .signed_duration_since(start)
+            .num_microseconds()
+            .unwrap_or(-1)
+    );
+    Ok(transport)
+}
+
+fn tcp_is_alive(tcpstream: &TcpStream) -> bool {
+    if tcpstream.set_nonblocking(true).is_err() {
+        return false;
     }
+    let mut buf = [0_u8; 1];
+    let alive = matches!(
+        tcpstream.peek(&mut buf),
+        Err(ref e) if e.kind() == io::ErrorKind::WouldBlock
+    );
+    tcpstream.set_nonblocking(false).is_ok() && alive
+}
 
-    /// Returns a descriptor of the chosen type
-    pub fn s_type(&self) -> &'static str {
-        match self {
-            Buffalo::Plain(_) => "Plain TCP",
-            #[cfg(feature = "tls")]
-            Buffalo::Secure(_) => "TLS",
+// Opens a TcpStream to the address of the given ConnectParams, and applies their
+// timeout and keepalive settings.
+fn connect_tcp_stream(params: &ConnectParams) -> io::Result<TcpStream> {
+    let tcpstream = match params.connect_timeout() {
+        None => TcpStream::connect(params.addr())?,
+        Some(timeout) => {
+            let mut last_error = None;
+            let mut o_tcpstream = None;
+            for sock_addr in params.addr().to_socket_addrs()? {
+                match TcpStream::connect_timeout(&sock_addr, timeout) {
+                    Ok(tcpstream) => {
+                        o_tcpstream = Some(tcpstream);
+                        break;
+                    }
+                    Err(e) => last_error = Some(e),
+                }
+            }
+            match o_tcpstream {
+                Some(tcpstream) => tcpstream,
+                None => {
+                    return Err(last_error.unwrap_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("Could not resolve {}", params.addr()),
+                        )
+                    }))
+                }
+            }
         }
+    };
+    tcpstream.set_read_timeout(params.read_timeout())?;
+    tcpstream.set_write_timeout(params.write_timeout())?;
+    if let Some(idle_time) = params.tcp_keepalive() {
+        SockRef::from(&tcpstream).set_tcp_keepalive(&TcpKeepalive::new().with_time(idle_time))?;
     }
+    Ok(tcpstream)
 }
@@ -1,40 +1,40 @@
+use crate::conn_core::buffalo::{connect_tcp_stream, tcp_is_alive};
 use crate::conn_core::connect_params::ConnectParams;
-use std::cell::RefCell;
+use crate::conn_core::transport::Transport;
 use std::io;
 use std::net::TcpStream;
 
 #[derive(Debug)]
 pub struct PlainConnection {
-    params: ConnectParams,
-    reader: RefCell<io::BufReader<TcpStream>>,
-    writer: RefCell<io::BufWriter<TcpStream>>,
+    reader: io::BufReader<TcpStream>,
+    writer: io::BufWriter<TcpStream>,
 }
 
 impl PlainConnection {
     /// Returns an initialized plain tcp connection
     pub fn try_new(params: ConnectParams) -> io::Result<(PlainConnection)> {
-        let tcpstream = TcpStream::connect(params.addr())?;
+        let tcpstream = connect_tcp_stream(&params)?;
         Ok(PlainConnection {
-            params,
-            writer: RefCell::new(io::BufWriter::new(tcpstream.try_clone()?)),
-            reader: RefCell::new(io::BufReader::new(tcpstream)),
+            writer: io::BufWriter::new(tcpstream.try_clone()?),
+            reader: io::BufReader::new(tcpstream),
         })
     }
+}
+
+impl Transport for PlainConnection {
+    fn writer(&mut self) -> &mut dyn io::Write {
+        &mut self.writer
+    }
 
-    pub fn writer(&self) -> &RefCell<io::BufWriter<TcpStream>> {
-        &self.writer
+    fn reader(&mut self) -> &mut dyn io::BufRead {
+        &mut self.reader
     }
 
-    pub fn reader(&self) -> &RefCell<io::BufReader<TcpStream>> {
-        &self.reader
+    fn is_alive(&self) -> bool {
+        self.reader.buffer().is_empty() && tcp_is_alive(self.reader.get_ref())
     }
 
-    #[allow(dead_code)]
-    pub fn reconnect(&self) -> io::Result<()> {
-        let tcpstream = TcpStream::connect(self.params.addr())?;
-        self.writer
-            .replace(io::BufWriter::new(tcpstream.try_clone()?));
-        self.reader.replace(io::BufReader::new(tcpstream));
-        Ok(())
+    fn s_type(&self) -> &'static str {
+        "Plain TCP"
     }
 }
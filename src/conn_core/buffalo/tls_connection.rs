@@ -1,13 +1,13 @@
 use crate::conn_core::buffalo::tls_stream::TlsStream;
 use crate::conn_core::connect_params::ConnectParams;
-use std::cell::RefCell;
+use crate::conn_core::transport::Transport;
 use std::fmt;
 use std::io;
 
 pub struct TlsConnection {
     params: ConnectParams,
-    reader: RefCell<io::BufReader<TlsStream>>,
-    writer: RefCell<io::BufWriter<TlsStream>>,
+    reader: io::BufReader<TlsStream>,
+    writer: io::BufWriter<TlsStream>,
 }
 impl fmt::Debug for TlsConnection {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -19,25 +19,26 @@ impl TlsConnection {
         let tlsstream = TlsStream::try_new(&params)?;
         Ok(TlsConnection {
             params,
-            reader: RefCell::new(io::BufReader::new(tlsstream.try_clone()?)),
-            writer: RefCell::new(io::BufWriter::new(tlsstream)),
+            reader: io::BufReader::new(tlsstream.try_clone()?),
+            writer: io::BufWriter::new(tlsstream),
         })
     }
+}
+
+impl Transport for TlsConnection {
+    fn writer(&mut self) -> &mut dyn io::Write {
+        &mut self.writer
+    }
 
-    #[allow(dead_code)]
-    pub fn reconnect(&self) -> io::Result<()> {
-        let tlsstream = TlsStream::try_new(&self.params)?;
-        self.reader
-            .replace(io::BufReader::new(tlsstream.try_clone()?));
-        self.writer.replace(io::BufWriter::new(tlsstream));
-        Ok(())
+    fn reader(&mut self) -> &mut dyn io::BufRead {
+        &mut self.reader
     }
 
-    pub fn writer(&self) -> &RefCell<io::BufWriter<TlsStream>> {
-        &self.writer
+    fn is_alive(&self) -> bool {
+        self.reader.buffer().is_empty() && self.reader.get_ref().is_alive()
     }
 
-    pub fn reader(&self) -> &RefCell<io::BufReader<TlsStream>> {
-        &self.reader
+    fn s_type(&self) -> &'static str {
+        "TLS"
     }
 }
@@ -1,6 +1,9 @@
+use crate::conn_core::buffalo::{connect_tcp_stream, tcp_is_alive};
 use crate::conn_core::connect_params::{ConnectParams, ServerCerts};
-use rustls::ClientConfig;
-use rustls::{ClientSession, Session};
+use rustls::{
+    Certificate, ClientConfig, ClientSession, RootCertStore, ServerCertVerified,
+    ServerCertVerifier, Session, TLSError,
+};
 use std::env;
 use std::fs::{read_dir, File};
 use std::io::{self, Cursor};
@@ -8,6 +11,7 @@ use std::net::TcpStream;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use webpki::DNSNameRef;
+use webpki_roots::TLS_SERVER_ROOTS;
 
 pub struct TlsStream {
     is_handshaking: bool,
@@ -25,6 +29,10 @@ impl TlsStream {
             tlssession: Arc::new(Mutex::new(tlssession)),
         })
     }
+    pub fn is_alive(&self) -> bool {
+        tcp_is_alive(&self.tcpstream)
+    }
+
     pub fn try_clone(&self) -> io::Result<TlsStream> {
         Ok(TlsStream {
             is_handshaking: false,
@@ -40,18 +48,36 @@ fn connect_tcp(
 ) -> io::Result<(TcpStream, Arc<ClientConfig>, ClientSession)> {
     debug!("connect_tcp(): Connecting to {:?}", params.addr());
 
-    let tcpstream = TcpStream::connect(params.addr())?;
+    let tcpstream = connect_tcp_stream(params)?;
 
     trace!("tcpstream working");
 
-    let mut config = ClientConfig::new();
+    let mut config = match params.tls_config() {
+        Some(tls_config) => ClientConfig::clone(tls_config),
+        None => ClientConfig::new(),
+    };
     match params.server_certs() {
+        None if params.tls_config().is_some() => {}
         None => {
             return Err(io::Error::new(
                 io::ErrorKind::Other,
                 "No server certificates provided",
             ))
         }
+        Some(ServerCerts::RootCertificates) => {
+            config
+                .root_store
+                .add_server_trust_anchors(&TLS_SERVER_ROOTS);
+        }
+        Some(ServerCerts::None) => {
+            warn!(
+                "The server's certificate is NOT VERIFIED! \
+                 The connection is vulnerable to man-in-the-middle attacks!"
+            );
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(NoCertificateVerification));
+        }
         Some(ServerCerts::Direct(pem)) => {
             let mut cursor = Cursor::new(pem);
             let (n_ok, n_err) = config.root_store.add_pem_file(&mut cursor).unwrap();
@@ -129,14 +155,20 @@ fn connect_tcp(
         }
     }
 
+    if let Some(client_cert) = params.client_cert() {
+        let (cert_chain, private_key) = client_cert.load()?;
+        config.set_single_client_cert(cert_chain, private_key);
+    }
+
     let tlsconfig = Arc::new(config);
 
+    let server_name = params.tls_server_name().unwrap_or_else(|| params.host());
     let tlssession = ClientSession::new(
         &tlsconfig,
-        DNSNameRef::try_from_ascii_str(params.host()).map_err(|_| {
+        DNSNameRef::try_from_ascii_str(server_name).map_err(|_| {
             io::Error::new(
                 io::ErrorKind::Other,
-                format!("Cannot use {} for DNSNameRef", params.host()),
+                format!("Cannot use {} for DNSNameRef", server_name),
             )
         })?,
     );
@@ -144,6 +176,20 @@ fn connect_tcp(
     Ok((tcpstream, tlsconfig, tlssession))
 }
 
+// Accepts every server certificate.
+struct NoCertificateVerification;
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _roots: &RootCertStore,
+        _presented_certs: &[Certificate],
+        _dns_name: DNSNameRef,
+        _ocsp_response: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
 impl io::Write for TlsStream {
     fn write(&mut self, request: &[u8]) -> io::Result<usize> {
         trace!("io::Write::write() with request size {}", request.len());
@@ -1,10 +1,19 @@
-use crate::conn_core::connect_params::ConnectParams;
+use crate::authentication::TokenProvider;
+use crate::conn_core::connect_params::{non_zero, ConnectParams};
 #[cfg(feature = "tls")]
-use crate::conn_core::connect_params::ServerCerts;
+use crate::conn_core::connect_params::{ClientCert, ServerCerts, TlsConfig};
+use crate::conn_core::trace::Tracer;
+use crate::conn_core::transport::{Transport, TransportConnector};
 use crate::{HdbError, HdbResult};
+#[cfg(feature = "tls")]
+use rustls::ClientConfig;
 use secstr::SecStr;
 use std::env;
+use std::io;
 use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 /// A builder for `ConnectParams`.
 ///
@@ -27,12 +36,37 @@ use std::mem;
 pub struct ConnectParamsBuilder {
     hostname: Option<String>,
     port: Option<u16>,
+    failover: Vec<(String, String)>,
+    randomize_hosts: bool,
     dbuser: Option<String>,
     password: Option<String>,
+    jwt: Option<String>,
+    saml_assertion: Option<String>,
+    session_cookie: Option<Vec<u8>>,
+    token_providers: Vec<Arc<dyn TokenProvider>>,
     clientlocale: Option<String>,
     #[cfg(feature = "tls")]
     server_certs: Option<ServerCerts>,
+    #[cfg(feature = "tls")]
+    tls_server_name: Option<String>,
+    #[cfg(feature = "tls")]
+    client_cert: Option<ClientCert>,
+    #[cfg(feature = "tls")]
+    tls_config: Option<TlsConfig>,
     options: Vec<(String, String)>,
+    reconnect: bool,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+    transport_connector: Option<TransportConnector>,
+    trace_file: Option<PathBuf>,
+    #[cfg(feature = "compression")]
+    compression: bool,
+    #[cfg(feature = "ldap")]
+    ldap: bool,
+    #[cfg(feature = "rust_decimal")]
+    rust_decimal: bool,
 }
 
 impl ConnectParamsBuilder {
@@ -41,12 +75,37 @@ impl ConnectParamsBuilder {
         ConnectParamsBuilder {
             hostname: None,
             port: None,
+            failover: vec![],
+            randomize_hosts: false,
             dbuser: None,
             password: None,
+            jwt: None,
+            saml_assertion: None,
+            session_cookie: None,
+            token_providers: vec![],
             clientlocale: None,
             #[cfg(feature = "tls")]
             server_certs: None,
+            #[cfg(feature = "tls")]
+            tls_server_name: None,
+            #[cfg(feature = "tls")]
+            client_cert: None,
+            #[cfg(feature = "tls")]
+            tls_config: None,
             options: vec![],
+            reconnect: false,
+            connect_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+            tcp_keepalive: None,
+            transport_connector: None,
+            trace_file: None,
+            #[cfg(feature = "compression")]
+            compression: false,
+            #[cfg(feature = "ldap")]
+            ldap: false,
+            #[cfg(feature = "rust_decimal")]
+            rust_decimal: false,
         }
     }
 
@@ -62,6 +121,26 @@ impl ConnectParamsBuilder {
         self
     }
 
+    /// Adds a failover address.
+    ///
+    /// See [`ConnectParams::addrs()`](struct.ConnectParams.html#method.addrs).
+    pub fn failover_host<H: AsRef<str>>(
+        &mut self,
+        hostname: H,
+        port: u16,
+    ) -> &mut ConnectParamsBuilder {
+        let hostname = hostname.as_ref().to_owned();
+        let addr = format!("{}:{}", hostname, port);
+        self.failover.push((hostname, addr));
+        self
+    }
+
+    /// Makes the driver try the addresses in random order.
+    pub fn randomize_hosts(&mut self, randomize_hosts: bool) -> &mut ConnectParamsBuilder {
+        self.randomize_hosts = randomize_hosts;
+        self
+    }
+
     /// Sets the database user.
     pub fn dbuser<D: AsRef<str>>(&mut self, dbuser: D) -> &mut ConnectParamsBuilder {
         self.dbuser = Some(dbuser.as_ref().to_owned());
@@ -74,6 +153,56 @@ impl ConnectParamsBuilder {
         self
     }
 
+    /// Makes the driver use the password for LDAP authentication.
+    ///
+    /// See [`ConnectParams::ldap()`](struct.ConnectParams.html#method.ldap).
+    ///
+    /// This method is only available with feature `ldap`.
+    #[cfg(feature = "ldap")]
+    pub fn ldap(&mut self, ldap: bool) -> &mut ConnectParamsBuilder {
+        self.ldap = ldap;
+        self
+    }
+
+    /// Sets a JSON Web Token for authentication.
+    ///
+    /// With a token, dbuser and password are optional.
+    /// See [`ConnectParams::jwt()`](struct.ConnectParams.html#method.jwt).
+    pub fn jwt<T: AsRef<str>>(&mut self, token: T) -> &mut ConnectParamsBuilder {
+        self.jwt = Some(token.as_ref().to_owned());
+        self
+    }
+
+    /// Sets a SAML bearer assertion for authentication.
+    ///
+    /// With an assertion, dbuser and password are optional.
+    /// See [`ConnectParams::saml_assertion()`](struct.ConnectParams.html#method.saml_assertion).
+    pub fn saml_assertion<A: AsRef<str>>(&mut self, assertion: A) -> &mut ConnectParamsBuilder {
+        self.saml_assertion = Some(assertion.as_ref().to_owned());
+        self
+    }
+
+    /// Sets a session cookie for authentication.
+    ///
+    /// With a session cookie, the password is optional.
+    /// See [`ConnectParams::session_cookie()`](struct.ConnectParams.html#method.session_cookie).
+    pub fn session_cookie<C: AsRef<[u8]>>(&mut self, cookie: C) -> &mut ConnectParamsBuilder {
+        self.session_cookie = Some(cookie.as_ref().to_vec());
+        self
+    }
+
+    /// Adds an application-provided authentication method.
+    ///
+    /// With a token provider, dbuser and password are optional.
+    /// See [`TokenProvider`](trait.TokenProvider.html).
+    pub fn token_provider<P: TokenProvider + 'static>(
+        &mut self,
+        provider: P,
+    ) -> &mut ConnectParamsBuilder {
+        self.token_providers.push(Arc::new(provider));
+        self
+    }
+
     /// Sets the client locale.
     pub fn clientlocale<P: AsRef<str>>(&mut self, cl: P) -> &mut ConnectParamsBuilder {
         self.clientlocale = Some(cl.as_ref().to_owned());
@@ -113,6 +242,125 @@ impl ConnectParamsBuilder {
         self
     }
 
+    /// Makes the driver use TLS with the given configuration.
+    ///
+    /// This allows controlling all aspects of the TLS handshake, like cipher suites,
+    /// ALPN protocols, SNI, or the verification of the server certificate.
+    /// The server certificates from [`tls_with()`](#method.tls_with)
+    /// are then optional; if they are given, they are added to the configuration's root store.
+    ///
+    /// This method is only available with feature `tls`.
+    #[cfg(feature = "tls")]
+    pub fn tls_config(&mut self, tls_config: Arc<ClientConfig>) -> &mut ConnectParamsBuilder {
+        self.tls_config = Some(TlsConfig(tls_config));
+        self
+    }
+
+    /// Sets the name with which the server's certificate is verified,
+    /// if it differs from the hostname.
+    ///
+    /// See [`ConnectParams::tls_server_name()`](struct.ConnectParams.html#method.tls_server_name).
+    ///
+    /// This method is only available with feature `tls`.
+    #[cfg(feature = "tls")]
+    pub fn tls_server_name<S: AsRef<str>>(&mut self, server_name: S) -> &mut ConnectParamsBuilder {
+        self.tls_server_name = Some(server_name.as_ref().to_owned());
+        self
+    }
+
+    /// Makes the driver present a client certificate in the TLS handshake
+    /// and use it for the logon (X.509 authentication).
+    ///
+    /// With a client certificate, dbuser and password are optional.
+    /// See [`ConnectParams::client_cert()`](struct.ConnectParams.html#method.client_cert).
+    ///
+    /// This method is only available with feature `tls`.
+    #[cfg(feature = "tls")]
+    pub fn tls_client_cert(&mut self, client_cert: ClientCert) -> &mut ConnectParamsBuilder {
+        self.client_cert = Some(client_cert);
+        self
+    }
+
+    /// Makes the driver re-establish the connection automatically after a network failure.
+    ///
+    /// See [`ConnectParams::reconnect()`](struct.ConnectParams.html#method.reconnect).
+    pub fn reconnect(&mut self, reconnect: bool) -> &mut ConnectParamsBuilder {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Sets the timeout for establishing the TCP connection;
+    /// a zero duration switches the timeout off.
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut ConnectParamsBuilder {
+        self.connect_timeout = non_zero(timeout);
+        self
+    }
+
+    /// Sets the timeout for reading from the TCP connection;
+    /// a zero duration switches the timeout off.
+    pub fn read_timeout(&mut self, timeout: Duration) -> &mut ConnectParamsBuilder {
+        self.read_timeout = non_zero(timeout);
+        self
+    }
+
+    /// Sets the timeout for writing to the TCP connection;
+    /// a zero duration switches the timeout off.
+    pub fn write_timeout(&mut self, timeout: Duration) -> &mut ConnectParamsBuilder {
+        self.write_timeout = non_zero(timeout);
+        self
+    }
+
+    /// Switches TCP keepalive on, with the given idle time before the first probe;
+    /// a zero duration switches TCP keepalive off.
+    pub fn tcp_keepalive(&mut self, idle_time: Duration) -> &mut ConnectParamsBuilder {
+        self.tcp_keepalive = non_zero(idle_time);
+        self
+    }
+
+    /// Makes the driver ask the server to use LZ4 compression.
+    ///
+    /// See [`ConnectParams::compression()`](struct.ConnectParams.html#method.compression).
+    ///
+    /// This method is only available with feature `compression`.
+    #[cfg(feature = "compression")]
+    pub fn compression(&mut self, compression: bool) -> &mut ConnectParamsBuilder {
+        self.compression = compression;
+        self
+    }
+
+    /// Makes the driver parse decimal values into `rust_decimal::Decimal`s.
+    ///
+    /// See [`ConnectParams::rust_decimal()`](struct.ConnectParams.html#method.rust_decimal).
+    ///
+    /// This method is only available with feature `rust_decimal`.
+    #[cfg(feature = "rust_decimal")]
+    pub fn rust_decimal(&mut self, rust_decimal: bool) -> &mut ConnectParamsBuilder {
+        self.rust_decimal = rust_decimal;
+        self
+    }
+
+    /// Makes the driver use an application-provided transport instead of a
+    /// plain tcp or TLS connection.
+    ///
+    /// The given function is called whenever a connection to the database is opened
+    /// (also for reconnects and failover); it gets the `ConnectParams`
+    /// with the address to connect to. See [`Transport`](trait.Transport.html).
+    pub fn transport<F>(&mut self, connect: F) -> &mut ConnectParamsBuilder
+    where
+        F: Fn(&ConnectParams) -> io::Result<Box<dyn Transport>> + Send + Sync + 'static,
+    {
+        self.transport_connector = Some(TransportConnector(Arc::new(connect)));
+        self
+    }
+
+    /// Makes the driver record the protocol traffic into the given file.
+    ///
+    /// See [`ConnectParams::trace_file()`](struct.ConnectParams.html#method.trace_file).
+    pub fn trace_file<P: AsRef<Path>>(&mut self, path: P) -> &mut ConnectParamsBuilder {
+        self.trace_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
     /// Adds a runtime parameter.
     pub fn option<'a>(&'a mut self, name: &str, value: &str) -> &'a mut ConnectParamsBuilder {
         self.options.push((name.to_string(), value.to_string()));
@@ -121,7 +369,7 @@ impl ConnectParamsBuilder {
 
     /// Constructs a `ConnectParams` from the builder.
     pub fn build(&mut self) -> HdbResult<ConnectParams> {
-        Ok(ConnectParams {
+        let params = ConnectParams {
             host: match self.hostname {
                 Some(ref s) => s.clone(),
                 None => return Err(HdbError::Usage("hostname is missing".to_owned())),
@@ -137,25 +385,52 @@ impl ConnectParamsBuilder {
                     None => return Err(HdbError::Usage("port is missing".to_owned())),
                 }
             ),
-            dbuser: match self.dbuser {
-                Some(_) => self.dbuser.take().unwrap(),
-                None => return Err(HdbError::Usage("dbuser is missing".to_owned())),
-            },
-            password: match self.password {
-                Some(_) => SecStr::from(self.password.take().unwrap()),
-                None => return Err(HdbError::Usage("password is missing".to_owned())),
-            },
+            failover: self.failover.clone(),
+            randomize_hosts: self.randomize_hosts,
+            dbuser: self.dbuser.take().unwrap_or_default(),
+            password: self.password.take().map(SecStr::from),
+            jwt: self.jwt.take().map(SecStr::from),
+            saml_assertion: self.saml_assertion.take().map(SecStr::from),
+            session_cookie: self.session_cookie.take().map(SecStr::new),
+            token_providers: mem::take(&mut self.token_providers),
             clientlocale: match self.clientlocale {
                 Some(_) => Some(self.clientlocale.take().unwrap()),
                 None => None,
             },
             options: mem::replace(&mut self.options, vec![]),
+            reconnect: self.reconnect,
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+            write_timeout: self.write_timeout,
+            tcp_keepalive: self.tcp_keepalive,
+            transport_connector: self.transport_connector.clone(),
+            tracer: self
+                .trace_file
+                .clone()
+                .map(|path| Arc::new(Tracer::new(path))),
+            #[cfg(feature = "compression")]
+            compression: self.compression,
+            #[cfg(feature = "ldap")]
+            ldap: self.ldap,
+            #[cfg(feature = "rust_decimal")]
+            rust_decimal: self.rust_decimal,
 
             #[cfg(feature = "tls")]
-            use_tls: self.server_certs.is_some(),
+            use_tls: self.server_certs.is_some() || self.tls_config.is_some(),
 
             #[cfg(feature = "tls")]
             server_certs: self.server_certs.clone(),
-        })
+
+            #[cfg(feature = "tls")]
+            tls_server_name: self.tls_server_name.clone(),
+
+            #[cfg(feature = "tls")]
+            client_cert: self.client_cert.take(),
+
+            #[cfg(feature = "tls")]
+            tls_config: self.tls_config.clone(),
+        };
+        params.verify_credentials()?;
+        Ok(params)
     }
 }
@@ -1,10 +1,12 @@
+use crate::authentication;
 use crate::conn_core::am_conn_core::AmConnCore;
-use crate::conn_core::buffalo::Buffalo;
+use crate::conn_core::buffalo;
 use crate::conn_core::connect_params::ConnectParams;
 use crate::conn_core::initial_request;
-use crate::conn_core::session_state::SessionState;
+use crate::conn_core::session_state::{SessionState, TransactionState};
+use crate::conn_core::transport::Transport;
 use crate::protocol::argument::Argument;
-use crate::protocol::part::Parts;
+use crate::protocol::part::{Part, Parts};
 use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::client_info::ClientInfo;
 use crate::protocol::parts::connect_options::ConnectOptions;
@@ -17,17 +19,26 @@ use crate::protocol::parts::statement_context::StatementContext;
 use crate::protocol::parts::topology::Topology;
 use crate::protocol::parts::transactionflags::TransactionFlags;
 use crate::protocol::reply::Reply;
-use crate::protocol::request::Request;
+use crate::protocol::request::{Request, HOLD_CURSORS_OVER_COMMIT};
+use crate::protocol::request_type::RequestType;
 use crate::protocol::server_resource_consumption_info::ServerResourceConsumptionInfo;
 use crate::{HdbError, HdbResult};
-use std::io::Write;
+use secstr::SecStr;
+use std::collections::HashMap;
+use std::io;
 use std::mem;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub const DEFAULT_FETCH_SIZE: u32 = 32;
 pub const DEFAULT_LOB_READ_LENGTH: i32 = 1_000_000;
+pub const DEFAULT_LOB_WRITE_LENGTH: usize = 1_000_000;
 
 #[derive(Debug)]
 pub(crate) struct ConnectionCore {
+    params: ConnectParams,
     authenticated: bool,
     session_id: i64,
     client_info: ClientInfo,
@@ -37,20 +48,67 @@ pub(crate) struct ConnectionCore {
     server_resource_consumption_info: ServerResourceConsumptionInfo,
     fetch_size: u32,
     lob_read_length: i32,
+    lob_write_length: usize,
+    query_timeout: Option<Duration>,
     session_state: SessionState,
     statement_sequence: Option<i64>, // statement sequence within the transaction
     connect_options: ConnectOptions,
+    connection_id: Arc<AtomicI32>, // shared with CancelHandles
     topology: Option<Topology>,
+    reconnect_wait_timeout: i32, // seconds
+    reconnecting: bool,
+    session_cookie: Option<SecStr>, // from the last logon, for logging on again
+    // prepared statements that have to be prepared again after a reconnect;
+    // key: the initial statement id, value: the statement and its current statement id
+    statements: HashMap<u64, (String, u64)>,
+    #[cfg(feature = "alpha_routing")]
+    routed_sessions: HashMap<String, AmConnCore>, // key: addr
     pub warnings: Vec<ServerError>,
-    buffalo: Buffalo,
+    transport: Box<dyn Transport>,
 }
 
 impl<'a> ConnectionCore {
+    // Opens an authenticated session; the addresses of the params are tried
+    // one after the other until one of them works.
+    // Only I/O errors (including timeouts) lead to trying the next address; other
+    // errors, like a failed authentication, are returned immediately.
     pub fn try_new(params: ConnectParams) -> HdbResult<ConnectionCore> {
-        let mut buffalo = Buffalo::try_new(params)?;
-        initial_request::send_and_receive(&mut buffalo)?;
+        let mut failures = Vec::<String>::new();
+        let mut o_last_error = None;
+        for params in params.connect_sequence() {
+            let addr = params.addr().to_string();
+            match ConnectionCore::try_new_authenticated(params) {
+                Ok(conn_core) => return Ok(conn_core),
+                Err(e @ HdbError::Io(_)) | Err(e @ HdbError::Timeout(_)) => {
+                    warn!("Connecting to {} failed with {}", addr, e);
+                    failures.push(format!("{}: {}", addr, e));
+                    o_last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        match o_last_error {
+            // with a single address, the error is returned unchanged
+            Some(e) if failures.len() == 1 => Err(e),
+            // otherwise the kind of the last error is kept, and all failures are reported
+            Some(HdbError::Timeout(io_error)) => Err(HdbError::Timeout(io::Error::new(
+                io_error.kind(),
+                all_failed(&failures),
+            ))),
+            Some(HdbError::Io(io_error)) => Err(HdbError::Io(io::Error::new(
+                io_error.kind(),
+                all_failed(&failures),
+            ))),
+            _ => Err(HdbError::Usage("No address to connect to".to_owned())),
+        }
+    }
+
+    fn try_new_authenticated(params: ConnectParams) -> HdbResult<ConnectionCore> {
+        let mut transport = buffalo::try_new(params.clone()).map_err(HdbError::from_socket)?;
+        initial_request::send_and_receive(&mut *transport).map_err(HdbError::from_socket)?;
 
-        Ok(ConnectionCore {
+        let mut conn_core = ConnectionCore {
+            params,
             authenticated: false,
             session_id: 0,
             seq_number: 0,
@@ -58,15 +116,27 @@ impl<'a> ConnectionCore {
             server_resource_consumption_info: Default::default(),
             fetch_size: DEFAULT_FETCH_SIZE,
             lob_read_length: DEFAULT_LOB_READ_LENGTH,
+            lob_write_length: DEFAULT_LOB_WRITE_LENGTH,
+            query_timeout: None,
             client_info: Default::default(),
             client_info_touched: false,
             session_state: Default::default(),
             statement_sequence: None,
             connect_options: Default::default(),
+            connection_id: Arc::new(AtomicI32::new(-1)),
             topology: None,
+            reconnect_wait_timeout: 0,
+            reconnecting: false,
+            session_cookie: None,
+            statements: HashMap::new(),
+            #[cfg(feature = "alpha_routing")]
+            routed_sessions: HashMap::new(),
             warnings: Vec::<ServerError>::new(),
-            buffalo,
-        })
+            transport,
+        };
+
+        authentication::authenticate(&mut conn_core)?;
+        Ok(conn_core)
     }
 
     pub fn set_application_version(&mut self, version: &str) -> HdbResult<()> {
@@ -87,6 +157,18 @@ impl<'a> ConnectionCore {
         Ok(())
     }
 
+    #[cfg(feature = "alpha_routing")]
+    pub fn client_info(&self) -> &ClientInfo {
+        &self.client_info
+    }
+
+    #[cfg(feature = "alpha_routing")]
+    // is sent with the next request
+    pub fn set_client_info(&mut self, client_info: ClientInfo) {
+        self.client_info = client_info;
+        self.client_info_touched = true;
+    }
+
     pub fn is_client_info_touched(&self) -> bool {
         self.client_info_touched
     }
@@ -107,11 +189,13 @@ impl<'a> ConnectionCore {
             stmt_ctx.get_server_cpu_time(),
             stmt_ctx.get_server_memory_usage(),
         );
+        if let Some(timeout) = stmt_ctx.get_client_reconnection_wait_timeout() {
+            self.reconnect_wait_timeout = timeout;
+        }
         // FIXME do not ignore the other content of StatementContext
         // StatementContextId::SchemaName => 3,
         // StatementContextId::FlagSet => 4,
         // StatementContextId::QueryTimeout => 5,
-        // StatementContextId::ClientReconnectionWaitTimeout => 6,
 
         Ok(())
     }
@@ -149,6 +233,26 @@ impl<'a> ConnectionCore {
         self.lob_read_length = lob_read_length;
     }
 
+    pub fn get_lob_write_length(&self) -> usize {
+        self.lob_write_length
+    }
+
+    pub fn set_lob_write_length(&mut self, lob_write_length: usize) {
+        self.lob_write_length = lob_write_length;
+    }
+
+    // The query timeout is only used if the server does not reject it.
+    pub fn query_timeout(&self) -> Option<Duration> {
+        match self.connect_options.get_query_timeout_ok() {
+            Some(false) => None,
+            _ => self.query_timeout,
+        }
+    }
+
+    pub fn set_query_timeout(&mut self, query_timeout: Option<Duration>) {
+        self.query_timeout = query_timeout;
+    }
+
     pub fn set_session_id(&mut self, session_id: i64) {
         self.session_id = session_id;
     }
@@ -157,19 +261,80 @@ impl<'a> ConnectionCore {
         self.topology = Some(topology);
     }
 
+    #[cfg(feature = "alpha_routing")]
+    pub fn topology(&self) -> Option<&Topology> {
+        self.topology.as_ref()
+    }
+
+    pub fn connect_params(&self) -> &ConnectParams {
+        &self.params
+    }
+
+    // Message bodies are compressed only if the client asked for it
+    // and the server agreed.
+    fn is_compression_active(&self) -> bool {
+        self.params.compression()
+            && self
+                .connect_options
+                .get_compression_level_and_flags()
+                .unwrap_or(0)
+                > 0
+    }
+
+    #[cfg(feature = "alpha_routing")]
+    pub fn routed_sessions(&mut self) -> &mut HashMap<String, AmConnCore> {
+        &mut self.routed_sessions
+    }
+
+    // Remembers a prepared statement, so that it can be prepared again after a reconnect.
+    pub fn register_statement(&mut self, statement_id: u64, stmt: &str) {
+        if self.params.reconnect() {
+            self.statements
+                .insert(statement_id, (stmt.to_string(), statement_id));
+        }
+    }
+
+    pub fn unregister_statement(&mut self, statement_id: u64) {
+        self.statements.remove(&statement_id);
+    }
+
+    // The statement id that is valid in the current session.
+    pub fn current_statement_id(&self, statement_id: u64) -> u64 {
+        self.statements
+            .get(&statement_id)
+            .map(|(_, current_id)| *current_id)
+            .unwrap_or(statement_id)
+    }
+
     pub fn digest_server_connect_options(
         &mut self,
         new_conn_opts: ConnectOptions,
         old_conn_opts: ConnectOptions,
     ) -> HdbResult<()> {
         self.connect_options
-            .digest_server_connect_options(new_conn_opts, old_conn_opts)
+            .digest_server_connect_options(new_conn_opts, old_conn_opts)?;
+        self.connection_id
+            .store(self.get_connection_id(), Ordering::SeqCst);
+        Ok(())
+    }
+
+    // The connection id, which changes if the connection is re-established.
+    pub fn shared_connection_id(&self) -> Arc<AtomicI32> {
+        Arc::clone(&self.connection_id)
     }
 
     pub fn is_authenticated(&self) -> bool {
         self.authenticated
     }
 
+    pub fn session_cookie(&self) -> Option<&SecStr> {
+        self.session_cookie.as_ref()
+    }
+
+    pub fn set_session_cookie(&mut self, session_cookie: SecStr) {
+        self.session_cookie = Some(session_cookie);
+    }
+
     pub fn set_authenticated(&mut self, authenticated: bool) {
         self.authenticated = authenticated;
     }
@@ -194,6 +359,11 @@ impl<'a> ConnectionCore {
         self.seq_number
     }
 
+    #[cfg(feature = "alpha_routing")]
+    pub fn session_state(&self) -> &SessionState {
+        &self.session_state
+    }
+
     pub fn evaluate_ta_flags(&mut self, ta_flags: TransactionFlags) -> HdbResult<()> {
         self.session_state.update(ta_flags);
         if self.session_state.dead {
@@ -245,41 +415,188 @@ impl<'a> ConnectionCore {
 
     pub fn roundtrip(
         &mut self,
-        request: Request<'a>,
-        am_conn_core: &AmConnCore,
+        mut request: Request<'a>,
+        o_am_conn_core: Option<&AmConnCore>,
         o_rs_md: Option<&ResultSetMetadata>,
         o_par_md: Option<&[ParameterDescriptor]>,
         o_rs: &mut Option<&mut ResultSet>,
     ) -> HdbResult<Reply> {
-        let auto_commit_flag: i8 = if self.is_auto_commit() { 1 } else { 0 };
-        let nsn = self.next_seq_number();
+        let emitted = if self.params.reconnect() && !self.transport.is_alive() {
+            Err(HdbError::Io(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "connection was closed by the server",
+            )))
+        } else {
+            self.emit_request(&request, o_par_md)
+        };
+        if let Err(e) = emitted {
+            // the request was not (completely) sent, so it can be sent again on a new session
+            let e = self.recover(e)?;
+            let replacements = self.reconnect()?;
+            if !request.request_type.is_replayable() {
+                return Err(e);
+            }
+            request.replace_statement_ids(&replacements);
+            self.emit_request(&request, o_par_md)?;
+        }
 
-        match self.buffalo {
-            Buffalo::Plain(ref pc) => {
-                let writer = &mut *(pc.writer()).borrow_mut();
-                request.emit(self.session_id(), nsn, auto_commit_flag, o_par_md, writer)?;
+        let mut reply = match self.receive_reply(o_am_conn_core, o_rs_md, o_par_md, o_rs) {
+            Ok(reply) => reply,
+            Err(e) => {
+                // the request may have been executed, so it must not be sent again
+                let e = self.recover(e)?;
+                self.reconnect()?;
+                return Err(e);
             }
-            #[cfg(feature = "tls")]
-            Buffalo::Secure(ref sc) => {
-                let writer = &mut *(sc.writer()).borrow_mut();
-                request.emit(self.session_id(), nsn, auto_commit_flag, writer)?;
+        };
+
+        self.handle_db_error(&mut reply.parts)?;
+        Ok(reply)
+    }
+
+    fn emit_request(
+        &mut self,
+        request: &Request<'a>,
+        o_par_md: Option<&[ParameterDescriptor]>,
+    ) -> HdbResult<()> {
+        let auto_commit_flag: i8 = if self.is_auto_commit() { 1 } else { 0 };
+        let compress = self.is_compression_active();
+        let nsn = self.next_seq_number();
+        request
+            .emit(
+                self.session_id(),
+                nsn,
+                auto_commit_flag,
+                compress,
+                o_par_md,
+                &mut self.transport.writer(),
+            )
+            .map_err(HdbError::from_socket)
+    }
+
+    fn receive_reply(
+        &mut self,
+        o_am_conn_core: Option<&AmConnCore>,
+        o_rs_md: Option<&ResultSetMetadata>,
+        o_par_md: Option<&[ParameterDescriptor]>,
+        o_rs: &mut Option<&mut ResultSet>,
+    ) -> HdbResult<Reply> {
+        Reply::parse(
+            o_rs_md,
+            o_par_md,
+            o_rs,
+            o_am_conn_core,
+            &mut self.transport.reader(),
+        )
+        .map_err(HdbError::from_socket)
+    }
+
+    // Decides whether the connection can be re-established after the given error;
+    // returns the error back if so, and an error to be returned to the caller otherwise.
+    // If a write transaction was open, its loss is reported, but the connection is
+    // re-established nevertheless.
+    fn recover(&mut self, e: HdbError) -> HdbResult<HdbError> {
+        if !self.params.reconnect() || !self.authenticated || self.reconnecting {
+            return Err(e);
+        }
+        let in_write_transaction = !self.auto_commit
+            && matches!(
+                self.session_state.ta_state,
+                TransactionState::WriteTransaction
+            );
+        match e {
+            HdbError::Io(io_error) | HdbError::Timeout(io_error) if in_write_transaction => {
+                self.reconnect()?;
+                Err(HdbError::TransactionLost(io_error))
             }
+            HdbError::Io(_) | HdbError::Timeout(_) => Ok(e),
+            e => Err(e),
         }
+    }
 
-        let mut reply = match self.buffalo {
-            Buffalo::Plain(ref pc) => {
-                let reader = &mut *(pc.reader()).borrow_mut();
-                Reply::parse(o_rs_md, o_par_md, o_rs, Some(am_conn_core), reader)?
+    // Opens a new session with the same settings, and prepares the registered statements
+    // again; returns the pairs of outdated and new statement ids.
+    fn reconnect(&mut self) -> HdbResult<Vec<(u64, u64)>> {
+        warn!(
+            "Connection to {} was lost (session_id = {}), reconnecting",
+            self.params.addr(),
+            self.session_id
+        );
+        self.reconnecting = true;
+        let result = self.reconnect_impl();
+        self.reconnecting = false;
+        result
+    }
+
+    fn reconnect_impl(&mut self) -> HdbResult<Vec<(u64, u64)>> {
+        // the server tells us how long we may try
+        let deadline =
+            Instant::now() + Duration::from_secs(self.reconnect_wait_timeout.max(0) as u64);
+        let (params, transport) = 'retry: loop {
+            // the current address comes first, then the failover addresses
+            let mut o_error = None;
+            for params in self.params.connect_sequence() {
+                let result = buffalo::try_new(params.clone())
+                    .map_err(HdbError::from)
+                    .and_then(|mut transport| {
+                        initial_request::send_and_receive(&mut *transport)?;
+                        Ok(transport)
+                    })
+                    .map_err(HdbError::from_socket);
+                match result {
+                    Ok(transport) => break 'retry (params, transport),
+                    Err(e) => {
+                        debug!("reconnect to {} failed with {:?}", params.addr(), e);
+                        o_error = Some(e);
+                    }
+                }
             }
-            #[cfg(feature = "tls")]
-            Buffalo::Secure(ref sc) => {
-                let reader = &mut *(sc.reader()).borrow_mut();
-                Reply::parse(o_rs_md, o_par_md, o_rs, Some(am_conn_core), reader)?
+            if Instant::now() >= deadline {
+                return Err(o_error.unwrap(/* connect_sequence is never empty */));
             }
+            thread::sleep(Duration::from_secs(1));
         };
+        self.params = params;
+        self.transport = transport;
+
+        self.authenticated = false;
+        self.session_id = 0;
+        self.seq_number = 0;
+        self.statement_sequence = None;
+        self.session_state = Default::default();
+        self.warnings.clear();
 
-        self.handle_db_error(&mut reply.parts)?;
-        Ok(reply)
+        authentication::authenticate(self)?;
+        // client info is sent again with the next request
+        self.client_info_touched = true;
+
+        let mut replacements = Vec::<(u64, u64)>::new();
+        let handles: Vec<u64> = self.statements.keys().cloned().collect();
+        for handle in handles {
+            let stmt = self.statements[&handle].0.clone();
+            let mut request = Request::new(RequestType::Prepare, HOLD_CURSORS_OVER_COMMIT);
+            request.push(Part::new(PartKind::Command, Argument::Command(&stmt)));
+            let mut reply = self.roundtrip(request, None, None, None, &mut None)?;
+            match reply
+                .parts
+                .extract_first_part_of_type(PartKind::StatementId)
+                .map(Part::into_elements)
+            {
+                Some((_, Argument::StatementId(new_id))) => {
+                    let entry = self.statements.get_mut(&handle).unwrap(/* key exists */);
+                    replacements.push((entry.1, new_id));
+                    entry.1 = new_id;
+                }
+                _ => return Err(HdbError::impl_("reconnect: no StatementId found")),
+            }
+        }
+        info!(
+            "Reconnected to {} (session_id = {}), {} statement(s) prepared again",
+            self.params.addr(),
+            self.session_id(),
+            replacements.len()
+        );
+        Ok(replacements)
     }
 
     fn handle_db_error(&mut self, parts: &mut Parts) -> HdbResult<()> {
@@ -377,26 +694,24 @@ impl<'a> ConnectionCore {
         if self.authenticated {
             let request = Request::new_for_disconnect();
 
+            let session_id = self.session_id();
             let nsn = self.next_seq_number();
-            match self.buffalo {
-                Buffalo::Plain(ref pc) => {
-                    let writer = &mut *(pc.writer()).borrow_mut();
-                    request.emit(self.session_id(), nsn, 0, None, writer)?;
-                    writer.flush()?;
-                }
-                #[cfg(feature = "tls")]
-                Buffalo::Secure(ref sc) => {
-                    let writer = &mut *(sc.writer()).borrow_mut();
-                    request.emit(self.session_id(), nsn, 0, writer)?;
-                    writer.flush()?;
-                }
-            }
+            let mut writer = self.transport.writer();
+            request.emit(session_id, nsn, 0, false, None, &mut writer)?;
+            writer.flush()?;
             trace!("Disconnect: request successfully sent");
         }
         Ok(())
     }
 }
 
+fn all_failed(failures: &[String]) -> String {
+    format!(
+        "Could not connect to any of the addresses ({})",
+        failures.join("; ")
+    )
+}
+
 impl Drop for ConnectionCore {
     // try to send a disconnect to the database, ignore all errors
     fn drop(&mut self) {
@@ -1,34 +1,13 @@
-use crate::conn_core::buffalo::Buffalo;
+use crate::conn_core::transport::Transport;
 use crate::protocol::util;
 use crate::HdbResult;
 
 use byteorder::{BigEndian, WriteBytesExt};
 
-pub fn send_and_receive(buffalo: &mut Buffalo) -> HdbResult<()> {
+pub fn send_and_receive(transport: &mut dyn Transport) -> HdbResult<()> {
     trace!("send_and_receive()");
-    match buffalo {
-        Buffalo::Plain(ref pc) => {
-            let writer = &mut *(pc.writer()).borrow_mut();
-            emit_initial_request(writer)?;
-        }
-        #[cfg(feature = "tls")]
-        Buffalo::Secure(ref sc) => {
-            let writer = &mut *(sc.writer()).borrow_mut();
-            emit_initial_request(writer)?;
-        }
-    }
-
-    match buffalo {
-        Buffalo::Plain(ref pc) => {
-            let reader = &mut *(pc.reader()).borrow_mut();
-            util::skip_bytes(8, reader)?; // ignore the response content
-        }
-        #[cfg(feature = "tls")]
-        Buffalo::Secure(ref sc) => {
-            let reader = &mut *(sc.reader()).borrow_mut();
-            util::skip_bytes(8, reader)?; // ignore the response content
-        }
-    }
+    emit_initial_request(&mut transport.writer())?;
+    util::skip_bytes(8, &mut transport.reader())?; // ignore the response content
 
     debug!("successfully initialized");
     Ok(())
@@ -1,6 +1,6 @@
 //! The low-level database connection.
 //! Depending on the ConnectParams, the physical connection is either a plain
-//! TcpStream or a TlsStream, which are the two variants of Buffalo.
+//! TcpStream, a TlsStream, or an application-provided Transport.
 
 mod am_conn_core;
 mod buffalo;
@@ -8,6 +8,11 @@ pub mod connect_params;
 pub mod connect_params_builder;
 mod connection_core;
 mod initial_request;
+#[cfg(feature = "alpha_routing")]
+pub(crate) mod routing;
 mod session_state;
+pub(crate) mod trace;
+pub(crate) mod transport;
 
 pub(crate) use self::am_conn_core::AmConnCore;
+pub(crate) use self::connection_core::ConnectionCore;
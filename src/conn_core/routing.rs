@@ -0,0 +1,86 @@
+//! Client-side statement routing in scale-out systems.
+//!
+//! The connection that was opened by the application is the anchor connection.
+//! When a statement is prepared, the server reports the volumes that hold the data
+//! of the involved tables (`TableLocation`). If the first of these volumes is served
+//! by another index server of the topology, a session to that index server is
+//! opened lazily (and cached in the anchor's ConnectionCore), and the statement
+//! is prepared there as well.
+//!
+//! Executions are only routed while the anchor connection is in auto-commit mode;
+//! otherwise they are done on the anchor connection, because the transaction
+//! is bound to its session.
+//! Before each routed execution, the settings and the client info of the anchor
+//! connection are copied to the routed session. The isolation level and the other
+//! parts of the session state are maintained by the server; if they differ between
+//! the sessions, the execution is done on the anchor connection.
+//! Hash partitions are not evaluated on the client side; statements on
+//! hash-partitioned tables are routed like all other statements,
+//! i.e. according to their table location, or they stay on the anchor connection.
+use crate::conn_core::AmConnCore;
+use crate::HdbResult;
+
+// Returns the session to which statements with the given table location
+// should be routed, or None if the anchor connection is the right one.
+pub(crate) fn routed_session(
+    anchor: &AmConnCore,
+    table_location: &[i32],
+) -> HdbResult<Option<AmConnCore>> {
+    let volume_id = match table_location.first() {
+        Some(volume_id) => *volume_id,
+        None => return Ok(None),
+    };
+
+    let params = {
+        let mut anchor_core = anchor.lock()?;
+        let (host, port) = match anchor_core
+            .topology()
+            .and_then(|topology| topology.location_of_volume(volume_id))
+        {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+        let params = anchor_core.connect_params().with_location(&host, port);
+        if params.addr() == anchor_core.connect_params().addr() {
+            return Ok(None);
+        }
+        if let Some(am_conn_core) = anchor_core.routed_sessions().get(params.addr()) {
+            return Ok(Some(am_conn_core.clone()));
+        }
+        params
+    };
+
+    debug!(
+        "routing: opening session to {} for volume {}",
+        params.addr(),
+        volume_id
+    );
+    let am_conn_core = AmConnCore::try_new(params.clone())?;
+    anchor
+        .lock()?
+        .routed_sessions()
+        .insert(params.addr().to_string(), am_conn_core.clone());
+    Ok(Some(am_conn_core))
+}
+
+// Copies the settings and the client info of the anchor connection to the routed session.
+// Returns false if the session states differ, so that the routed session must not be used.
+pub(crate) fn sync_session(anchor: &AmConnCore, routed: &AmConnCore) -> HdbResult<bool> {
+    let anchor_core = anchor.lock()?;
+    let mut routed_core = routed.lock()?;
+    if !routed_core
+        .session_state()
+        .has_same_settings(anchor_core.session_state())
+    {
+        debug!("routing: session states differ, the anchor connection is used");
+        return Ok(false);
+    }
+    routed_core.set_fetch_size(anchor_core.get_fetch_size());
+    routed_core.set_lob_read_length(anchor_core.get_lob_read_length());
+    routed_core.set_lob_write_length(anchor_core.get_lob_write_length());
+    routed_core.set_query_timeout(anchor_core.query_timeout());
+    if routed_core.client_info() != anchor_core.client_info() {
+        routed_core.set_client_info(anchor_core.client_info().clone());
+    }
+    Ok(true)
+}
@@ -22,6 +22,14 @@ impl Default for SessionState {
     }
 }
 impl SessionState {
+    // Whether both sessions execute statements with the same settings.
+    #[cfg(feature = "alpha_routing")]
+    pub fn has_same_settings(&self, other: &SessionState) -> bool {
+        self.isolation_level == other.isolation_level
+            && self.ddl_commit_mode == other.ddl_commit_mode
+            && self.read_only_mode == other.read_only_mode
+    }
+
     pub fn update(&mut self, transaction_flags: TransactionFlags) {
         for (id, value) in transaction_flags {
             match (id, value) {
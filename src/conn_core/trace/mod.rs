@@ -0,0 +1,245 @@
+//! Recording of the protocol traffic into trace files, and their replay.
+//!
+//! The format of trace files is documented with `TraceReplay`.
+
+mod recorder;
+mod replay;
+
+pub(crate) use self::recorder::{Tracer, TracingTransport};
+pub use self::replay::TraceReplay;
+
+use crate::protocol::partkind::PartKind;
+use crate::protocol::parts::authfields::AuthFields;
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io;
+
+const MAGIC: &[u8; 8] = b"HDBTRACE";
+const VERSION: u8 = 1;
+
+const REQUEST: u8 = b'Q';
+const REPLY: u8 = b'R';
+
+const MESSAGE_HEADER_SIZE: usize = 32;
+const SEGMENT_HEADER_SIZE: usize = 24;
+const PART_HEADER_SIZE: usize = 16;
+
+// A single request or reply of a connection.
+#[derive(Debug)]
+pub(crate) struct Record {
+    kind: u8,
+    pub connection_no: u32,
+    pub data: Vec<u8>,
+}
+impl Record {
+    pub fn is_request(&self) -> bool {
+        self.kind == REQUEST
+    }
+
+    fn emit<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_u8(self.kind)?;
+        w.write_u32::<LittleEndian>(self.connection_no)?;
+        w.write_u32::<LittleEndian>(self.data.len() as u32)?;
+        w.write_all(&self.data)
+    }
+
+    // Returns None at the end of the trace.
+    fn parse<R: io::Read>(rdr: &mut R) -> io::Result<Option<Record>> {
+        let kind = match rdr.read_u8() {
+            Ok(kind) => kind,
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if kind != REQUEST && kind != REPLY {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid record kind {} in trace", kind),
+            ));
+        }
+        let connection_no = rdr.read_u32::<LittleEndian>()?;
+        let mut data = vec![0_u8; rdr.read_u32::<LittleEndian>()? as usize];
+        rdr.read_exact(&mut data)?;
+        Ok(Some(Record {
+            kind,
+            connection_no,
+            data,
+        }))
+    }
+}
+
+pub(crate) fn is_trace(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+// Reads all records of a trace file.
+pub(crate) fn parse_trace<R: io::Read>(rdr: &mut R) -> io::Result<Vec<Record>> {
+    parse_file_header(rdr)?;
+    let mut records = Vec::<Record>::new();
+    while let Some(record) = Record::parse(rdr)? {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn emit_file_header<W: io::Write>(w: &mut W) -> io::Result<()> {
+    w.write_all(MAGIC)?;
+    w.write_u8(VERSION)
+}
+
+fn parse_file_header<R: io::Read>(rdr: &mut R) -> io::Result<()> {
+    let mut magic = [0_u8; 8];
+    rdr.read_exact(&mut magic)?;
+    let version = rdr.read_u8()?;
+    if &magic != MAGIC || version != VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a trace file of a supported version",
+        ));
+    }
+    Ok(())
+}
+
+// The request type of an uncompressed request, or None.
+fn request_type(message: &[u8]) -> Option<i8> {
+    if message.len() < MESSAGE_HEADER_SIZE + SEGMENT_HEADER_SIZE || is_compressed(message) {
+        None
+    } else {
+        Some(message[MESSAGE_HEADER_SIZE + 13] as i8)
+    }
+}
+
+fn is_compressed(message: &[u8]) -> bool {
+    message[22] & 0b10 != 0
+}
+
+// Replaces the fields of the Authentication part of the given message with the
+// result of the given function, and adapts the sizes in the headers accordingly.
+// Messages without Authentication part are returned unchanged.
+fn rewrite_auth_fields<F>(message: &[u8], rewrite: F) -> Vec<u8>
+where
+    F: FnOnce(Vec<Vec<u8>>) -> Vec<Vec<u8>>,
+{
+    let (part_pos, arg_size) = match find_auth_part(message) {
+        Some(position) => position,
+        None => return message.to_vec(),
+    };
+    let data_pos = part_pos + PART_HEADER_SIZE;
+    let data_end = (data_pos + arg_size + padsize(arg_size)).min(message.len());
+
+    let mut fields = Vec::<Vec<u8>>::new();
+    if let Ok(mut auth_fields) = AuthFields::parse(&mut io::Cursor::new(
+        &message[data_pos..data_pos + arg_size],
+    )) {
+        while let Some(field) = auth_fields.pop() {
+            fields.push(field);
+        }
+        fields.reverse();
+    }
+    let mut auth_fields = AuthFields::with_capacity(fields.len());
+    for field in rewrite(fields) {
+        auth_fields.push(field);
+    }
+    let mut new_data = Vec::<u8>::with_capacity(auth_fields.size());
+    if auth_fields.emit(&mut new_data).is_err() {
+        new_data = vec![0, 0]; // no fields
+    }
+
+    let mut result = Vec::<u8>::with_capacity(message.len());
+    result.extend_from_slice(&message[..data_pos]);
+    result.extend_from_slice(&new_data);
+    result.resize(result.len() + padsize(new_data.len()), 0);
+    result.extend_from_slice(&message[data_end..]);
+
+    let delta = result.len() as i64 - message.len() as i64;
+    LittleEndian::write_i32(&mut result[part_pos + 8..], new_data.len() as i32);
+    let varpart_length = i64::from(LittleEndian::read_u32(&message[12..])) + delta;
+    LittleEndian::write_u32(&mut result[12..], varpart_length as u32);
+    let seg_size = i64::from(LittleEndian::read_i32(&message[MESSAGE_HEADER_SIZE..])) + delta;
+    LittleEndian::write_i32(&mut result[MESSAGE_HEADER_SIZE..], seg_size as i32);
+    result
+}
+
+// The position and the argument size of the Authentication part, if there is one.
+fn find_auth_part(message: &[u8]) -> Option<(usize, usize)> {
+    if message.len() < MESSAGE_HEADER_SIZE + SEGMENT_HEADER_SIZE || is_compressed(message) {
+        return None;
+    }
+    let no_of_parts = LittleEndian::read_i16(&message[MESSAGE_HEADER_SIZE + 8..]);
+    let mut pos = MESSAGE_HEADER_SIZE + SEGMENT_HEADER_SIZE;
+    for _ in 0..no_of_parts {
+        if message.len() < pos + PART_HEADER_SIZE {
+            return None;
+        }
+        let kind = message[pos] as i8;
+        let arg_size = LittleEndian::read_i32(&message[pos + 8..]).max(0) as usize;
+        if kind == PartKind::Authentication.to_i8() {
+            return if message.len() < pos + PART_HEADER_SIZE + arg_size {
+                None
+            } else {
+                Some((pos, arg_size))
+            };
+        } else if kind == PartKind::Error.to_i8() {
+            // error parts are not padded, and are never followed by authentication data
+            return None;
+        }
+        pos += PART_HEADER_SIZE + arg_size + padsize(arg_size);
+    }
+    None
+}
+
+fn padsize(size: usize) -> usize {
+    7 - (size + 7) % 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_auth_part, recorder, request_type};
+    use crate::protocol::argument::Argument;
+    use crate::protocol::part::Part;
+    use crate::protocol::partkind::PartKind;
+    use crate::protocol::parts::authfields::AuthFields;
+    use crate::protocol::request::Request;
+    use crate::protocol::request_type::RequestType;
+    use byteorder::{ByteOrder, LittleEndian};
+    use std::io;
+
+    fn connect_request(fields: &[&[u8]]) -> Vec<u8> {
+        let mut auth_fields = AuthFields::with_capacity(fields.len());
+        for field in fields {
+            auth_fields.push(field.to_vec());
+        }
+        let mut request = Request::new(RequestType::Connect, 0);
+        request.push(Part::new(PartKind::Command, Argument::Command("X")));
+        request.push(Part::new(
+            PartKind::Authentication,
+            Argument::Auth(auth_fields),
+        ));
+        request.push(Part::new(PartKind::Command, Argument::Command("Y")));
+        let mut buf = Vec::<u8>::new();
+        request.emit(0, 1, 0, false, None, &mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_redaction() {
+        let request = connect_request(&[b"MEIER", b"SCRAMSHA256", &[7_u8; 35]]);
+        let redacted = recorder::redact_request(&request);
+        assert_eq!(request_type(&redacted), Some(RequestType::Connect.to_i8()));
+        assert_eq!(redacted.len(), request.len() - 32);
+        assert_eq!(
+            LittleEndian::read_u32(&redacted[12..]) as usize,
+            redacted.len() - 32
+        );
+        // the part behind the Authentication part is unchanged
+        assert_eq!(redacted[redacted.len() - 8..], request[request.len() - 8..]);
+
+        let (pos, arg_size) = find_auth_part(&redacted).unwrap();
+        let mut auth_fields = AuthFields::parse(&mut io::Cursor::new(
+            &redacted[pos + 16..pos + 16 + arg_size],
+        ))
+        .unwrap();
+        assert_eq!(auth_fields.pop().unwrap(), b"");
+        assert_eq!(auth_fields.pop().unwrap(), b"SCRAMSHA256");
+        assert_eq!(auth_fields.pop().unwrap(), b"MEIER");
+        assert!(auth_fields.is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+use super::{emit_file_header, rewrite_auth_fields, Record, REPLY, REQUEST};
+use crate::conn_core::transport::Transport;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+// The trace file, shared by all connections that are opened with the same ConnectParams.
+// The file is created (or truncated) when the first connection is opened.
+#[derive(Debug)]
+pub(crate) struct Tracer {
+    path: PathBuf,
+    file: Mutex<Option<BufWriter<File>>>,
+    connections: AtomicU32,
+}
+impl Tracer {
+    pub fn new(path: PathBuf) -> Tracer {
+        Tracer {
+            path,
+            file: Mutex::new(None),
+            connections: AtomicU32::new(0),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write(&self, kind: u8, connection_no: u32, data: Vec<u8>) {
+        let record = Record {
+            kind,
+            connection_no,
+            data,
+        };
+        if let Err(e) = self.try_write(&record) {
+            warn!("writing to trace file {:?} failed with {}", self.path, e);
+        }
+    }
+
+    fn try_write(&self, record: &Record) -> io::Result<()> {
+        // a panic in another thread does not make the trace file unusable
+        let mut guard = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        if guard.is_none() {
+            let mut file = BufWriter::new(File::create(&self.path)?);
+            emit_file_header(&mut file)?;
+            *guard = Some(file);
+        }
+        let file = guard.as_mut().unwrap(/* was just set */);
+        record.emit(file)?;
+        // the trace should be complete even if the process dies
+        file.flush()
+    }
+}
+
+// Wraps the real transport, and records what is written to and read from it.
+//
+// A request is recorded when the driver flushes it; the reply is what the driver
+// consumes until it sends the next request, or until the connection is dropped.
+#[derive(Debug)]
+pub(crate) struct TracingTransport {
+    inner: Box<dyn Transport>,
+    tracer: Arc<Tracer>,
+    connection_no: u32,
+    request: Vec<u8>,
+    reply: Vec<u8>,
+}
+impl TracingTransport {
+    pub fn new(inner: Box<dyn Transport>, tracer: Arc<Tracer>) -> TracingTransport {
+        let connection_no = tracer.connections.fetch_add(1, Ordering::SeqCst) + 1;
+        TracingTransport {
+            inner,
+            tracer,
+            connection_no,
+            request: Vec::new(),
+            reply: Vec::new(),
+        }
+    }
+
+    fn record_reply(&mut self) {
+        if !self.reply.is_empty() {
+            let reply = std::mem::take(&mut self.reply);
+            self.tracer
+                .write(REPLY, self.connection_no, redact_reply(&reply));
+        }
+    }
+
+    fn record_request(&mut self) {
+        if !self.request.is_empty() {
+            let request = std::mem::take(&mut self.request);
+            self.tracer
+                .write(REQUEST, self.connection_no, redact_request(&request));
+        }
+    }
+}
+
+impl Transport for TracingTransport {
+    fn writer(&mut self) -> &mut dyn io::Write {
+        self
+    }
+
+    fn reader(&mut self) -> &mut dyn io::BufRead {
+        self
+    }
+
+    fn is_alive(&self) -> bool {
+        self.inner.is_alive()
+    }
+
+    fn s_type(&self) -> &'static str {
+        self.inner.s_type()
+    }
+}
+
+impl io::Write for TracingTransport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let count = self.inner.writer().write(buf)?;
+        self.request.extend_from_slice(&buf[..count]);
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.record_reply();
+        self.record_request();
+        self.inner.writer().flush()
+    }
+}
+
+impl io::Read for TracingTransport {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.reader().read(buf)?;
+        self.reply.extend_from_slice(&buf[..count]);
+        Ok(count)
+    }
+}
+
+impl io::BufRead for TracingTransport {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.reader().fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let reader = self.inner.reader();
+        // the data are still buffered, so this does not block
+        if let Ok(buf) = reader.fill_buf() {
+            self.reply.extend_from_slice(&buf[..amt.min(buf.len())]);
+        }
+        reader.consume(amt);
+    }
+}
+
+impl Drop for TracingTransport {
+    fn drop(&mut self) {
+        self.record_reply();
+        self.record_request();
+    }
+}
+
+// Keeps the database user and the method names, i.e., the fields with index 0 and
+// the odd indices, and clears challenges and proofs.
+pub(super) fn redact_request(request: &[u8]) -> Vec<u8> {
+    rewrite_auth_fields(request, |fields| {
+        fields
+            .into_iter()
+            .enumerate()
+            .map(|(i, field)| if i == 0 || i % 2 == 1 { field } else { vec![] })
+            .collect()
+    })
+}
+
+// Keeps the method name, clears server challenge or server proof,
+// and drops the session cookie.
+fn redact_reply(reply: &[u8]) -> Vec<u8> {
+    rewrite_auth_fields(reply, |fields| {
+        fields
+            .into_iter()
+            .take(2)
+            .enumerate()
+            .map(|(i, field)| if i == 0 { field } else { vec![] })
+            .collect()
+    })
+}
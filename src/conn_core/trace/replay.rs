@@ -0,0 +1,194 @@
+use super::{parse_trace, request_type, rewrite_auth_fields, REPLY, REQUEST};
+use crate::conn_core::connect_params::ConnectParams;
+use crate::conn_core::transport::Transport;
+use crate::protocol::parts::authfields::AuthFields;
+use crate::protocol::request_type::RequestType;
+use crate::{HdbError, HdbResult};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Replays the replies of a protocol trace to a `Connection`.
+///
+/// A trace is recorded by using
+/// [`ConnectParamsBuilder::trace_file()`](struct.ConnectParamsBuilder.html#method.trace_file)
+/// (or the URL option `trace_file`).
+/// It contains all requests and replies of all connections that were opened
+/// with these connect parameters, with redacted authentication data.
+///
+/// For a replay, the connect parameters for one of the recorded connections are
+/// produced; a `Connection` that is opened with them gets the recorded replies,
+/// one for each request it sends, without any database being involved.
+/// The authentication is simulated with SCRAMSHA256, using fake credentials.
+/// Everything else is served as recorded, so the application has to send the
+/// same sequence of requests as the application that recorded the trace.
+///
+/// # Trace file format
+///
+/// A trace file starts with the 8 bytes `HDBTRACE` and a version byte (1).
+/// Then a sequence of records follows, each consisting of
+///
+/// - the record kind (1 byte): `Q` for a request, `R` for a reply,
+/// - the number of the connection (u32, little endian), starting with 1,
+/// - the length of the data (u32, little endian),
+/// - the data, i.e. the message as it was sent over the wire.
+///
+/// The first request of each connection is the 14 byte initial request.
+/// In authentication parts, only the database user and the names of the
+/// authentication methods are kept; challenges, proofs, tokens and session cookies
+/// are replaced by empty fields.
+/// Note that all other data, including the SQL commands and the data of resultsets,
+/// are recorded as they are.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use hdbconnect::{Connection, HdbResult, TraceReplay};
+/// # fn foo() -> HdbResult<()> {
+/// let replay = TraceReplay::load("hdbconnect.trace")?;
+/// let mut connection = Connection::new(replay.connect_params(1)?)?;
+/// let count: u32 = connection.query("select count(*) from TABLE_X")?.try_into()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TraceReplay {
+    connections: BTreeMap<u32, Arc<Vec<Exchange>>>,
+}
+
+// A recorded request with its reply; the reply is missing if the connection
+// was closed before it was received.
+#[derive(Clone, Debug)]
+struct Exchange {
+    request: Vec<u8>,
+    reply: Option<Vec<u8>>,
+}
+
+impl TraceReplay {
+    /// Reads a trace file.
+    pub fn load<P: AsRef<Path>>(path: P) -> HdbResult<TraceReplay> {
+        let records = parse_trace(&mut BufReader::new(File::open(path)?))?;
+        let mut connections = BTreeMap::<u32, Vec<Exchange>>::new();
+        for record in records {
+            let exchanges = connections.entry(record.connection_no).or_default();
+            match (record.kind, exchanges.last_mut()) {
+                (REQUEST, _) => exchanges.push(Exchange {
+                    request: record.data,
+                    reply: None,
+                }),
+                (REPLY, Some(exchange)) if exchange.reply.is_none() => {
+                    exchange.reply = Some(record.data)
+                }
+                _ => {
+                    return Err(HdbError::Usage(format!(
+                        "trace contains an unexpected reply for connection {}",
+                        record.connection_no
+                    )));
+                }
+            }
+        }
+        Ok(TraceReplay {
+            connections: connections
+                .into_iter()
+                .map(|(no, exchanges)| (no, Arc::new(exchanges)))
+                .collect(),
+        })
+    }
+
+    /// The numbers of the recorded connections.
+    pub fn connections(&self) -> Vec<u32> {
+        self.connections.keys().cloned().collect()
+    }
+
+    /// Connect parameters for replaying the given recorded connection.
+    pub fn connect_params(&self, connection_no: u32) -> HdbResult<ConnectParams> {
+        let exchanges = Arc::clone(self.connections.get(&connection_no).ok_or_else(|| {
+            HdbError::Usage(format!("trace contains no connection {}", connection_no))
+        })?);
+        ConnectParams::builder()
+            .hostname("trace-replay")
+            .port(30_015)
+            .dbuser("REPLAY")
+            .password("replay")
+            .transport(move |_| {
+                Ok(Box::new(ReplayTransport {
+                    exchanges: exchanges.iter().cloned().collect(),
+                    request: Vec::new(),
+                    reply: io::Cursor::new(Vec::new()),
+                }))
+            })
+            .build()
+    }
+}
+
+// Serves the recorded replies, one for each request that is flushed.
+#[derive(Debug)]
+struct ReplayTransport {
+    exchanges: VecDeque<Exchange>,
+    request: Vec<u8>,
+    reply: io::Cursor<Vec<u8>>,
+}
+
+impl Transport for ReplayTransport {
+    fn writer(&mut self) -> &mut dyn io::Write {
+        self
+    }
+
+    fn reader(&mut self) -> &mut dyn io::BufRead {
+        &mut self.reply
+    }
+
+    fn s_type(&self) -> &'static str {
+        "replay"
+    }
+}
+
+impl io::Write for ReplayTransport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.request.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.request.is_empty() {
+            return Ok(());
+        }
+        let request = std::mem::take(&mut self.request);
+        let exchange = self.exchanges.pop_front().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the trace contains no further requests",
+            )
+        })?;
+        if request_type(&request) != request_type(&exchange.request) {
+            warn!(
+                "replay: request of type {:?} is answered with the reply to a request of type {:?}",
+                request_type(&request),
+                request_type(&exchange.request)
+            );
+        }
+        let reply = exchange.reply.unwrap_or_default();
+        self.reply = io::Cursor::new(match request_type(&request) {
+            Some(t) if t == RequestType::Authenticate.to_i8() => {
+                rewrite_auth_fields(&reply, |_| vec![b"SCRAMSHA256".to_vec(), server_data()])
+            }
+            Some(t) if t == RequestType::Connect.to_i8() => {
+                rewrite_auth_fields(&reply, |_| vec![b"SCRAMSHA256".to_vec(), vec![]])
+            }
+            _ => reply,
+        });
+        Ok(())
+    }
+}
+
+// Salt and server nonce for the simulated SCRAMSHA256 authentication.
+fn server_data() -> Vec<u8> {
+    let mut server_data = AuthFields::with_capacity(2);
+    server_data.push(vec![0_u8; 16]);
+    server_data.push(vec![0_u8; 48]);
+    let mut buf = Vec::<u8>::with_capacity(server_data.size());
+    server_data.emit(&mut buf).unwrap(/* fields are small */);
+    buf
+}
@@ -0,0 +1,121 @@
+use crate::conn_core::connect_params::ConnectParams;
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+
+/// A buffered, bidirectional connection to a HANA server.
+///
+/// The driver writes its requests to the [`writer()`](#tymethod.writer) and flushes it
+/// at the end of each request, and it reads the replies from the
+/// [`reader()`](#tymethod.reader).
+///
+/// `hdbconnect` uses its own implementations for plain TCP and for TLS
+/// (see [`ConnectParams::use_tls()`](struct.ConnectParams.html#method.use_tls)).
+/// Other transports, like Unix domain sockets, proxies, or in-memory pipes for tests,
+/// can be plugged in with
+/// [`ConnectParamsBuilder::transport()`](struct.ConnectParamsBuilder.html#method.transport).
+pub trait Transport: fmt::Debug + Send {
+    /// The buffered writer to which requests are written.
+    fn writer(&mut self) -> &mut dyn io::Write;
+
+    /// The buffered reader from which replies are read.
+    fn reader(&mut self) -> &mut dyn io::BufRead;
+
+    /// Checks without blocking whether the connection was closed by the peer.
+    ///
+    /// Between two roundtrips the server does not send anything, so any pending input
+    /// (e.g. EOF, or a TLS alert) indicates that the connection is not usable anymore.
+    /// Is used before requests are sent on connections with
+    /// [`reconnect()`](struct.ConnectParams.html#method.reconnect);
+    /// the default implementation always returns true.
+    fn is_alive(&self) -> bool {
+        true
+    }
+
+    /// A short description of the transport, for log output.
+    fn s_type(&self) -> &'static str {
+        "custom"
+    }
+}
+
+// The application-provided function that opens a Transport to the address
+// of the given ConnectParams; wrapped because closures have no Debug implementation.
+#[derive(Clone)]
+pub(crate) struct TransportConnector(pub(crate) Arc<ConnectFn>);
+pub(crate) type ConnectFn = dyn Fn(&ConnectParams) -> io::Result<Box<dyn Transport>> + Send + Sync;
+impl fmt::Debug for TransportConnector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TransportConnector(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transport;
+    use crate::{ConnectParams, Connection};
+    use std::io;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    // A transport that records what is written, and replies from a fixed buffer.
+    #[derive(Debug)]
+    struct MemoryTransport {
+        written: SharedBuffer,
+        reader: io::Cursor<Vec<u8>>,
+    }
+    impl Transport for MemoryTransport {
+        fn writer(&mut self) -> &mut dyn io::Write {
+            &mut self.written
+        }
+        fn reader(&mut self) -> &mut dyn io::BufRead {
+            &mut self.reader
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+    impl io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_custom_transport() {
+        let written = SharedBuffer::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let (written_clone, calls_clone) = (written.clone(), Arc::clone(&calls));
+        let params = ConnectParams::builder()
+            .hostname("abcd123")
+            .port(2222)
+            .dbuser("meier")
+            .password("schLau")
+            .transport(move |params| {
+                assert_eq!(params.addr(), "abcd123:2222");
+                calls_clone.fetch_add(1, Ordering::SeqCst);
+                Ok(Box::new(MemoryTransport {
+                    written: written_clone.clone(),
+                    // only the reply to the initial request is available
+                    reader: io::Cursor::new(vec![0_u8; 8]),
+                }))
+            })
+            .build()
+            .unwrap();
+
+        // the authentication fails because the server does not answer
+        assert!(Connection::new(params).is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // the initial request and the first authentication request were sent
+        let written = written.0.lock().unwrap();
+        assert_eq!(
+            &written[..14],
+            &[255, 255, 255, 255, 4, 0, 20, 4, 0, 1, 0, 1, 1, 1][..]
+        );
+        assert!(written.len() > 14);
+    }
+}
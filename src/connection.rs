@@ -1,4 +1,4 @@
-use crate::authentication;
+use crate::cancel_handle::CancelHandle;
 use crate::conn_core::connect_params::ConnectParams;
 use crate::conn_core::AmConnCore;
 use crate::prepared_statement::PreparedStatement;
@@ -8,13 +8,15 @@ use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::command_info::CommandInfo;
 use crate::protocol::parts::resultset::ResultSet;
 use crate::protocol::parts::server_error::ServerError;
-use crate::protocol::request::{Request, HOLD_CURSORS_OVER_COMMIT};
+use crate::protocol::request::{Request, HOLD_CURSORS_OVER_COMMIT, SCROLLABLE_CURSOR_ON};
 use crate::protocol::request_type::RequestType;
 use crate::protocol::server_resource_consumption_info::ServerResourceConsumptionInfo;
 use crate::xa_impl::new_resource_manager;
 use crate::{HdbError, HdbResponse, HdbResult};
 use chrono::Local;
 use dist_tx::rm::ResourceManager;
+use secstr::SecStr;
+use std::time::Duration;
 
 /// Connection object.
 ///
@@ -42,14 +44,7 @@ impl Connection {
         trace!("Entering connect()");
         let start = Local::now();
 
-        let mut am_conn_core = AmConnCore::try_new(params.clone())?;
-
-        authentication::authenticate(
-            &mut (am_conn_core),
-            params.dbuser(),
-            params.password(),
-            params.clientlocale(),
-        )?;
+        let am_conn_core = AmConnCore::try_new(params.clone())?;
 
         {
             let guard = am_conn_core.lock()?;
@@ -80,7 +75,12 @@ impl Connection {
     /// internally convert the `HdbResponse` to the
     /// respective adequate simple result type.
     pub fn statement<S: AsRef<str>>(&mut self, stmt: S) -> HdbResult<HdbResponse> {
-        execute(&mut self.am_conn_core, stmt.as_ref(), None)
+        execute(
+            &mut self.am_conn_core,
+            stmt.as_ref(),
+            None,
+            HOLD_CURSORS_OVER_COMMIT,
+        )
     }
 
     /// Executes a statement and expects a single ResultSet.
@@ -90,6 +90,24 @@ impl Connection {
         self.statement(stmt)?.into_resultset()
     }
 
+    /// Executes a query with a scrollable cursor and expects a single ResultSet.
+    ///
+    /// The cursor of the returned ResultSet can be repositioned with
+    /// [`ResultSet::fetch_absolute()`](struct.ResultSet.html#method.fetch_absolute),
+    /// [`fetch_relative()`](struct.ResultSet.html#method.fetch_relative),
+    /// [`fetch_first()`](struct.ResultSet.html#method.fetch_first), and
+    /// [`fetch_last()`](struct.ResultSet.html#method.fetch_last).
+    pub fn query_scrollable<S: AsRef<str>>(&mut self, stmt: S) -> HdbResult<ResultSet> {
+        let mut response = execute(
+            &mut self.am_conn_core,
+            stmt.as_ref(),
+            None,
+            HOLD_CURSORS_OVER_COMMIT | SCROLLABLE_CURSOR_ON,
+        )?;
+        response.set_scrollable()?;
+        response.into_resultset()
+    }
+
     /// Executes a statement and expects a single number of affected rows.
     ///
     /// Should be used for DML statements only, i.e., INSERT, UPDATE, DELETE, UPSERT.
@@ -132,17 +150,34 @@ impl Connection {
 
     /// Creates a new connection object with the same settings and
     /// authentication.
+    ///
+    /// If the server handed out a session cookie, it is used for the logon.
     pub fn spawn(&self) -> HdbResult<Connection> {
-        let mut other_conn = Connection::new(self.params.clone())?;
+        let params = match self.session_cookie()? {
+            Some(session_cookie) => self.params.with_session_cookie(session_cookie),
+            None => self.params.clone(),
+        };
+        let mut other_conn = Connection::new(params)?;
         {
             let am_conn_core = self.am_conn_core.lock()?;
             other_conn.set_auto_commit(am_conn_core.is_auto_commit())?;
             other_conn.set_fetch_size(am_conn_core.get_fetch_size())?;
             other_conn.set_lob_read_length(am_conn_core.get_lob_read_length())?;
+            other_conn.set_lob_write_length(am_conn_core.get_lob_write_length())?;
+            other_conn.set_query_timeout(am_conn_core.query_timeout())?;
         }
         Ok(other_conn)
     }
 
+    /// Returns the session cookie that the server handed out with the logon, if any.
+    ///
+    /// It can be used to open further connections for the same database user without
+    /// the password, see
+    /// [`ConnectParamsBuilder::session_cookie()`](struct.ConnectParamsBuilder.html#method.session_cookie).
+    pub fn session_cookie(&self) -> HdbResult<Option<SecStr>> {
+        Ok(self.am_conn_core.lock()?.session_cookie().cloned())
+    }
+
     /// Utility method to fire a couple of statements, ignoring errors and
     /// return values
     pub fn multiple_statements_ignore_err<S: AsRef<str>>(&mut self, stmts: Vec<S>) {
@@ -196,6 +231,42 @@ impl Connection {
         self.am_conn_core.lock()?.set_lob_read_length(l);
         Ok(())
     }
+    /// Returns the connection's lob write length.
+    pub fn get_lob_write_length(&self) -> HdbResult<usize> {
+        Ok(self.am_conn_core.lock()?.get_lob_write_length())
+    }
+    /// Configures the connection's lob write length for future calls.
+    ///
+    /// The lob write length is the size of the chunks in which the data of
+    /// streamed LOB parameters (see `HdbValue::LOBSTREAM`) are sent to the database.
+    pub fn set_lob_write_length(&mut self, l: usize) -> HdbResult<()> {
+        self.am_conn_core.lock()?.set_lob_write_length(l);
+        Ok(())
+    }
+
+    /// Returns the connection's query timeout.
+    pub fn query_timeout(&self) -> HdbResult<Option<Duration>> {
+        Ok(self.am_conn_core.lock()?.query_timeout())
+    }
+    /// Configures a timeout for the execution of future statements.
+    ///
+    /// The server aborts statements that run longer, they then return with an error.
+    /// The timeout is applied in full seconds.
+    /// Individual prepared statements can use a different timeout, see
+    /// [`PreparedStatement::set_query_timeout()`](struct.PreparedStatement.html#method.set_query_timeout).
+    pub fn set_query_timeout(&mut self, query_timeout: Option<Duration>) -> HdbResult<()> {
+        self.am_conn_core.lock()?.set_query_timeout(query_timeout);
+        Ok(())
+    }
+
+    /// Returns a handle that allows cancelling the currently running statement
+    /// of this connection from another thread.
+    pub fn cancel_handle(&self) -> HdbResult<CancelHandle> {
+        Ok(CancelHandle::new(
+            self.params.clone(),
+            self.am_conn_core.lock()?.shared_connection_id(),
+        ))
+    }
 
     /// Returns the ID of the connection.
     ///
@@ -213,13 +284,16 @@ impl Connection {
             .clone())
     }
 
-    #[doc(hidden)]
-    pub fn data_format_version_2(&self) -> HdbResult<Option<i32>> {
+    /// Returns the data format version that was negotiated with the server.
+    ///
+    /// The driver proposes version 8; older servers answer with lower versions,
+    /// and then use other type ids for some database types.
+    pub fn data_format_version(&self) -> HdbResult<i32> {
         Ok(self
             .am_conn_core
             .lock()?
             .connect_options()
-            .get_dataformat_version2())
+            .get_effective_dataformat_version())
     }
 
     /// Returns the number of roundtrips to the database that
@@ -284,6 +358,7 @@ impl Connection {
             &mut self.am_conn_core,
             stmt,
             Some(CommandInfo::new(line, module.as_ref())),
+            HOLD_CURSORS_OVER_COMMIT,
         )
     }
 }
@@ -292,6 +367,7 @@ fn execute<S>(
     am_conn_core: &mut AmConnCore,
     stmt: S,
     o_command_info: Option<CommandInfo>,
+    command_options: u8,
 ) -> HdbResult<HdbResponse>
 where
     S: AsRef<str>,
@@ -300,7 +376,7 @@ where
         "connection[{}]::execute()",
         am_conn_core.lock()?.get_connection_id()
     );
-    let mut request = Request::new(RequestType::ExecuteDirect, HOLD_CURSORS_OVER_COMMIT);
+    let mut request = Request::new(RequestType::ExecuteDirect, command_options);
     {
         let conn_core = am_conn_core.lock()?;
         let fetch_size = conn_core.get_fetch_size();
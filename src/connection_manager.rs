@@ -2,6 +2,7 @@
 //!
 use crate::{ConnectParams, Connection, HdbError};
 use r2d2;
+use std::sync::Mutex;
 
 /// Implementation of r2d2's
 /// [`ManageConnection`](https://docs.rs/r2d2/*/r2d2/trait.ManageConnection.html)
@@ -35,16 +36,20 @@ use r2d2;
 ///
 /// ```
 ///
+/// If the server hands out a session cookie, the manager uses it for opening
+/// further connections, and forgets the password. If a logon with the cookie fails,
+/// the cookie is discarded as well; the manager then cannot open further connections
+/// and must be replaced.
 #[derive(Debug)]
 pub struct ConnectionManager {
-    connect_params: ConnectParams,
+    connect_params: Mutex<ConnectParams>,
 }
 
 impl ConnectionManager {
     ///
     pub fn new(connect_params: &ConnectParams) -> ConnectionManager {
         ConnectionManager {
-            connect_params: connect_params.clone(),
+            connect_params: Mutex::new(connect_params.clone()),
         }
     }
 }
@@ -56,7 +61,26 @@ impl r2d2::ManageConnection for ConnectionManager {
     // Attempts to create a new connection.
     fn connect(&self) -> Result<Self::Connection, Self::Error> {
         trace!("ConnectionManager::connect()");
-        Connection::new(self.connect_params.clone())
+        let params = self.connect_params.lock()?.clone();
+        let o_used_session_cookie = params.session_cookie().cloned();
+        let connection = match Connection::new(params) {
+            Ok(connection) => connection,
+            Err(e) => {
+                // the logon failed with the cookie, unless another connection got a new one
+                if e.server_error().is_some() && o_used_session_cookie.is_some() {
+                    let mut connect_params = self.connect_params.lock()?;
+                    if connect_params.session_cookie() == o_used_session_cookie.as_ref() {
+                        *connect_params = connect_params.without_session_cookie();
+                    }
+                }
+                return Err(e);
+            }
+        };
+        if let Some(session_cookie) = connection.session_cookie()? {
+            let mut connect_params = self.connect_params.lock()?;
+            *connect_params = connect_params.with_session_cookie_only(session_cookie);
+        }
+        Ok(connection)
     }
 
     // Determines if the connection is still connected to the database.
@@ -43,6 +43,10 @@ pub enum HdbError {
     /// IO error occured in communication with the database.
     Io(io::Error),
 
+    /// A socket timeout (see `ConnectParams::connect_timeout()` etc) expired
+    /// in communication with the database.
+    Timeout(io::Error),
+
     /// Error occured in thread synchronization.
     Poison(String),
 
@@ -52,6 +56,10 @@ pub enum HdbError {
 
     /// Error due to wrong usage of API.
     Usage(String),
+
+    /// The connection was lost and re-established (see `ConnectParams::reconnect()`),
+    /// but the open write transaction was lost.
+    TransactionLost(io::Error),
 }
 
 impl HdbError {
@@ -95,6 +103,18 @@ impl HdbError {
     pub(crate) fn usage_<S: AsRef<str>>(s: S) -> HdbError {
         HdbError::Usage(s.as_ref().to_owned())
     }
+    // For errors from connecting, reading, or writing the socket:
+    // an expired socket timeout is reported as Timeout.
+    pub(crate) fn from_socket<E: Into<HdbError>>(error: E) -> HdbError {
+        match error.into() {
+            HdbError::Io(io_error) => match io_error.kind() {
+                // an expired read timeout is reported as WouldBlock on some platforms
+                io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => HdbError::Timeout(io_error),
+                _ => HdbError::Io(io_error),
+            },
+            error => error,
+        }
+    }
 }
 
 impl error::Error for HdbError {
@@ -107,6 +127,10 @@ impl error::Error for HdbError {
             HdbError::Deserialization(ref e) => e.description(),
             HdbError::Cesu8(ref e) => e.description(),
             HdbError::Io(ref e) => e.description(),
+            HdbError::Timeout(_) => "Timeout in communication with the database",
+            HdbError::TransactionLost(_) => {
+                "Connection was re-established, but the open transaction was lost"
+            }
             HdbError::Serialization(ref e) => e.description(),
             HdbError::Impl(ref s)
             | HdbError::Evaluation(ref s)
@@ -120,7 +144,9 @@ impl error::Error for HdbError {
             HdbError::Cesu8(ref e) => Some(e),
             HdbError::Conversion(ref error) => Some(error),
             HdbError::Deserialization(ref error) => Some(error),
-            HdbError::Io(ref error) => Some(error),
+            HdbError::Io(ref error)
+            | HdbError::Timeout(ref error)
+            | HdbError::TransactionLost(ref error) => Some(error),
             HdbError::Serialization(ref error) => Some(error),
             HdbError::DbError(ref server_error) => Some(server_error),
             HdbError::Impl(_)
@@ -140,6 +166,8 @@ impl fmt::Display for HdbError {
             HdbError::Conversion(ref e) => write!(fmt, "{}", e),
             HdbError::Deserialization(ref error) => write!(fmt, "{:?}", error),
             HdbError::Io(ref error) => write!(fmt, "{:?}", error),
+            HdbError::Timeout(ref error) => write!(fmt, "Timeout({:?})", error),
+            HdbError::TransactionLost(ref error) => write!(fmt, "TransactionLost({:?})", error),
             HdbError::Impl(ref error) => write!(fmt, "{:?}", error),
             HdbError::Serialization(ref error) => write!(fmt, "{:?}", error),
             HdbError::Evaluation(ref s)
@@ -215,7 +243,7 @@ impl From<Cesu8DecodingError> for HdbError {
     }
 }
 
-impl<'a, T> From<sync::PoisonError<sync::MutexGuard<'a, T>>> for HdbError {
+impl<'a, T: ?Sized> From<sync::PoisonError<sync::MutexGuard<'a, T>>> for HdbError {
     fn from(error: sync::PoisonError<sync::MutexGuard<'a, T>>) -> HdbError {
         HdbError::Poison(error.description().to_owned())
     }
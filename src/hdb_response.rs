@@ -179,6 +179,16 @@ impl HdbResponse {
         HdbError::Evaluation(errmsg)
     }
 
+    // Marks the contained resultsets as having a scrollable cursor.
+    pub(crate) fn set_scrollable(&mut self) -> HdbResult<()> {
+        for return_value in &self.return_values {
+            if let HdbReturnValue::ResultSet(ref rs) = *return_value {
+                rs.set_scrollable()?;
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn resultset(
         mut int_return_values: Vec<InternalReturnValue>,
     ) -> HdbResult<HdbResponse> {
@@ -1,16 +1,25 @@
 use crate::HdbError;
+#[cfg(feature = "rust_decimal")]
+use bigdecimal::BigDecimal;
 use bigdecimal::ToPrimitive;
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::prelude::FromPrimitive;
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
 use serde_db::de::{
     ConversionError, DbValue, DbValueInto, DeserializableResultset, DeserializableRow,
     DeserializationError, DeserializationResult,
 };
+use std::convert::TryFrom;
 use std::error::Error;
 use std::{fmt, i16, i32, i64, i8, u16, u32, u8};
 
 use crate::protocol::parts::hdb_value::HdbValue;
 use crate::protocol::parts::resultset::ResultSet;
 use crate::protocol::parts::row::Row;
+#[cfg(feature = "geo")]
+use crate::types::Spatial;
 
 impl DeserializableResultset for ResultSet {
     type ROW = Row;
@@ -100,6 +109,9 @@ impl DbValueInto<u8> for HdbValue {
                 }
             }
             HdbValue::DECIMAL(bigdec) => bigdec.to_u8().ok_or_else(|| decimal_range("u8")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_u8().ok_or_else(|| decimal_range("u8")),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "u8"),
             value => Err(wrong_type(&value, "u8")),
         }
     }
@@ -134,6 +146,9 @@ impl DbValueInto<u16> for HdbValue {
                 }
             }
             HdbValue::DECIMAL(bigdec) => bigdec.to_u16().ok_or_else(|| decimal_range("u16")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_u16().ok_or_else(|| decimal_range("u16")),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "u16"),
             value => Err(wrong_type(&value, "u16")),
         }
     }
@@ -165,6 +180,9 @@ impl DbValueInto<u32> for HdbValue {
                 }
             }
             HdbValue::DECIMAL(bigdec) => bigdec.to_u32().ok_or_else(|| decimal_range("u32")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_u32().ok_or_else(|| decimal_range("u32")),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "u32"),
             value => Err(wrong_type(&value, "u32")),
         }
     }
@@ -196,6 +214,9 @@ impl DbValueInto<u64> for HdbValue {
                 }
             }
             HdbValue::DECIMAL(bigdec) => bigdec.to_u64().ok_or_else(|| decimal_range("u64")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_u64().ok_or_else(|| decimal_range("u64")),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "u64"),
             value => Err(wrong_type(&value, "u64")),
         }
     }
@@ -233,6 +254,9 @@ impl DbValueInto<i8> for HdbValue {
                 }
             }
             HdbValue::DECIMAL(bigdec) => bigdec.to_i8().ok_or_else(|| decimal_range("i8")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_i8().ok_or_else(|| decimal_range("i8")),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "i8"),
             value => Err(wrong_type(&value, "i8")),
         }
     }
@@ -258,6 +282,9 @@ impl DbValueInto<i16> for HdbValue {
                 }
             }
             HdbValue::DECIMAL(bigdec) => bigdec.to_i16().ok_or_else(|| decimal_range("i16")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_i16().ok_or_else(|| decimal_range("i16")),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "i16"),
             value => Err(wrong_type(&value, "i16")),
         }
     }
@@ -277,6 +304,9 @@ impl DbValueInto<i32> for HdbValue {
                 }
             }
             HdbValue::DECIMAL(bigdec) => bigdec.to_i32().ok_or_else(|| decimal_range("i32")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_i32().ok_or_else(|| decimal_range("i32")),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "i32"),
             value => Err(wrong_type(&value, "i32")),
         }
     }
@@ -292,6 +322,9 @@ impl DbValueInto<i64> for HdbValue {
             HdbValue::LONGDATE(ld) => Ok(*ld.ref_raw()),
             HdbValue::SECONDDATE(sd) => Ok(*sd.ref_raw()),
             HdbValue::DECIMAL(bigdec) => bigdec.to_i64().ok_or_else(|| decimal_range("i64")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_i64().ok_or_else(|| decimal_range("i64")),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "i64"),
             value => Err(wrong_type(&value, "i64")),
         }
     }
@@ -301,7 +334,10 @@ impl DbValueInto<f32> for HdbValue {
     fn try_into(self) -> Result<f32, ConversionError> {
         match self {
             HdbValue::DECIMAL(bigdec) => bigdec.to_f32().ok_or_else(|| decimal_range("f32")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_f32().ok_or_else(|| decimal_range("f32")),
             HdbValue::REAL(f) => Ok(f),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "f32"),
             value => Err(wrong_type(&value, "f32")),
         }
     }
@@ -311,7 +347,10 @@ impl DbValueInto<f64> for HdbValue {
     fn try_into(self) -> Result<f64, ConversionError> {
         match self {
             HdbValue::DECIMAL(bigdec) => bigdec.to_f64().ok_or_else(|| decimal_range("f64")),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => dec.to_f64().ok_or_else(|| decimal_range("f64")),
             HdbValue::DOUBLE(f) => Ok(f),
+            HdbValue::ALPHANUM(s) => alphanum_into(&s, "f64"),
             value => Err(wrong_type(&value, "f64")),
         }
     }
@@ -327,45 +366,141 @@ impl DbValueInto<String> for HdbValue {
             HdbValue::BIGINT(i) => Ok(format!("{}", i)),
             HdbValue::REAL(f) => Ok(format!("{}", f)),
             HdbValue::DOUBLE(f) => Ok(format!("{}", f)),
-            HdbValue::TEXT(s) | HdbValue::STRING(s) => Ok(s),
+            HdbValue::TEXT(s) | HdbValue::STRING(s) | HdbValue::ALPHANUM(s) => Ok(s),
 
             HdbValue::LONGDATE(ld) => Ok(str_from(&ld)),
             HdbValue::SECONDDATE(sd) => Ok(str_from(&sd)),
             HdbValue::DAYDATE(date) => Ok(str_from(&date)),
             HdbValue::SECONDTIME(time) => Ok(str_from(&time)),
             HdbValue::DECIMAL(bigdec) => Ok(format!("{}", bigdec)),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(dec) => Ok(format!("{}", dec)),
             HdbValue::CLOB(clob) => Ok(clob
                 .into_string()
                 .map_err(|e| ConversionError::Incomplete(e.description().to_owned()))?),
             HdbValue::NCLOB(nclob) => Ok(nclob
                 .into_string()
                 .map_err(|e| ConversionError::Incomplete(e.description().to_owned()))?),
+            #[cfg(feature = "geo")]
+            HdbValue::GEOMETRY(v) | HdbValue::POINT(v) => Ok(Spatial::from_ewkb(&v)
+                .map_err(|e| ConversionError::ValueType(e.to_string()))?
+                .to_string()),
             value => Err(wrong_type(&value, "String")),
         }
     }
 }
 
+#[cfg(feature = "rust_decimal")]
+impl DbValueInto<Decimal> for HdbValue {
+    fn try_into(self) -> Result<Decimal, ConversionError> {
+        trace!("try_into -> Decimal");
+        match self {
+            HdbValue::RUST_DECIMAL(dec) => Ok(dec),
+            HdbValue::DECIMAL(bigdec) => decimal_from_bigdecimal(&bigdec),
+            HdbValue::TINYINT(i) => Ok(Decimal::from(i)),
+            HdbValue::SMALLINT(i) => Ok(Decimal::from(i)),
+            HdbValue::INT(i) => Ok(Decimal::from(i)),
+            HdbValue::BIGINT(i) => Ok(Decimal::from(i)),
+            HdbValue::REAL(f) => Decimal::from_f32(f).ok_or_else(|| decimal_range("Decimal")),
+            HdbValue::DOUBLE(f) => Decimal::from_f64(f).ok_or_else(|| decimal_range("Decimal")),
+            HdbValue::STRING(s) | HdbValue::ALPHANUM(s) => {
+                Decimal::from_str_exact(&s).map_err(|_| wrong_type(&HdbValue::STRING(s), "Decimal"))
+            }
+            value => Err(wrong_type(&value, "Decimal")),
+        }
+    }
+}
+
 impl DbValueInto<NaiveDateTime> for HdbValue {
     fn try_into(self) -> Result<NaiveDateTime, ConversionError> {
         trace!("try_into -> NaiveDateTime");
+        match self {
+            HdbValue::LONGDATE(ld) => NaiveDateTime::try_from(ld).map_err(date_conversion),
+            HdbValue::SECONDDATE(sd) => NaiveDateTime::try_from(sd).map_err(date_conversion),
+            _ => Err(ConversionError::ValueType(
+                "Not a LongDate or SecondDate value".to_owned(),
+            )),
+        }
+    }
+}
+
+impl DbValueInto<DateTime<Utc>> for HdbValue {
+    fn try_into(self) -> Result<DateTime<Utc>, ConversionError> {
+        trace!("try_into -> DateTime<Utc>");
+        match self {
+            HdbValue::LONGDATE(ld) => DateTime::<Utc>::try_from(ld).map_err(date_conversion),
+            HdbValue::SECONDDATE(sd) => DateTime::<Utc>::try_from(sd).map_err(date_conversion),
+            value => Err(wrong_type(&value, "DateTime<Utc>")),
+        }
+    }
+}
+
+impl DbValueInto<NaiveDate> for HdbValue {
+    fn try_into(self) -> Result<NaiveDate, ConversionError> {
+        trace!("try_into -> NaiveDate");
+        match self {
+            HdbValue::DAYDATE(dd) => NaiveDate::try_from(dd).map_err(date_conversion),
+            value => Err(wrong_type(&value, "NaiveDate")),
+        }
+    }
+}
+
+impl DbValueInto<NaiveTime> for HdbValue {
+    fn try_into(self) -> Result<NaiveTime, ConversionError> {
+        trace!("try_into -> NaiveTime");
+        match self {
+            HdbValue::SECONDTIME(st) => NaiveTime::try_from(st).map_err(date_conversion),
+            value => Err(wrong_type(&value, "NaiveTime")),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl DbValueInto<time::PrimitiveDateTime> for HdbValue {
+    fn try_into(self) -> Result<time::PrimitiveDateTime, ConversionError> {
+        trace!("try_into -> time::PrimitiveDateTime");
         match self {
             HdbValue::LONGDATE(ld) => {
-                let (year, month, day, hour, min, sec, frac) = ld.as_ymd_hms_f();
-                Ok(NaiveDateTime::new(
-                    NaiveDate::from_ymd(year, month, day),
-                    NaiveTime::from_hms_nano(hour, min, sec, frac * 100),
-                ))
+                time::PrimitiveDateTime::try_from(ld).map_err(date_conversion)
             }
             HdbValue::SECONDDATE(sd) => {
-                let (year, month, day, hour, min, sec) = sd.as_ymd_hms();
-                Ok(NaiveDateTime::new(
-                    NaiveDate::from_ymd(year, month, day),
-                    NaiveTime::from_hms(hour, min, sec),
-                ))
+                time::PrimitiveDateTime::try_from(sd).map_err(date_conversion)
             }
-            _ => Err(ConversionError::ValueType(
-                "Not a LongDate or SecondDate value".to_owned(),
-            )),
+            value => Err(wrong_type(&value, "time::PrimitiveDateTime")),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl DbValueInto<time::OffsetDateTime> for HdbValue {
+    fn try_into(self) -> Result<time::OffsetDateTime, ConversionError> {
+        trace!("try_into -> time::OffsetDateTime");
+        match self {
+            HdbValue::LONGDATE(ld) => time::OffsetDateTime::try_from(ld).map_err(date_conversion),
+            HdbValue::SECONDDATE(sd) => time::OffsetDateTime::try_from(sd).map_err(date_conversion),
+            value => Err(wrong_type(&value, "time::OffsetDateTime")),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl DbValueInto<time::Date> for HdbValue {
+    fn try_into(self) -> Result<time::Date, ConversionError> {
+        trace!("try_into -> time::Date");
+        match self {
+            HdbValue::DAYDATE(dd) => time::Date::try_from(dd).map_err(date_conversion),
+            value => Err(wrong_type(&value, "time::Date")),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl DbValueInto<time::Time> for HdbValue {
+    fn try_into(self) -> Result<time::Time, ConversionError> {
+        trace!("try_into -> time::Time");
+        match self {
+            HdbValue::SECONDTIME(st) => time::Time::try_from(st).map_err(date_conversion),
+            value => Err(wrong_type(&value, "time::Time")),
         }
     }
 }
@@ -381,11 +516,113 @@ impl DbValueInto<Vec<u8>> for HdbValue {
 
             HdbValue::TEXT(s) | HdbValue::STRING(s) => Ok(s.into_bytes()),
 
+            HdbValue::ARRAY(values) => values
+                .into_iter()
+                .map(DbValueInto::<u8>::try_into)
+                .collect(),
+
             value => Err(wrong_type(&value, "Vec<u8>")),
         }
     }
 }
 
+// Deserializes a single value into a rust type, like serde_db does,
+// and additionally maps arrays to sequences.
+pub(crate) struct ValueDeserializer(HdbValue);
+impl ValueDeserializer {
+    pub fn new(value: HdbValue) -> ValueDeserializer {
+        ValueDeserializer(value)
+    }
+}
+
+macro_rules! deserialize_via_db_value_into {
+    ($($method:ident => $visit:ident($t:ty)),*) => {$(
+        fn $method<V>(self, visitor: V) -> DeserializationResult<V::Value>
+        where
+            V: serde::de::Visitor<'de>,
+        {
+            visitor.$visit(DbValueInto::<$t>::try_into(self.0)?)
+        }
+    )*};
+}
+
+impl<'de> serde::Deserializer<'de> for ValueDeserializer {
+    type Error = DeserializationError;
+
+    fn deserialize_any<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.0 {
+            HdbValue::ARRAY(_) => self.deserialize_seq(visitor),
+            value => visitor.visit_string(DbValueInto::<String>::try_into(value)?),
+        }
+    }
+
+    deserialize_via_db_value_into!(
+        deserialize_bool => visit_bool(bool),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+        deserialize_char => visit_string(String),
+        deserialize_str => visit_string(String),
+        deserialize_string => visit_string(String),
+        deserialize_bytes => visit_byte_buf(Vec<u8>),
+        deserialize_byte_buf => visit_byte_buf(Vec<u8>)
+    );
+
+    fn deserialize_option<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        if self.0.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.0 {
+            HdbValue::ARRAY(values) => visitor.visit_seq(ArrayAccess(values.into_iter())),
+            value => Err(wrong_type(&value, "a sequence").into()),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        unit unit_struct newtype_struct tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct ArrayAccess(std::vec::IntoIter<HdbValue>);
+impl<'de> serde::de::SeqAccess<'de> for ArrayAccess {
+    type Error = DeserializationError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> DeserializationResult<Option<T::Value>>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        match self.0.next() {
+            Some(value) => seed.deserialize(ValueDeserializer(value)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
 fn wrong_type(tv: &HdbValue, ovt: &str) -> ConversionError {
     ConversionError::ValueType(format!(
         "The value {:?} cannot be converted into type {}",
@@ -393,6 +630,13 @@ fn wrong_type(tv: &HdbValue, ovt: &str) -> ConversionError {
     ))
 }
 
+fn date_conversion(e: HdbError) -> ConversionError {
+    match e {
+        HdbError::Conversion(ce) => ce,
+        e => ConversionError::ValueType(e.to_string()),
+    }
+}
+
 fn number_range(value: i64, ovt: &str) -> ConversionError {
     ConversionError::NumberRange(format!(
         "The value {:?} exceeds the number range of type {}",
@@ -400,6 +644,23 @@ fn number_range(value: i64, ovt: &str) -> ConversionError {
     ))
 }
 
+// ALPHANUM values can be converted into numbers if they consist only of digits.
+fn alphanum_into<T: std::str::FromStr>(s: &str, ovt: &str) -> Result<T, ConversionError> {
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse::<T>().map_err(|_| {
+            ConversionError::NumberRange(format!(
+                "The value {:?} exceeds the number range of type {}",
+                s, ovt
+            ))
+        })
+    } else {
+        Err(ConversionError::ValueType(format!(
+            "The ALPHANUM value {:?} is not numeric and cannot be converted into type {}",
+            s, ovt
+        )))
+    }
+}
+
 fn decimal_range(ovt: &str) -> ConversionError {
     ConversionError::NumberRange(format!(
         "The given decimal value cannot be converted into a number of type {}",
@@ -407,6 +668,29 @@ fn decimal_range(ovt: &str) -> ConversionError {
     ))
 }
 
+// Values that need more than 28 digits are rejected, rather than rounded.
+#[cfg(feature = "rust_decimal")]
+fn decimal_from_bigdecimal(bigdec: &BigDecimal) -> Result<Decimal, ConversionError> {
+    let (bigint, mut exponent) = bigdec.as_bigint_and_exponent();
+    let mut mantissa = bigint.to_i128().ok_or_else(|| decimal_range("Decimal"))?;
+    while exponent < 0 {
+        mantissa = mantissa
+            .checked_mul(10)
+            .ok_or_else(|| decimal_range("Decimal"))?;
+        exponent += 1;
+    }
+    // trailing zeros can be dropped without losing precision
+    while exponent > 28 && mantissa % 10 == 0 {
+        mantissa /= 10;
+        exponent -= 1;
+    }
+    if exponent > 28 {
+        return Err(decimal_range("Decimal"));
+    }
+    Decimal::try_from_i128_with_scale(mantissa, exponent as u32)
+        .map_err(|_| decimal_range("Decimal"))
+}
+
 /// Deserializes a `LongDate` into a String format.
 fn str_from<T: fmt::Display>(t: &T) -> String {
     format!("{}", t)
@@ -418,3 +702,36 @@ impl From<HdbError> for DeserializationError {
         DeserializationError::Usage(e.to_string())
     }
 }
+
+#[cfg(all(test, feature = "rust_decimal"))]
+mod tests {
+    use super::decimal_from_bigdecimal;
+    use bigdecimal::BigDecimal;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_decimal_from_bigdecimal() {
+        for s in &[
+            "0",
+            "12.34560",
+            "-1234.5",
+            "-0.0001",
+            "1200",
+            "922337203685477.5807",
+        ] {
+            let dec = decimal_from_bigdecimal(&BigDecimal::from_str(s).unwrap()).unwrap();
+            assert_eq!(dec.to_string(), *s);
+        }
+
+        // trailing zeros beyond the maximal scale are dropped
+        let bigdec = BigDecimal::from_str("1.000000000000000000000000000000").unwrap();
+        let dec = decimal_from_bigdecimal(&bigdec).unwrap();
+        assert_eq!(dec, Decimal::from(1));
+
+        // values that need more than 28 digits are rejected
+        for s in &["0.00000000000000000000000000001", "1e40"] {
+            assert!(decimal_from_bigdecimal(&BigDecimal::from_str(s).unwrap()).is_err());
+        }
+    }
+}
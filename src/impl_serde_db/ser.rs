@@ -11,9 +11,13 @@ use crate::types::DayDate;
 use crate::types::LongDate;
 use crate::types::SecondDate;
 use crate::types::SecondTime;
+#[cfg(feature = "geo")]
+use crate::types::Spatial;
 
 use bigdecimal::BigDecimal;
 use bigdecimal::FromPrimitive;
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
 use serde_db::ser::{parse_error, DbvFactory, SerializationError};
 use std::str::FromStr;
 use std::{i16, i32, i64, i8, u16, u32, u8};
@@ -49,6 +53,13 @@ impl DbvFactory for ParameterDescriptor {
                     BigDecimal::from_i8(value).ok_or_else(|| decimal_range(input_type))?,
                 )
             }
+            TypeId::ALPHANUM => {
+                if value >= 0 {
+                    HdbValue::ALPHANUM(value.to_string())
+                } else {
+                    return Err(SerializationError::Range(input_type, self.descriptor()));
+                }
+            }
             _ => return Err(type_mismatch(input_type, self.descriptor())),
         })
     }
@@ -73,6 +84,13 @@ impl DbvFactory for ParameterDescriptor {
                     BigDecimal::from_i16(value).ok_or_else(|| decimal_range(input_type))?,
                 )
             }
+            TypeId::ALPHANUM => {
+                if value >= 0 {
+                    HdbValue::ALPHANUM(value.to_string())
+                } else {
+                    return Err(SerializationError::Range(input_type, self.descriptor()));
+                }
+            }
             _ => return Err(type_mismatch(input_type, self.descriptor())),
         })
     }
@@ -104,6 +122,13 @@ impl DbvFactory for ParameterDescriptor {
             }
             TypeId::DAYDATE => HdbValue::DAYDATE(DayDate::new(value)),
             TypeId::SECONDTIME => HdbValue::SECONDTIME(SecondTime::new(value)),
+            TypeId::ALPHANUM => {
+                if value >= 0 {
+                    HdbValue::ALPHANUM(value.to_string())
+                } else {
+                    return Err(SerializationError::Range(input_type, self.descriptor()));
+                }
+            }
             _ => return Err(type_mismatch(input_type, self.descriptor())),
         })
     }
@@ -142,6 +167,13 @@ impl DbvFactory for ParameterDescriptor {
                     BigDecimal::from_i64(value).ok_or_else(|| decimal_range(input_type))?,
                 )
             }
+            TypeId::ALPHANUM => {
+                if value >= 0 {
+                    HdbValue::ALPHANUM(value.to_string())
+                } else {
+                    return Err(SerializationError::Range(input_type, self.descriptor()));
+                }
+            }
             _ => return Err(type_mismatch(input_type, self.descriptor())),
         })
     }
@@ -159,6 +191,7 @@ impl DbvFactory for ParameterDescriptor {
                     BigDecimal::from_u8(value).ok_or_else(|| decimal_range(input_type))?,
                 )
             }
+            TypeId::ALPHANUM => HdbValue::ALPHANUM(value.to_string()),
             _ => return Err(type_mismatch(input_type, self.descriptor())),
         })
     }
@@ -188,6 +221,7 @@ impl DbvFactory for ParameterDescriptor {
                     BigDecimal::from_u16(value).ok_or_else(|| decimal_range(input_type))?,
                 )
             }
+            TypeId::ALPHANUM => HdbValue::ALPHANUM(value.to_string()),
             _ => return Err(type_mismatch(input_type, self.descriptor())),
         })
     }
@@ -224,6 +258,7 @@ impl DbvFactory for ParameterDescriptor {
                     BigDecimal::from_u32(value).ok_or_else(|| decimal_range(input_type))?,
                 )
             }
+            TypeId::ALPHANUM => HdbValue::ALPHANUM(value.to_string()),
             _ => return Err(type_mismatch(input_type, self.descriptor())),
         })
     }
@@ -265,6 +300,7 @@ impl DbvFactory for ParameterDescriptor {
                     BigDecimal::from_u64(value).ok_or_else(|| decimal_range(input_type))?,
                 )
             }
+            TypeId::ALPHANUM => HdbValue::ALPHANUM(value.to_string()),
             _ => return Err(type_mismatch(input_type, self.descriptor())),
         })
     }
@@ -310,7 +346,8 @@ impl DbvFactory for ParameterDescriptor {
             | TypeId::STRING
             | TypeId::NSTRING
             | TypeId::TEXT
-            | TypeId::SHORTTEXT => HdbValue::STRING(s),
+            | TypeId::SHORTTEXT
+            | TypeId::ALPHANUM => HdbValue::STRING(s),
             _ => return Err(type_mismatch("char", self.descriptor())),
         })
     }
@@ -344,23 +381,46 @@ impl DbvFactory for ParameterDescriptor {
             | TypeId::NSTRING
             | TypeId::TEXT
             | TypeId::SHORTTEXT
+            | TypeId::ALPHANUM
             | TypeId::CLOB
             | TypeId::NCLOB => HdbValue::STRING(String::from(value)),
 
             TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 => {
+                // Decimal values arrive here, since they serialize as strings;
+                // values that don't fit into a Decimal are handled with BigDecimal
+                #[cfg(feature = "rust_decimal")]
+                {
+                    if let Ok(dec) = Decimal::from_str_exact(value) {
+                        return Ok(HdbValue::RUST_DECIMAL(dec));
+                    }
+                }
                 HdbValue::DECIMAL(BigDecimal::from_str(value).map_err(map_bd)?)
             }
 
-            TypeId::LONGDATE => {
+            TypeId::LONGDATE | TypeId::TIMESTAMP => {
                 HdbValue::LONGDATE(LongDate::from_date_string(value).map_err(map_d)?)
             }
             TypeId::SECONDDATE => {
                 HdbValue::SECONDDATE(SecondDate::from_date_string(value).map_err(map_d)?)
             }
-            TypeId::DAYDATE => HdbValue::DAYDATE(DayDate::from_date_string(value).map_err(map_d)?),
-            TypeId::SECONDTIME => {
+            TypeId::DAYDATE | TypeId::DATE => {
+                HdbValue::DAYDATE(DayDate::from_date_string(value).map_err(map_d)?)
+            }
+            TypeId::SECONDTIME | TypeId::TIME => {
                 HdbValue::SECONDTIME(SecondTime::from_date_string(value).map_err(map_d)?)
             }
+            // spatial values can be given as WKT
+            #[cfg(feature = "geo")]
+            TypeId::GEOMETRY | TypeId::POINT => {
+                let ewkb = Spatial::from_wkt(value)
+                    .map_err(|e| parse_error(value, "WKT".to_string(), Some(Box::new(e))))?
+                    .to_ewkb();
+                if tid == TypeId::POINT {
+                    HdbValue::POINT(ewkb)
+                } else {
+                    HdbValue::GEOMETRY(ewkb)
+                }
+            }
             _ => return Err(type_mismatch("&str", self.descriptor())),
         })
     }
@@ -27,7 +27,10 @@ extern crate log;
 pub use r2d2;
 pub use serde_db;
 
+#[cfg(feature = "async")]
+mod async_impl;
 mod authentication;
+mod cancel_handle;
 mod conn_core;
 mod connection;
 mod connection_manager;
@@ -35,6 +38,8 @@ mod hdb_error;
 mod hdb_response;
 mod hdb_return_value;
 mod impl_serde_db;
+#[cfg(feature = "mock_server")]
+mod mock_server;
 mod prepared_statement;
 mod protocol;
 mod xa_impl;
@@ -43,16 +48,27 @@ pub mod code_examples;
 
 pub use crate::conn_core::connect_params::{ConnectParams, IntoConnectParams};
 pub use crate::conn_core::connect_params_builder::ConnectParamsBuilder;
+pub use crate::conn_core::trace::TraceReplay;
+pub use crate::conn_core::transport::Transport;
 
 #[cfg(feature = "tls")]
-pub use crate::conn_core::connect_params::ServerCerts;
+pub use crate::conn_core::connect_params::{ClientCert, ServerCerts};
 
+#[cfg(feature = "async")]
+pub use crate::async_impl::{AsyncConnection, AsyncPreparedStatement, AsyncResultSet};
+
+#[cfg(feature = "mock_server")]
+pub use crate::mock_server::{MockReply, MockServer};
+
+pub use crate::authentication::{TokenProvider, TokenSession};
+pub use crate::cancel_handle::CancelHandle;
 pub use crate::connection::Connection;
 pub use crate::connection_manager::ConnectionManager;
 pub use crate::hdb_error::{HdbError, HdbResult};
 pub use crate::hdb_response::HdbResponse;
 pub use crate::hdb_return_value::HdbReturnValue;
 pub use crate::prepared_statement::PreparedStatement;
+pub use crate::protocol::packet_dump::dump_packets;
 pub use crate::protocol::parts::output_parameters::OutputParameters;
 pub use crate::protocol::parts::resultset::ResultSet;
 pub use crate::protocol::parts::row::Row;
@@ -82,5 +98,11 @@ pub mod types {
     pub use crate::types_impl::longdate::LongDate;
     pub use crate::types_impl::seconddate::SecondDate;
     pub use crate::types_impl::secondtime::SecondTime;
+
+    #[cfg(feature = "geo")]
+    pub use crate::types_impl::spatial::Spatial;
 }
+
+#[cfg(feature = "time")]
+pub use crate::types_impl::time_serde;
 pub use crate::protocol::parts::hdb_value::HdbValue;
@@ -0,0 +1,197 @@
+//! A fake HANA server for tests that run without a database.
+//!
+//! The mock server listens on a loopback port and speaks the wire protocol
+//! (message, segment, and part framing) that `hdbconnect` itself uses.
+//! It accepts logons with SCRAMSHA256 for a single user, and answers all further
+//! requests with replies that were scripted by the test.
+//! LOBs in scripted resultsets are handed out in chunks,
+//! so that the driver's LOB reading is exercised as well.
+
+mod session;
+mod wire;
+
+use crate::{
+    ConnectParams, ConnectParamsBuilder, Connection, HdbResult, HdbValue, ResultSet, TypeId,
+};
+use std::collections::VecDeque;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A scripted reply of the [`MockServer`](struct.MockServer.html).
+#[derive(Clone, Debug)]
+pub enum MockReply {
+    /// A resultset with the given columns (name and type) and rows.
+    ///
+    /// All columns are nullable, use `HdbValue::NULL` for null values.
+    /// The server can encode values of integer types, `REAL`, `DOUBLE`, `BOOLEAN`,
+    /// string types (`HdbValue::STRING`), binary types (`HdbValue::BINARY`),
+    /// `ALPHANUM` (`HdbValue::ALPHANUM`), arrays (`HdbValue::ARRAY`),
+    /// spatial types (`HdbValue::GEOMETRY`, `HdbValue::POINT`),
+    /// and LOBs (`HdbValue::BINARY` for `BLOB`, `HdbValue::STRING` for `CLOB` and `NCLOB`).
+    /// The legacy types `DATE`, `TIME`, and `TIMESTAMP` are encoded
+    /// from `HdbValue::DAYDATE`, `HdbValue::SECONDTIME`, and `HdbValue::LONGDATE`.
+    /// Of the LOBs, only the first 32 bytes (characters with `NCLOB`) are sent
+    /// with the resultset; the rest has to be read by the driver with additional requests.
+    ResultSet {
+        /// Column names and types.
+        columns: Vec<(String, TypeId)>,
+        /// The rows, each with one value per column.
+        rows: Vec<Vec<HdbValue>>,
+    },
+    /// The number of affected rows, as for DML statements.
+    RowsAffected(Vec<usize>),
+    /// Plain success, as for DDL statements.
+    Success,
+    /// A server error with the given code and text.
+    Error(i32, String),
+}
+
+/// A fake HANA server that answers with scripted replies.
+///
+/// The server runs in background threads, until it is dropped.
+/// Each connection is served in its own thread, all connections take their replies
+/// from the same script.
+///
+/// # Example
+///
+/// ```rust
+/// # use hdbconnect::{Connection, HdbResult, HdbValue, MockReply, MockServer, TypeId};
+/// # fn foo() -> HdbResult<()> {
+/// let server = MockServer::start("MEIER", "schLau")?;
+/// server.push_reply(MockReply::ResultSet {
+///     columns: vec![("NAME".to_string(), TypeId::NVARCHAR)],
+///     rows: vec![vec![HdbValue::STRING("Hugo".to_string())]],
+/// });
+///
+/// let mut connection = Connection::new(server.connect_params())?;
+/// let names: Vec<String> = connection.query("select name from persons")?.try_into()?;
+/// assert_eq!(names, vec!["Hugo"]);
+/// assert_eq!(server.commands(), vec!["select name from persons"]);
+/// # Ok(())
+/// # }
+/// # foo().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    script: Arc<Script>,
+    stopped: Arc<AtomicBool>,
+}
+
+// The state that is shared between the MockServer and its connections.
+#[derive(Debug)]
+struct Script {
+    dbuser: String,
+    password: String,
+    replies: Mutex<VecDeque<MockReply>>,
+    commands: Mutex<Vec<String>>,
+    data_format_version: AtomicI32,
+}
+
+impl MockServer {
+    /// Starts a server on a free loopback port that accepts logons of the given user.
+    pub fn start(dbuser: &str, password: &str) -> HdbResult<MockServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let script = Arc::new(Script {
+            dbuser: dbuser.to_string(),
+            password: password.to_string(),
+            replies: Mutex::new(VecDeque::new()),
+            commands: Mutex::new(Vec::new()),
+            data_format_version: AtomicI32::new(8),
+        });
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let (script_clone, stopped_clone) = (Arc::clone(&script), Arc::clone(&stopped));
+        thread::spawn(move || {
+            for (i, stream) in listener.incoming().enumerate() {
+                if stopped_clone.load(Ordering::SeqCst) {
+                    break;
+                }
+                match stream {
+                    Ok(stream) => {
+                        let script = Arc::clone(&script_clone);
+                        thread::spawn(move || session::serve(stream, i as i32 + 1, &script));
+                    }
+                    Err(e) => warn!("MockServer: accepting a connection failed with {}", e),
+                }
+            }
+        });
+        debug!("MockServer listens on {}", addr);
+
+        Ok(MockServer {
+            addr,
+            script,
+            stopped,
+        })
+    }
+
+    /// The address on which the server listens.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// A builder for connect parameters that point to this server,
+    /// with the server's user and password.
+    pub fn connect_params_builder(&self) -> ConnectParamsBuilder {
+        let mut builder = ConnectParams::builder();
+        builder
+            .hostname(self.addr.ip().to_string())
+            .port(self.addr.port())
+            .dbuser(&self.script.dbuser)
+            .password(&self.script.password);
+        builder
+    }
+
+    /// Connect parameters that point to this server, with the server's user and password.
+    pub fn connect_params(&self) -> ConnectParams {
+        self.connect_params_builder().build().unwrap(/* is complete */)
+    }
+
+    /// Appends a reply to the script.
+    ///
+    /// Every request that is not part of the logon or of LOB reading consumes
+    /// the next reply; if the script is exhausted, the server answers with an error.
+    pub fn push_reply(&self, reply: MockReply) {
+        self.script.replies.lock().unwrap().push_back(reply);
+    }
+
+    /// Appends the reply to the script, and runs the statement with a new connection.
+    ///
+    /// Is a shortcut for tests that check how a scripted resultset is converted.
+    pub fn query(&self, stmt: &str, reply: MockReply) -> HdbResult<ResultSet> {
+        self.push_reply(reply);
+        Connection::new(self.connect_params())?.query(stmt)
+    }
+
+    /// The number of scripted replies that were not yet consumed.
+    pub fn pending_replies(&self) -> usize {
+        self.script.replies.lock().unwrap().len()
+    }
+
+    /// Sets the data format version with which the server answers logons (default: 8).
+    ///
+    /// Servers with lower versions, like HANA 1.0, use e.g. the legacy types
+    /// `DATE`, `TIME`, and `TIMESTAMP` instead of `DAYDATE`, `SECONDTIME`, and `LONGDATE`;
+    /// the scripted resultsets have to use the types that fit to the version.
+    pub fn set_data_format_version(&self, version: i32) {
+        self.script
+            .data_format_version
+            .store(version, Ordering::SeqCst);
+    }
+
+    /// The SQL commands that were received so far, in the order of their arrival.
+    pub fn commands(&self) -> Vec<String> {
+        self.script.commands.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        // wake up the listener thread, so that it can stop
+        self.stopped.store(true, Ordering::SeqCst);
+        TcpStream::connect(self.addr).ok();
+    }
+}
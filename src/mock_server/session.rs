@@ -0,0 +1,248 @@
+use super::wire::{MockLob, OutgoingReply, ReceivedRequest};
+use super::{MockReply, Script};
+use crate::authentication::crypto_util::scram_sha256;
+use crate::protocol::partkind::PartKind;
+use crate::protocol::parts::authfields::AuthFields;
+use crate::protocol::parts::connect_options::{ConnOptId, ConnectOptions};
+use crate::protocol::parts::option_value::OptionValue;
+use crate::protocol::reply_type::ReplyType;
+use crate::protocol::request_type::RequestType;
+use crate::protocol::util;
+use crate::{HdbError, HdbResult};
+use byteorder::{LittleEndian, ReadBytesExt};
+use rand::{thread_rng, RngCore};
+use secstr::SecStr;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::atomic::Ordering;
+
+// The error code that HANA uses for failed logons
+const AUTHENTICATION_FAILED: i32 = 10;
+
+// Serves a single connection, until the client disconnects.
+pub(super) fn serve(stream: TcpStream, connection_id: i32, script: &Script) {
+    let mut session = Session {
+        script,
+        connection_id,
+        session_id: 0,
+        scram: None,
+        lobs: Vec::new(),
+        rs_count: 0,
+    };
+    if let Err(e) = session.serve(stream) {
+        warn!("MockServer: connection {} failed with {}", connection_id, e);
+    }
+}
+
+struct Session<'a> {
+    script: &'a Script,
+    connection_id: i32,
+    session_id: i64,
+    scram: Option<ScramState>,
+    lobs: Vec<MockLob>,
+    rs_count: u64,
+}
+
+// What the server must remember between the two authentication requests.
+struct ScramState {
+    salt: Vec<u8>,
+    server_nonce: Vec<u8>,
+    client_challenge: Vec<u8>,
+}
+
+impl<'a> Session<'a> {
+    fn serve(&mut self, stream: TcpStream) -> HdbResult<()> {
+        let mut reader = io::BufReader::new(stream.try_clone()?);
+        let mut writer = io::BufWriter::new(stream);
+
+        // the initial request has 14 bytes, its content is not evaluated
+        reader.read_exact(&mut [0_u8; 14])?;
+        writer.write_all(&[4, 20, 0, 4, 1, 0, 0, 0])?;
+        writer.flush()?;
+
+        while let Some(request) = ReceivedRequest::parse(&mut reader)? {
+            debug!(
+                "MockServer: connection {} got request of type {:?}",
+                self.connection_id, request.request_type
+            );
+            let reply = match request.request_type {
+                RequestType::Authenticate => self.first_auth_reply(&request),
+                RequestType::Connect => self.second_auth_reply(&request),
+                RequestType::Disconnect => return Ok(()),
+                _ if self.session_id == 0 => Err(HdbError::Usage(
+                    "MockServer: the session is not authenticated".to_owned(),
+                )),
+                RequestType::ReadLob => self.read_lob_reply(&request),
+                _ => self.scripted_reply(&request),
+            }
+            .unwrap_or_else(|e| OutgoingReply::error(0, &e.to_string()));
+            reply.emit(self.session_id, request.seq_number, &mut writer)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    // The client proposes some authentication methods; we choose SCRAMSHA256
+    // and answer with salt and server nonce.
+    fn first_auth_reply(&mut self, request: &ReceivedRequest) -> HdbResult<OutgoingReply> {
+        let mut fields = auth_fields(request)?.into_iter();
+        if fields.next().as_ref().map(|user| &user[..]) != Some(self.script.dbuser.as_bytes()) {
+            return Ok(OutgoingReply::error(
+                AUTHENTICATION_FAILED,
+                "authentication failed",
+            ));
+        }
+        let mut client_challenge = None;
+        while let (Some(method), Some(challenge)) = (fields.next(), fields.next()) {
+            if method == b"SCRAMSHA256" {
+                client_challenge = Some(challenge);
+            }
+        }
+        let client_challenge = match client_challenge {
+            Some(client_challenge) => client_challenge,
+            None => {
+                return Ok(OutgoingReply::error(
+                    AUTHENTICATION_FAILED,
+                    "authentication failed: the mock server supports only SCRAMSHA256",
+                ));
+            }
+        };
+
+        let mut salt = vec![0_u8; 16];
+        let mut server_nonce = vec![0_u8; 48];
+        thread_rng().fill_bytes(&mut salt);
+        thread_rng().fill_bytes(&mut server_nonce);
+
+        let mut server_data = AuthFields::with_capacity(2);
+        server_data.push(salt.clone());
+        server_data.push(server_nonce.clone());
+        let mut buf = Vec::<u8>::with_capacity(server_data.size());
+        server_data.emit(&mut buf)?;
+
+        self.scram = Some(ScramState {
+            salt,
+            server_nonce,
+            client_challenge,
+        });
+        let mut reply = OutgoingReply::new(ReplyType::Nil);
+        reply.push_auth_fields(vec![b"SCRAMSHA256".to_vec(), buf])?;
+        Ok(reply)
+    }
+
+    // Verifies the client proof, and opens the session.
+    fn second_auth_reply(&mut self, request: &ReceivedRequest) -> HdbResult<OutgoingReply> {
+        let scram = self.scram.take().ok_or_else(|| {
+            HdbError::Usage("MockServer: CONNECT without AUTHENTICATE".to_owned())
+        })?;
+        let fields = auth_fields(request)?;
+        let (expected_proof, _) = scram_sha256(
+            &scram.salt,
+            &scram.server_nonce,
+            &scram.client_challenge,
+            &SecStr::from(self.script.password.as_str()),
+        )?;
+        // the client proof is again a list of fields, with the proof as only element
+        let proof_ok = fields.len() == 3
+            && fields[1] == b"SCRAMSHA256"
+            && fields[2].len() == 3 + expected_proof.len()
+            && fields[2][3..] == expected_proof[..];
+        if !proof_ok {
+            return Ok(OutgoingReply::error(
+                AUTHENTICATION_FAILED,
+                "authentication failed",
+            ));
+        }
+
+        self.session_id = 0x4D4F_434B_0000_0000 + i64::from(self.connection_id);
+        let mut connect_options = ConnectOptions::default();
+        connect_options.set_value(
+            ConnOptId::ConnectionID,
+            OptionValue::INT(self.connection_id),
+        );
+        connect_options.set_value(ConnOptId::SystemID, OptionValue::STRING("MCK".to_owned()));
+        connect_options.set_value(
+            ConnOptId::DatabaseName,
+            OptionValue::STRING("MOCK".to_owned()),
+        );
+        connect_options.set_value(
+            ConnOptId::DataFormatVersion2,
+            OptionValue::INT(self.script.data_format_version.load(Ordering::SeqCst)),
+        );
+        connect_options.set_value(
+            ConnOptId::FullVersionString,
+            OptionValue::STRING("2.00.000.00 (mock server)".to_owned()),
+        );
+
+        // the client evaluates the parts from the end
+        let mut reply = OutgoingReply::new(ReplyType::Nil);
+        reply.push_auth_fields(vec![b"SCRAMSHA256".to_vec(), vec![]])?;
+        reply.push_connect_options(&connect_options)?;
+        reply.push_empty_topology();
+        Ok(reply)
+    }
+
+    fn read_lob_reply(&mut self, request: &ReceivedRequest) -> HdbResult<OutgoingReply> {
+        let mut rdr = io::Cursor::new(
+            request
+                .part(PartKind::ReadLobRequest)
+                .ok_or_else(|| HdbError::Usage("MockServer: no ReadLobRequest".to_owned()))?,
+        );
+        let locator_id = rdr.read_u64::<LittleEndian>()?; // I8
+        let offset = rdr.read_u64::<LittleEndian>()?; // I8, starts with 1
+        let length = rdr.read_i32::<LittleEndian>()?; // I4
+
+        let lob = self
+            .lobs
+            .get((locator_id as usize).wrapping_sub(1))
+            .ok_or_else(|| HdbError::Usage(format!("MockServer: unknown LOB {}", locator_id)))?;
+        let (chunk, is_last_data) =
+            lob.chunk(offset.saturating_sub(1) as usize, length.max(0) as usize);
+        let mut reply = OutgoingReply::new(ReplyType::ReadLob);
+        reply.push_read_lob_reply(locator_id, chunk, is_last_data);
+        Ok(reply)
+    }
+
+    fn scripted_reply(&mut self, request: &ReceivedRequest) -> HdbResult<OutgoingReply> {
+        if let Some(command) = request.part(PartKind::Command) {
+            let command = util::string_from_cesu8(command.to_vec())?;
+            self.script.commands.lock()?.push(command);
+        }
+
+        let o_mock_reply = self.script.replies.lock()?.pop_front();
+        Ok(match o_mock_reply {
+            Some(MockReply::ResultSet { columns, rows }) => {
+                self.rs_count += 1;
+                let mut reply = OutgoingReply::new(ReplyType::Select);
+                reply.push_resultset(self.rs_count, &columns, &rows, &mut self.lobs)?;
+                reply
+            }
+            Some(MockReply::RowsAffected(rows_affected)) => {
+                let mut reply = OutgoingReply::new(ReplyType::Nil);
+                reply.push_execution_results(&rows_affected);
+                reply
+            }
+            Some(MockReply::Success) => OutgoingReply::new(ReplyType::Ddl),
+            Some(MockReply::Error(code, text)) => OutgoingReply::error(code, &text),
+            None => OutgoingReply::error(
+                0,
+                &format!(
+                    "MockServer: no scripted reply left for request of type {:?}",
+                    request.request_type
+                ),
+            ),
+        })
+    }
+}
+
+fn auth_fields(request: &ReceivedRequest) -> HdbResult<Vec<Vec<u8>>> {
+    let data = request
+        .part(PartKind::Authentication)
+        .ok_or_else(|| HdbError::Usage("MockServer: no Authentication part".to_owned()))?;
+    let mut auth_fields = AuthFields::parse(&mut io::Cursor::new(data))?;
+    let mut fields = Vec::<Vec<u8>>::with_capacity(auth_fields.len());
+    while let Some(field) = auth_fields.pop() {
+        fields.push(field);
+    }
+    fields.reverse();
+    Ok(fields)
+}
@@ -0,0 +1,498 @@
+// Parsing of requests and encoding of replies, i.e., the server side
+// of what `protocol::request` and `protocol::reply` do.
+use crate::protocol::partkind::PartKind;
+use crate::protocol::parts::authfields::AuthFields;
+use crate::protocol::parts::connect_options::ConnectOptions;
+use crate::protocol::parts::hdb_value::{
+    emit_array, emit_length_and_bytes, emit_length_and_string,
+};
+use crate::protocol::reply_type::ReplyType;
+use crate::protocol::request_type::RequestType;
+use crate::protocol::util;
+use crate::types_impl::legacy_dates::{emit_date, emit_time, emit_timestamp};
+use crate::{HdbError, HdbResult, HdbValue, TypeId};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io;
+
+const MESSAGE_HEADER_SIZE: usize = 32;
+const SEGMENT_HEADER_SIZE: usize = 24;
+const PART_HEADER_SIZE: usize = 16;
+
+// Segment kinds
+const REPLY: i8 = 2;
+const ERROR: i8 = 5;
+
+// Of LOBs, only this many bytes (with NCLOBs: characters) are sent with the resultset
+const INLINE_LOB_LENGTH: usize = 32;
+
+// A request, with the raw data of its parts.
+#[derive(Debug)]
+pub(super) struct ReceivedRequest {
+    pub seq_number: i32,
+    pub request_type: RequestType,
+    parts: Vec<(PartKind, Vec<u8>)>,
+}
+impl ReceivedRequest {
+    pub fn part(&self, kind: PartKind) -> Option<&[u8]> {
+        self.parts
+            .iter()
+            .find(|(k, _)| k.to_i8() == kind.to_i8())
+            .map(|(_, data)| &data[..])
+    }
+
+    // Returns None if the client has closed the connection.
+    pub fn parse<T: io::Read>(rdr: &mut T) -> HdbResult<Option<ReceivedRequest>> {
+        let mut header = [0_u8; MESSAGE_HEADER_SIZE];
+        match rdr.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(HdbError::Io(e)),
+        }
+        let mut header = io::Cursor::new(&header[..]);
+        header.read_i64::<LittleEndian>()?; // I8 session_id
+        let seq_number = header.read_i32::<LittleEndian>()?; // I4
+        let varpart_length = header.read_u32::<LittleEndian>()? as usize; // UI4
+        header.read_u32::<LittleEndian>()?; // UI4 remaining_bufsize
+        header.read_i16::<LittleEndian>()?; // I2 no_of_segs
+        if header.read_u8()? & 0b10 != 0 {
+            return Err(HdbError::Impl(
+                "MockServer: compressed requests are not supported".to_owned(),
+            ));
+        }
+
+        let mut varpart = vec![0_u8; varpart_length];
+        rdr.read_exact(&mut varpart)?;
+        let mut rdr = io::Cursor::new(&varpart[..]);
+
+        // SEGMENT HEADER: 24 bytes
+        rdr.read_i32::<LittleEndian>()?; // I4 seg_size
+        rdr.read_i32::<LittleEndian>()?; // I4 seg_offset
+        let no_of_parts = rdr.read_i16::<LittleEndian>()?; // I2
+        rdr.read_i16::<LittleEndian>()?; // I2 seg_number
+        rdr.read_i8()?; // I1 seg_kind
+        let request_type = RequestType::from_i8(rdr.read_i8()?)?; // I1
+        rdr.set_position(SEGMENT_HEADER_SIZE as u64);
+
+        let mut parts = Vec::<(PartKind, Vec<u8>)>::with_capacity(no_of_parts as usize);
+        for _ in 0..no_of_parts {
+            // PART HEADER: 16 bytes
+            let kind = PartKind::from_i8(rdr.read_i8()?)?; // I1
+            util::skip_bytes(7, &mut rdr)?; // U1 attributes, I2 and I4 argcount
+            let arg_size = rdr.read_i32::<LittleEndian>()? as usize; // I4
+            rdr.read_i32::<LittleEndian>()?; // I4 remaining_packet_size
+            let data = util::parse_bytes(arg_size, &mut rdr)?;
+            parts.push((kind, data));
+
+            // the padding of the last part may be omitted
+            let pos = rdr.position() as usize;
+            rdr.set_position(std::cmp::min(pos + padsize(pos), varpart.len()) as u64);
+        }
+
+        Ok(Some(ReceivedRequest {
+            seq_number,
+            request_type,
+            parts,
+        }))
+    }
+}
+
+// A reply, with the already encoded data of its parts.
+#[derive(Debug)]
+pub(super) struct OutgoingReply {
+    segment_kind: i8,
+    reply_type: ReplyType,
+    parts: Vec<OutgoingPart>,
+}
+
+#[derive(Debug)]
+struct OutgoingPart {
+    kind: PartKind,
+    attributes: u8,
+    no_of_args: usize,
+    data: Vec<u8>,
+}
+
+impl OutgoingReply {
+    pub fn new(reply_type: ReplyType) -> OutgoingReply {
+        OutgoingReply {
+            segment_kind: REPLY,
+            reply_type,
+            parts: Vec::new(),
+        }
+    }
+
+    pub fn error(code: i32, text: &str) -> OutgoingReply {
+        const BASE_SIZE: usize = 4 + 4 + 4 + 1 + 5;
+        let text = cesu8::to_cesu8(text);
+        let mut data = Vec::<u8>::with_capacity(BASE_SIZE + text.len() + 8);
+        data.write_i32::<LittleEndian>(code).unwrap(); // I4
+        data.write_i32::<LittleEndian>(0).unwrap(); // I4 position
+        data.write_i32::<LittleEndian>(text.len() as i32).unwrap(); // I4
+        data.write_i8(1).unwrap(); // I1 severity: error
+        data.extend_from_slice(b"HY000"); // B5 sqlstate
+        data.extend_from_slice(&text);
+        // each error is padded individually
+        data.resize(data.len() + 8 - (BASE_SIZE + text.len()) % 8, 0);
+
+        let mut reply = OutgoingReply::new(ReplyType::Nil);
+        reply.segment_kind = ERROR;
+        reply.push(PartKind::Error, 0, 1, data);
+        reply
+    }
+
+    fn push(&mut self, kind: PartKind, attributes: u8, no_of_args: usize, data: Vec<u8>) {
+        self.parts.push(OutgoingPart {
+            kind,
+            attributes,
+            no_of_args,
+            data,
+        });
+    }
+
+    pub fn push_auth_fields(&mut self, fields: Vec<Vec<u8>>) -> HdbResult<()> {
+        let mut auth_fields = AuthFields::with_capacity(fields.len());
+        for field in fields {
+            auth_fields.push(field);
+        }
+        let mut data = Vec::<u8>::with_capacity(auth_fields.size());
+        auth_fields.emit(&mut data)?;
+        self.push(PartKind::Authentication, 0, 1, data);
+        Ok(())
+    }
+
+    pub fn push_connect_options(&mut self, connect_options: &ConnectOptions) -> HdbResult<()> {
+        let mut data = Vec::<u8>::with_capacity(connect_options.size());
+        connect_options.emit(&mut data)?;
+        self.push(PartKind::ConnectOptions, 0, connect_options.count(), data);
+        Ok(())
+    }
+
+    // A single-node system is described with an empty topology
+    pub fn push_empty_topology(&mut self) {
+        self.push(PartKind::TopologyInformation, 0, 0, Vec::new());
+    }
+
+    pub fn push_execution_results(&mut self, rows_affected: &[usize]) {
+        let mut data = Vec::<u8>::with_capacity(4 * rows_affected.len());
+        for count in rows_affected {
+            data.write_i32::<LittleEndian>(*count as i32).unwrap();
+        }
+        self.push(PartKind::ExecutionResult, 0, rows_affected.len(), data);
+    }
+
+    // The resultset is sent completely and is closed immediately,
+    // so the driver neither fetches nor closes it.
+    // The LOBs are registered in `lobs`; their index plus 1 is their locator id.
+    pub fn push_resultset(
+        &mut self,
+        rs_id: u64,
+        columns: &[(String, TypeId)],
+        rows: &[Vec<HdbValue>],
+        lobs: &mut Vec<MockLob>,
+    ) -> HdbResult<()> {
+        // metadata: the fixed part of all columns, then the names
+        let mut data = Vec::<u8>::new();
+        let mut names = Vec::<u8>::new();
+        for (name, type_id) in columns {
+            let name = cesu8::to_cesu8(name);
+            if name.len() > 255 {
+                return Err(HdbError::Usage(format!(
+                    "MockServer: column name too long: {:?}",
+                    name
+                )));
+            }
+            data.write_u8(0b_0000_0010)?; // U1 column_options: nullable
+            data.write_u8(type_id.type_code(false))?; // I1
+            data.write_i16::<LittleEndian>(0)?; // I2 scale
+            data.write_i16::<LittleEndian>(0)?; // I2 precision
+            data.write_i16::<LittleEndian>(0)?; // I2
+            data.write_u32::<LittleEndian>(u32::MAX)?; // I4 no tablename
+            data.write_u32::<LittleEndian>(u32::MAX)?; // I4 no schemaname
+            data.write_u32::<LittleEndian>(names.len() as u32)?; // I4 columnname
+            data.write_u32::<LittleEndian>(names.len() as u32)?; // I4 displayname
+            names.write_u8(name.len() as u8)?;
+            names.extend_from_slice(&name);
+        }
+        data.append(&mut names);
+        self.push(PartKind::ResultSetMetadata, 0, columns.len(), data);
+
+        let mut data = Vec::<u8>::with_capacity(8);
+        data.write_u64::<LittleEndian>(rs_id)?;
+        self.push(PartKind::ResultSetId, 0, 1, data);
+
+        let mut data = Vec::<u8>::new();
+        for row in rows {
+            if row.len() != columns.len() {
+                return Err(HdbError::Usage(format!(
+                    "MockServer: row with {} values for {} columns",
+                    row.len(),
+                    columns.len()
+                )));
+            }
+            for ((_, type_id), value) in columns.iter().zip(row.iter()) {
+                emit_value(*type_id, value, lobs, &mut data)?;
+            }
+        }
+        // IS_LAST_PACKET & RESULTSET_CLOSED
+        self.push(PartKind::ResultSet, 0b_0001_0001, rows.len(), data);
+        Ok(())
+    }
+
+    pub fn push_read_lob_reply(&mut self, locator_id: u64, chunk: &[u8], is_last_data: bool) {
+        let mut data = Vec::<u8>::with_capacity(16 + chunk.len());
+        data.write_u64::<LittleEndian>(locator_id).unwrap(); // I8
+        data.write_u8(if is_last_data { 0b_100 } else { 0 })
+            .unwrap(); // I1 options
+        data.write_i32::<LittleEndian>(chunk.len() as i32).unwrap(); // I4
+        data.extend_from_slice(&[0_u8; 3]); // B3 filler
+        data.extend_from_slice(chunk);
+        self.push(PartKind::ReadLobReply, 0, 1, data);
+    }
+
+    pub fn emit<T: io::Write>(&self, session_id: i64, seq_number: i32, w: &mut T) -> HdbResult<()> {
+        let no_of_parts = self.parts.len();
+        let mut varpart_length = SEGMENT_HEADER_SIZE;
+        for (i, part) in self.parts.iter().enumerate() {
+            varpart_length += PART_HEADER_SIZE + part.data.len();
+            if part.is_padded(i + 1 == no_of_parts) {
+                varpart_length += padsize(part.data.len());
+            }
+        }
+
+        // MESSAGE HEADER
+        w.write_i64::<LittleEndian>(session_id)?; // I8
+        w.write_i32::<LittleEndian>(seq_number)?; // I4
+        w.write_u32::<LittleEndian>(varpart_length as u32)?; // UI4
+        w.write_u32::<LittleEndian>(varpart_length as u32)?; // UI4 remaining_bufsize
+        w.write_i16::<LittleEndian>(1)?; // I2 no_of_segs
+        w.write_u8(0)?; // I1 packet_options
+        w.write_u8(0)?; // B1 reserved
+        w.write_u32::<LittleEndian>(0)?; // UI4 uncompressed_varpart_length
+        w.write_u32::<LittleEndian>(0)?; // B4 reserved
+
+        // SEGMENT HEADER
+        w.write_i32::<LittleEndian>(varpart_length as i32)?; // I4 seg_size
+        w.write_i32::<LittleEndian>(0)?; // I4 seg_offset
+        w.write_i16::<LittleEndian>(no_of_parts as i16)?; // I2
+        w.write_i16::<LittleEndian>(1)?; // I2 seg_number
+        w.write_i8(self.segment_kind)?; // I1
+        w.write_u8(0)?; // I1 reserved
+        w.write_i16::<LittleEndian>(self.reply_type.to_i16())?; // I2
+        w.write_all(&[0_u8; 8])?; // B8 reserved
+
+        for (i, part) in self.parts.iter().enumerate() {
+            // PART HEADER
+            w.write_i8(part.kind.to_i8())?; // I1
+            w.write_u8(part.attributes)?; // U1
+            if part.no_of_args < i16::MAX as usize {
+                w.write_i16::<LittleEndian>(part.no_of_args as i16)?; // I2
+                w.write_i32::<LittleEndian>(0)?; // I4
+            } else {
+                w.write_i16::<LittleEndian>(-1)?; // I2
+                w.write_i32::<LittleEndian>(part.no_of_args as i32)?; // I4
+            }
+            w.write_i32::<LittleEndian>(part.data.len() as i32)?; // I4 arg_size
+            w.write_i32::<LittleEndian>(0)?; // I4 remaining_packet_size
+
+            w.write_all(&part.data)?;
+            if part.is_padded(i + 1 == no_of_parts) {
+                w.write_all(&vec![0_u8; padsize(part.data.len())])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OutgoingPart {
+    // mirrors what Part::parse() expects
+    fn is_padded(&self, last: bool) -> bool {
+        match (self.kind, last) {
+            (PartKind::ResultSet, true)
+            | (PartKind::ResultSetId, true)
+            | (PartKind::ReadLobReply, true)
+            | (PartKind::Error, _) => false,
+            (_, _) => true,
+        }
+    }
+}
+
+// The content of a LOB that was sent in a resultset.
+//
+// With NCLOBs, offsets and lengths are counted in characters, where the
+// two halves of a surrogate pair count individually; otherwise in bytes.
+#[derive(Debug)]
+pub(super) struct MockLob {
+    data: Vec<u8>,
+    starts: Option<Vec<usize>>,
+}
+impl MockLob {
+    fn new(type_id: TypeId, data: Vec<u8>) -> MockLob {
+        let starts = match type_id {
+            TypeId::NCLOB => Some(
+                data.iter()
+                    .enumerate()
+                    .filter(|(_, b)| util::is_utf8_char_start(**b))
+                    .map(|(i, _)| i)
+                    .collect(),
+            ),
+            _ => None,
+        };
+        MockLob { data, starts }
+    }
+
+    fn length_c(&self) -> usize {
+        match self.starts {
+            Some(ref starts) => starts.len(),
+            None => self.data.len(),
+        }
+    }
+
+    // Returns the requested part of the data, and whether it reaches the end.
+    // The offset is zero-based.
+    pub fn chunk(&self, offset: usize, length: usize) -> (&[u8], bool) {
+        let end = offset.saturating_add(length);
+        let is_last_data = end >= self.length_c();
+        let byte_pos = |pos: usize| match self.starts {
+            Some(ref starts) => starts.get(pos).cloned().unwrap_or(self.data.len()),
+            None => std::cmp::min(pos, self.data.len()),
+        };
+        (&self.data[byte_pos(offset)..byte_pos(end)], is_last_data)
+    }
+}
+
+// Writes a value in the format that HdbValue::parse_from_reply() expects.
+fn emit_value(
+    type_id: TypeId,
+    value: &HdbValue,
+    lobs: &mut Vec<MockLob>,
+    w: &mut Vec<u8>,
+) -> HdbResult<()> {
+    match (type_id, value) {
+        (TypeId::TINYINT, HdbValue::NULL(_))
+        | (TypeId::SMALLINT, HdbValue::NULL(_))
+        | (TypeId::INT, HdbValue::NULL(_))
+        | (TypeId::BIGINT, HdbValue::NULL(_)) => w.write_u8(0)?,
+        (TypeId::REAL, HdbValue::NULL(_)) => w.write_u32::<LittleEndian>(u32::MAX)?,
+        (TypeId::DOUBLE, HdbValue::NULL(_)) => w.write_u64::<LittleEndian>(u64::MAX)?,
+        (TypeId::BOOLEAN, HdbValue::NULL(_)) => w.write_u8(1)?,
+        (TypeId::CHAR, HdbValue::NULL(_))
+        | (TypeId::VARCHAR, HdbValue::NULL(_))
+        | (TypeId::NCHAR, HdbValue::NULL(_))
+        | (TypeId::NVARCHAR, HdbValue::NULL(_))
+        | (TypeId::STRING, HdbValue::NULL(_))
+        | (TypeId::NSTRING, HdbValue::NULL(_))
+        | (TypeId::SHORTTEXT, HdbValue::NULL(_))
+        | (TypeId::BINARY, HdbValue::NULL(_))
+        | (TypeId::VARBINARY, HdbValue::NULL(_))
+        | (TypeId::BSTRING, HdbValue::NULL(_))
+        | (TypeId::ALPHANUM, HdbValue::NULL(_))
+        | (TypeId::ARRAY, HdbValue::NULL(_))
+        | (TypeId::GEOMETRY, HdbValue::NULL(_))
+        | (TypeId::POINT, HdbValue::NULL(_)) => w.write_u8(255)?,
+        // the legacy date types have the null indicator in the data
+        (TypeId::DATE, HdbValue::NULL(_)) | (TypeId::TIME, HdbValue::NULL(_)) => {
+            w.write_u32::<LittleEndian>(0)?
+        }
+        (TypeId::TIMESTAMP, HdbValue::NULL(_)) => w.write_u64::<LittleEndian>(0)?,
+        (TypeId::BLOB, HdbValue::NULL(_))
+        | (TypeId::CLOB, HdbValue::NULL(_))
+        | (TypeId::NCLOB, HdbValue::NULL(_)) => {
+            w.write_u8(type_id.type_code(false))?; // I1
+            w.write_u8(0b_1)?; // I1 options: is null
+        }
+
+        (TypeId::TINYINT, HdbValue::TINYINT(u)) => {
+            w.write_u8(1)?;
+            w.write_u8(*u)?;
+        }
+        (TypeId::SMALLINT, HdbValue::SMALLINT(i)) => {
+            w.write_u8(1)?;
+            w.write_i16::<LittleEndian>(*i)?;
+        }
+        (TypeId::INT, HdbValue::INT(i)) => {
+            w.write_u8(1)?;
+            w.write_i32::<LittleEndian>(*i)?;
+        }
+        (TypeId::BIGINT, HdbValue::BIGINT(i)) => {
+            w.write_u8(1)?;
+            w.write_i64::<LittleEndian>(*i)?;
+        }
+        (TypeId::REAL, HdbValue::REAL(f)) => w.write_f32::<LittleEndian>(*f)?,
+        (TypeId::DOUBLE, HdbValue::DOUBLE(f)) => w.write_f64::<LittleEndian>(*f)?,
+        (TypeId::BOOLEAN, HdbValue::BOOLEAN(b)) => w.write_u8(2 * (*b as u8))?,
+        (TypeId::CHAR, HdbValue::STRING(s))
+        | (TypeId::VARCHAR, HdbValue::STRING(s))
+        | (TypeId::NCHAR, HdbValue::STRING(s))
+        | (TypeId::NVARCHAR, HdbValue::STRING(s))
+        | (TypeId::STRING, HdbValue::STRING(s))
+        | (TypeId::NSTRING, HdbValue::STRING(s))
+        | (TypeId::SHORTTEXT, HdbValue::STRING(s)) => emit_length_and_string(s, w)?,
+        (TypeId::BINARY, HdbValue::BINARY(v))
+        | (TypeId::VARBINARY, HdbValue::BINARY(v))
+        | (TypeId::BSTRING, HdbValue::BINARY(v))
+        | (TypeId::GEOMETRY, HdbValue::GEOMETRY(v))
+        | (TypeId::POINT, HdbValue::POINT(v)) => emit_length_and_bytes(v, w)?,
+        (TypeId::ALPHANUM, HdbValue::ALPHANUM(s)) => emit_alphanum(s, w)?,
+        (TypeId::ARRAY, HdbValue::ARRAY(values)) => emit_array(values, 0, w)?,
+        (TypeId::DATE, HdbValue::DAYDATE(dd)) => emit_date(dd, w)?,
+        (TypeId::TIME, HdbValue::SECONDTIME(st)) => emit_time(st, w)?,
+        (TypeId::TIMESTAMP, HdbValue::LONGDATE(ld)) => emit_timestamp(ld, w)?,
+        (TypeId::BLOB, HdbValue::BINARY(v)) => emit_lob(type_id, v.clone(), lobs, w)?,
+        (TypeId::CLOB, HdbValue::STRING(s)) | (TypeId::NCLOB, HdbValue::STRING(s)) => {
+            emit_lob(type_id, cesu8::to_cesu8(s).into_owned(), lobs, w)?
+        }
+
+        (type_id, value) => {
+            return Err(HdbError::Usage(format!(
+                "MockServer: cannot send value {} in a column of type {}",
+                value, type_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+// Numeric values are sent without leading zeros, see HdbValue::parse_from_reply().
+fn emit_alphanum(s: &str, w: &mut Vec<u8>) -> HdbResult<()> {
+    let field_length = std::cmp::min(s.len(), 127) as u8;
+    let is_numeric = !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let mut bytes = Vec::<u8>::with_capacity(1 + s.len());
+    if is_numeric {
+        bytes.push(0b_1000_0000 | field_length);
+        bytes.extend_from_slice(s.trim_start_matches('0').as_bytes());
+    } else {
+        bytes.push(field_length);
+        bytes.extend_from_slice(&cesu8::to_cesu8(s));
+    }
+    emit_length_and_bytes(&bytes, w)
+}
+
+// Writes the LOB header, and the first chunk of the data
+fn emit_lob(
+    type_id: TypeId,
+    data: Vec<u8>,
+    lobs: &mut Vec<MockLob>,
+    w: &mut Vec<u8>,
+) -> HdbResult<()> {
+    let lob = MockLob::new(type_id, data);
+    let locator_id = lobs.len() as u64 + 1;
+    let (chunk, is_last_data) = lob.chunk(0, INLINE_LOB_LENGTH);
+
+    // options: bit 1: data is included; bit 2: no more data remaining
+    let options = if is_last_data { 0b_110 } else { 0b_010 };
+    w.write_u8(type_id.type_code(false))?; // I1
+    w.write_u8(options)?; // I1
+    w.write_u16::<LittleEndian>(0)?; // U2 filler
+    w.write_u64::<LittleEndian>(lob.length_c() as u64)?; // I8 length_c
+    w.write_u64::<LittleEndian>(lob.data.len() as u64)?; // I8 length_b
+    w.write_u64::<LittleEndian>(locator_id)?; // I8
+    w.write_u32::<LittleEndian>(chunk.len() as u32)?; // I4 chunk_length
+    w.extend_from_slice(chunk);
+
+    lobs.push(lob);
+    Ok(())
+}
+
+fn padsize(size: usize) -> usize {
+    7 - (size + 7) % 8
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "alpha_routing")]
+use crate::conn_core::routing;
 use crate::conn_core::AmConnCore;
 use crate::protocol::argument::Argument;
 use crate::protocol::part::Part;
@@ -6,8 +8,9 @@ use crate::protocol::parts::hdb_value::HdbValue;
 use crate::protocol::parts::parameter_descriptor::{ParameterDescriptor, ParameterDirection};
 use crate::protocol::parts::parameters::{ParameterRow, Parameters};
 use crate::protocol::parts::resultset_metadata::ResultSetMetadata;
-use crate::protocol::request::{Request, HOLD_CURSORS_OVER_COMMIT};
+use crate::protocol::request::{Request, HOLD_CURSORS_OVER_COMMIT, SCROLLABLE_CURSOR_ON};
 use crate::protocol::request_type::RequestType;
+use crate::types_impl::lob::{write_lob_chunks, LobWriter};
 use crate::{HdbError, HdbResponse, HdbResult};
 
 use serde;
@@ -15,6 +18,7 @@ use serde_db::ser::to_params;
 use serde_db::ser::SerializationError;
 
 use std::mem;
+use std::time::Duration;
 
 /// Allows injection-safe SQL execution and repeated calls of the same statement
 /// with different parameters with as few roundtrips as possible.
@@ -22,11 +26,15 @@ use std::mem;
 pub struct PreparedStatement {
     am_conn_core: AmConnCore,
     statement_id: u64,
-    _o_table_location: Option<Vec<i32>>,
+    o_table_location: Option<Vec<i32>>,
+    #[cfg(feature = "alpha_routing")]
+    o_routed: Option<(AmConnCore, u64)>, // session to the index server that owns the data
     o_par_md: Option<Vec<ParameterDescriptor>>,
     o_input_md: Option<Vec<ParameterDescriptor>>,
     o_rs_md: Option<ResultSetMetadata>,
     o_batch: Option<Vec<ParameterRow>>,
+    command_options: u8,
+    o_query_timeout: Option<Duration>,
 }
 
 impl PreparedStatement {
@@ -45,19 +53,62 @@ impl PreparedStatement {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// LOB parameters cannot be streamed with this method: the input is converted by
+    /// `serde_db`, and serde's data model has no representation for an `io::Read`.
+    /// Use [`execute_row()`](struct.PreparedStatement.html#method.execute_row) with
+    /// [`HdbValue::LOBSTREAM`](enum.HdbValue.html#variant.LOBSTREAM) instead.
     pub fn execute<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<HdbResponse> {
         trace!("PreparedStatement::execute()");
+        let o_rows = self.input_rows(input)?;
+        self.execute_parameter_rows(o_rows)
+    }
+
+    // Converts the input into a single row of parameters, if the statement has parameters.
+    pub(crate) fn input_rows<T: serde::ser::Serialize>(
+        &self,
+        input: &T,
+    ) -> HdbResult<Option<Vec<ParameterRow>>> {
         match self.o_input_md {
             Some(ref metadata) => {
                 let par_row = ParameterRow::new(to_params(input, metadata)?, metadata)?;
+                Ok(Some(vec![par_row]))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Consumes the given HdbValues as a row of parameters for immediate execution.
+    ///
+    /// In most cases [`execute()`](struct.PreparedStatement.html#method.execute)
+    /// is more convenient. This method is needed e.g. for streaming the data of
+    /// LOB parameters to the database with
+    /// [`HdbValue::LOBSTREAM`](enum.HdbValue.html#variant.LOBSTREAM).
+    pub fn execute_row(&mut self, hdb_values: Vec<HdbValue>) -> HdbResult<HdbResponse> {
+        trace!("PreparedStatement::execute_row()");
+        match self.o_input_md {
+            Some(ref metadata) => {
+                let par_row = ParameterRow::new(hdb_values, metadata)?;
                 self.execute_parameter_rows(Some(vec![par_row]))
             }
-            None => self.execute_parameter_rows(None),
+            None => {
+                if hdb_values.is_empty() {
+                    self.execute_parameter_rows(None)
+                } else {
+                    Err(HdbError::Usage(
+                        "The statement has no parameters, but values were given".to_string(),
+                    ))
+                }
+            }
         }
     }
 
     /// Converts the input into a row of parameters and adds it to the batch,
     /// if it is consistent with the metadata.
+    ///
+    /// As with [`execute()`](struct.PreparedStatement.html#method.execute), LOB parameters
+    /// cannot be streamed; use
+    /// [`add_row_to_batch()`](struct.PreparedStatement.html#method.add_row_to_batch) for that.
     pub fn add_batch<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<()> {
         trace!("PreparedStatement::add_batch()");
         match (&(self.o_input_md), &mut (self.o_batch)) {
@@ -77,7 +128,8 @@ impl PreparedStatement {
 
     /// Consumes the input as a row of parameters for the batch.
     ///
-    /// Useful mainly for generic code.
+    /// Useful mainly for generic code, and for streaming the data of LOB parameters with
+    /// [`HdbValue::LOBSTREAM`](enum.HdbValue.html#variant.LOBSTREAM).
     /// In most cases [`add_batch()`](struct.PreparedStatement.html#method.add_batch)
     /// is more convenient.
     pub fn add_row_to_batch(&mut self, row: Vec<HdbValue>) -> HdbResult<()> {
@@ -118,6 +170,25 @@ impl PreparedStatement {
         }
     }
 
+    /// Defines if the resultsets that are produced by this statement have a scrollable cursor.
+    ///
+    /// See [`ResultSet::fetch_absolute()`](struct.ResultSet.html#method.fetch_absolute).
+    pub fn set_scrollable_cursor(&mut self, scrollable: bool) {
+        if scrollable {
+            self.command_options |= SCROLLABLE_CURSOR_ON;
+        } else {
+            self.command_options &= !SCROLLABLE_CURSOR_ON;
+        }
+    }
+
+    /// Overrides the query timeout of the connection for the executions of this statement.
+    ///
+    /// With `None`, the query timeout of the connection is used;
+    /// `Some(Duration::from_secs(0))` switches the query timeout off for this statement.
+    pub fn set_query_timeout(&mut self, query_timeout: Option<Duration>) {
+        self.o_query_timeout = query_timeout;
+    }
+
     /// Descriptors of all parameters of the prepared statement (in, out, inout), if any.
     pub fn parameter_descriptors(&self) -> Option<&Vec<ParameterDescriptor>> {
         self.o_par_md.as_ref()
@@ -128,30 +199,59 @@ impl PreparedStatement {
         self.o_input_md.as_ref()
     }
 
-    fn execute_parameter_rows(
+    pub(crate) fn execute_parameter_rows(
         &mut self,
         o_rows: Option<Vec<ParameterRow>>,
     ) -> HdbResult<HdbResponse> {
         trace!("PreparedStatement::execute_parameter_rows()");
-        let mut request = Request::new(RequestType::Execute, HOLD_CURSORS_OVER_COMMIT);
+        let (mut am_conn_core, statement_id) = self.execution_target()?;
+        let mut request = Request::new(RequestType::Execute, self.command_options);
+        request.set_query_timeout(self.o_query_timeout);
         request.push(Part::new(
             PartKind::StatementId,
-            Argument::StatementId(self.statement_id),
+            Argument::StatementId(statement_id),
         ));
-        if let Some(rows) = o_rows {
+        let mut lob_writers = Vec::<LobWriter>::new();
+        if let Some(mut rows) = o_rows {
+            if let Some(ref par_md) = self.o_par_md {
+                let chunk_size = am_conn_core.lock()?.get_lob_write_length();
+                for row in &mut rows {
+                    lob_writers.extend(row.read_lob_streams(par_md, chunk_size)?);
+                }
+            }
             request.push(Part::new(
                 PartKind::Parameters,
                 Argument::Parameters(Parameters::new(rows)),
             ));
         }
 
-        let reply = self.am_conn_core.full_send(
+        let mut reply = am_conn_core.full_send(
             request,
             self.o_rs_md.as_ref(),
             self.o_par_md.as_ref().map(|vec| vec.as_slice()),
             &mut None,
         )?;
-        reply.into_hdbresponse(&mut (self.am_conn_core))
+
+        // send the remaining data of streamed LOBs
+        if !lob_writers.is_empty() {
+            let locator_ids = match reply
+                .parts
+                .extract_first_part_of_type(PartKind::WriteLobReply)
+                .map(Part::into_elements)
+            {
+                Some((_, Argument::WriteLobReply(write_lob_reply))) => {
+                    write_lob_reply.into_locator_ids()
+                }
+                _ => return Err(HdbError::impl_("No WriteLobReply part found")),
+            };
+            write_lob_chunks(&mut am_conn_core, locator_ids, lob_writers)?;
+        }
+
+        let mut response = reply.into_hdbresponse(&mut am_conn_core)?;
+        if self.command_options & SCROLLABLE_CURSOR_ON != 0 {
+            response.set_scrollable()?;
+        }
+        Ok(response)
     }
 
     // Prepare a statement.
@@ -159,56 +259,29 @@ impl PreparedStatement {
         mut am_conn_core: AmConnCore,
         stmt: &str,
     ) -> HdbResult<PreparedStatement> {
-        let mut request = Request::new(RequestType::Prepare, HOLD_CURSORS_OVER_COMMIT);
-        request.push(Part::new(PartKind::Command, Argument::Command(stmt)));
-
-        let mut reply = am_conn_core.send(request)?;
-
-        // ParameterMetadata, ResultSetMetadata
-        // StatementContext, StatementId,
-        // TableLocation, TransactionFlags,
-        let mut o_table_location: Option<Vec<i32>> = None;
-        let mut o_stmt_id: Option<u64> = None;
-        let mut o_par_md: Option<Vec<ParameterDescriptor>> = None;
-        let mut o_rs_md: Option<ResultSetMetadata> = None;
-
-        while !reply.parts.is_empty() {
-            match reply.parts.pop_arg() {
-                Some(Argument::ParameterMetadata(par_md)) => {
-                    o_par_md = Some(par_md);
-                }
-                Some(Argument::StatementId(id)) => {
-                    o_stmt_id = Some(id);
-                }
-                Some(Argument::TransactionFlags(ta_flags)) => {
-                    let mut guard = am_conn_core.lock()?;
-                    (*guard).evaluate_ta_flags(ta_flags)?;
-                }
-                Some(Argument::TableLocation(vec_i)) => {
-                    o_table_location = Some(vec_i);
-                }
-                Some(Argument::ResultSetMetadata(rs_md)) => {
-                    o_rs_md = Some(rs_md);
-                }
+        let PrepareResult {
+            statement_id,
+            o_table_location,
+            o_par_md,
+            o_rs_md,
+        } = prepare(&mut am_conn_core, stmt)?;
 
-                Some(Argument::StatementContext(ref stmt_ctx)) => {
-                    let mut guard = am_conn_core.lock()?;
-                    (*guard).evaluate_statement_context(stmt_ctx)?;
+        // if the data are located on another index server, prepare the statement there, too
+        #[cfg(feature = "alpha_routing")]
+        let o_routed = match o_table_location {
+            Some(ref table_location) => {
+                match routing::routed_session(&am_conn_core, table_location)? {
+                    Some(mut routed_conn_core) => {
+                        let routed_id = prepare(&mut routed_conn_core, stmt)?.statement_id;
+                        Some((routed_conn_core, routed_id))
+                    }
+                    None => None,
                 }
-                x => warn!("prepare(): Unexpected reply part found {:?}", x),
-            }
-        }
-
-        let statement_id = match o_stmt_id {
-            Some(id) => id,
-            None => {
-                return Err(HdbError::Impl(
-                    "PreparedStatement needs a StatementId".to_owned(),
-                ));
             }
+            None => None,
         };
 
-        let o_input_md = if let Some(ref mut metadata) = o_par_md {
+        let o_input_md = if let Some(ref metadata) = o_par_md {
             let mut input_metadata = Vec::<ParameterDescriptor>::new();
             for pd in metadata {
                 match pd.direction() {
@@ -242,21 +315,140 @@ impl PreparedStatement {
             o_par_md,
             o_input_md,
             o_rs_md,
-            _o_table_location: o_table_location,
+            o_table_location,
+            #[cfg(feature = "alpha_routing")]
+            o_routed,
+            command_options: HOLD_CURSORS_OVER_COMMIT,
+            o_query_timeout: None,
         })
     }
+
+    /// The volume IDs of the locations of the table data that are accessed by the statement,
+    /// as reported by the server.
+    pub fn table_location(&self) -> Option<&Vec<i32>> {
+        self.o_table_location.as_ref()
+    }
+
+    // The connection and the statement id to be used for the next execution.
+    //
+    // Executions are routed to the index server that owns the data only in auto-commit
+    // mode, because a transaction is bound to the session of the anchor connection.
+    fn execution_target(&self) -> HdbResult<(AmConnCore, u64)> {
+        #[cfg(feature = "alpha_routing")]
+        {
+            if let Some((ref routed_conn_core, routed_id)) = self.o_routed {
+                if self.am_conn_core.lock()?.is_auto_commit()
+                    && routing::sync_session(&self.am_conn_core, routed_conn_core)?
+                {
+                    let current_id = routed_conn_core.lock()?.current_statement_id(routed_id);
+                    return Ok((routed_conn_core.clone(), current_id));
+                }
+            }
+        }
+        let current_id = self
+            .am_conn_core
+            .lock()?
+            .current_statement_id(self.statement_id);
+        Ok((self.am_conn_core.clone(), current_id))
+    }
+}
+
+struct PrepareResult {
+    statement_id: u64,
+    o_table_location: Option<Vec<i32>>,
+    o_par_md: Option<Vec<ParameterDescriptor>>,
+    o_rs_md: Option<ResultSetMetadata>,
+}
+
+fn prepare(am_conn_core: &mut AmConnCore, stmt: &str) -> HdbResult<PrepareResult> {
+    let mut request = Request::new(RequestType::Prepare, HOLD_CURSORS_OVER_COMMIT);
+    request.push(Part::new(PartKind::Command, Argument::Command(stmt)));
+
+    let mut reply = am_conn_core.send(request)?;
+
+    // ParameterMetadata, ResultSetMetadata
+    // StatementContext, StatementId,
+    // TableLocation, TransactionFlags,
+    let mut o_table_location: Option<Vec<i32>> = None;
+    let mut o_stmt_id: Option<u64> = None;
+    let mut o_par_md: Option<Vec<ParameterDescriptor>> = None;
+    let mut o_rs_md: Option<ResultSetMetadata> = None;
+
+    while !reply.parts.is_empty() {
+        match reply.parts.pop_arg() {
+            Some(Argument::ParameterMetadata(par_md)) => {
+                o_par_md = Some(par_md);
+            }
+            Some(Argument::StatementId(id)) => {
+                o_stmt_id = Some(id);
+            }
+            Some(Argument::TransactionFlags(ta_flags)) => {
+                let mut guard = am_conn_core.lock()?;
+                (*guard).evaluate_ta_flags(ta_flags)?;
+            }
+            Some(Argument::TableLocation(vec_i)) => {
+                o_table_location = Some(vec_i);
+            }
+            Some(Argument::ResultSetMetadata(rs_md)) => {
+                o_rs_md = Some(rs_md);
+            }
+
+            Some(Argument::StatementContext(ref stmt_ctx)) => {
+                let mut guard = am_conn_core.lock()?;
+                (*guard).evaluate_statement_context(stmt_ctx)?;
+            }
+            x => warn!("prepare(): Unexpected reply part found {:?}", x),
+        }
+    }
+
+    let statement_id = match o_stmt_id {
+        Some(id) => id,
+        None => {
+            return Err(HdbError::Impl(
+                "PreparedStatement needs a StatementId".to_owned(),
+            ));
+        }
+    };
+
+    am_conn_core.lock()?.register_statement(statement_id, stmt);
+
+    Ok(PrepareResult {
+        statement_id,
+        o_table_location,
+        o_par_md,
+        o_rs_md,
+    })
+}
+
+// Frees all server-side ressources that belong to the statement.
+// The statement id is the initial one, which is translated if the session was reconnected.
+fn drop_statement(am_conn_core: &mut AmConnCore, statement_id: u64) {
+    let current_id = match am_conn_core.lock() {
+        Ok(conn_core) => conn_core.current_statement_id(statement_id),
+        Err(_) => return,
+    };
+    let mut request = Request::new(RequestType::DropStatementId, 0);
+    request.push(Part::new(
+        PartKind::StatementId,
+        Argument::StatementId(current_id),
+    ));
+    if let Ok(mut reply) = am_conn_core.send(request) {
+        reply.parts.pop_arg_if_kind(PartKind::StatementContext);
+    }
+    if let Ok(mut conn_core) = am_conn_core.lock() {
+        conn_core.unregister_statement(statement_id);
+    }
 }
 
 impl Drop for PreparedStatement {
     /// Frees all server-side ressources that belong to this prepared statement.
     fn drop(&mut self) {
-        let mut request = Request::new(RequestType::DropStatementId, 0);
-        request.push(Part::new(
-            PartKind::StatementId,
-            Argument::StatementId(self.statement_id),
-        ));
-        if let Ok(mut reply) = self.am_conn_core.send(request) {
-            reply.parts.pop_arg_if_kind(PartKind::StatementContext);
+        #[cfg(feature = "alpha_routing")]
+        {
+            if let Some((ref mut routed_conn_core, routed_id)) = self.o_routed {
+                drop_statement(routed_conn_core, routed_id);
+            }
         }
+        drop_statement(&mut self.am_conn_core, self.statement_id);
     }
 }
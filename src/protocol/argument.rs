@@ -16,6 +16,8 @@ use super::parts::server_error::ServerError;
 use super::parts::statement_context::StatementContext;
 use super::parts::topology::Topology;
 use super::parts::transactionflags::TransactionFlags;
+use super::parts::write_lob_reply::WriteLobReply;
+use super::parts::write_lob_request::WriteLobRequest;
 use super::parts::xat_options::XatOptions;
 use crate::conn_core::AmConnCore;
 use crate::protocol::parts::client_context::ClientContext;
@@ -61,6 +63,8 @@ pub(crate) enum Argument<'a> {
     TableLocation(Vec<i32>),
     TopologyInformation(Topology),
     TransactionFlags(TransactionFlags),
+    WriteLobRequest(WriteLobRequest),
+    WriteLobReply(WriteLobReply),
     XatOptions(XatOptions),
 }
 
@@ -75,7 +79,8 @@ impl<'a> Argument<'a> {
             | Argument::ResultSetId(_)
             | Argument::StatementId(_)
             // | Argument::TopologyInformation(_)
-            | Argument::ReadLobRequest(_) => 1,
+            | Argument::ReadLobRequest(_)
+            | Argument::WriteLobRequest(_) => 1,
             Argument::ClientInfo(ref client_info) => client_info.count(),
             Argument::CommandInfo(ref opts) => opts.count(),
             Argument::CommitOptions(ref opts) => opts.count(),
@@ -128,6 +133,7 @@ impl<'a> Argument<'a> {
             Argument::StatementContext(ref sc) => size += sc.size(),
             // Argument::TopologyInformation(ref topology) => size += topology.size(),
             Argument::TransactionFlags(ref taflags) => size += taflags.size(),
+            Argument::WriteLobRequest(ref r) => size += r.size(),
             Argument::XatOptions(ref xat) => size += xat.size(),
 
             ref arg => {
@@ -179,6 +185,7 @@ impl<'a> Argument<'a> {
             }
             Argument::StatementContext(ref sc) => sc.emit(w)?,
             Argument::TransactionFlags(ref taflags) => taflags.emit(w)?,
+            Argument::WriteLobRequest(ref r) => r.emit(w)?,
             Argument::XatOptions(ref xatid) => xatid.emit(w)?,
             ref a => {
                 return Err(HdbError::Impl(format!("emit() called on {:?}", a)));
@@ -216,6 +223,16 @@ impl<'a> Argument<'a> {
 
         let arg = match kind {
             PartKind::Authentication => Argument::Auth(AuthFields::parse(rdr)?),
+            // request parts, which are parsed only for packet dumps
+            PartKind::ClientContext => {
+                Argument::ClientContext(ClientContext::parse(no_of_args, rdr)?)
+            }
+            PartKind::CommitOptions => {
+                Argument::CommitOptions(CommitOptions::parse(no_of_args, rdr)?)
+            }
+            PartKind::FetchOptions => Argument::FetchOptions(FetchOptions::parse(no_of_args, rdr)?),
+            PartKind::FetchSize => Argument::FetchSize(rdr.read_u32::<LittleEndian>()?),
+            PartKind::LobFlags => Argument::LobFlags(LobFlags::parse(no_of_args, rdr)?),
             PartKind::CommandInfo => Argument::CommandInfo(CommandInfo::parse(no_of_args, rdr)?),
             PartKind::ConnectOptions => {
                 Argument::ConnectOptions(ConnectOptions::parse(no_of_args, rdr)?)
@@ -281,6 +298,9 @@ impl<'a> Argument<'a> {
             PartKind::TransactionFlags => {
                 Argument::TransactionFlags(TransactionFlags::parse(no_of_args, rdr)?)
             }
+            PartKind::WriteLobReply => {
+                Argument::WriteLobReply(WriteLobReply::parse(no_of_args, rdr)?)
+            }
             PartKind::XatOptions => Argument::XatOptions(XatOptions::parse(no_of_args, rdr)?),
             _ => {
                 return Err(HdbError::Impl(format!(
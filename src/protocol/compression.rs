@@ -0,0 +1,40 @@
+//! LZ4 compression of message bodies.
+//!
+//! If compression was negotiated, a message's varpart (segment header and parts)
+//! can be sent LZ4-compressed. The message header then has the packet option
+//! `PACKET_OPTION_COMPRESSED` set, its varpart length is the compressed length,
+//! and the uncompressed length is given in the otherwise unused header bytes.
+use crate::{HdbError, HdbResult};
+
+// Value of the connect option CompressionLevelAndFlags with which the client
+// asks for LZ4 compression; the server answers with 0 if it does not compress.
+pub(crate) const COMPRESSION_LEVEL_AND_FLAGS: i32 = 1;
+
+// Bit of the packet options in the message header that marks a compressed varpart.
+pub(crate) const PACKET_OPTION_COMPRESSED: u8 = 2;
+
+// Smaller message bodies are not worth being compressed.
+pub(crate) const COMPRESSION_THRESHOLD: u32 = 10 * 1024;
+
+pub(crate) fn compress(varpart: &[u8]) -> Vec<u8> {
+    lz4_flex::block::compress(varpart)
+}
+
+pub(crate) fn decompress(compressed: &[u8], uncompressed_size: usize) -> HdbResult<Vec<u8>> {
+    lz4_flex::block::decompress(compressed, uncompressed_size)
+        .map_err(|e| HdbError::Impl(format!("Decompression of reply failed with {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compress, decompress};
+
+    #[test]
+    fn test_roundtrip() {
+        let varpart: Vec<u8> = (0..100_000_u32).map(|i| (i % 251) as u8).collect();
+        let compressed = compress(&varpart);
+        assert!(compressed.len() < varpart.len());
+        assert_eq!(varpart, decompress(&compressed, varpart.len()).unwrap());
+        assert!(decompress(&compressed[..compressed.len() / 2], varpart.len()).is_err());
+    }
+}
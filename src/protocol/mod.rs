@@ -1,6 +1,9 @@
 pub(crate) mod argument;
+#[cfg(feature = "compression")]
+pub(crate) mod compression;
 pub(crate) mod part;
 pub(crate) mod part_attributes;
+pub(crate) mod packet_dump;
 pub(crate) mod partkind;
 pub(crate) mod reply;
 pub(crate) mod reply_type;
@@ -0,0 +1,357 @@
+// Decoding of captured wire protocol data into a readable form.
+use crate::conn_core::trace;
+#[cfg(feature = "compression")]
+use crate::protocol::compression;
+use crate::protocol::part::{Part, Parts};
+use crate::protocol::part_attributes::PartAttributes;
+use crate::protocol::partkind::PartKind;
+use crate::protocol::reply_type::ReplyType;
+use crate::protocol::request_type::RequestType;
+use crate::protocol::util;
+use crate::HdbResult;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+use std::cmp::max;
+use std::io;
+
+const MESSAGE_HEADER_SIZE: usize = 32;
+const PART_HEADER_SIZE: usize = 16;
+const INITIAL_REQUEST_SIZE: usize = 14;
+const INITIAL_REPLY_SIZE: usize = 8;
+
+/// Writes captured HANA wire protocol data in readable form.
+///
+/// `data` can be a trace file that was recorded by `hdbconnect` (see
+/// [`ConnectParams::trace_file()`](struct.ConnectParams.html#method.trace_file)),
+/// or the raw TCP payload of one direction of a connection, e.g. extracted with
+/// Wireshark from a tcpdump capture.
+///
+/// For each message, the message header, the segment header, and all parts with
+/// their kind, attributes, and decoded argument are written.
+/// The parts are decoded with the same code that the driver uses;
+/// parts that cannot be decoded standalone (like resultsets,
+/// which need metadata from earlier messages) are written as hex dump.
+///
+/// The binary `hdbdump` applies this function to a file.
+pub fn dump_packets(data: &[u8], w: &mut dyn io::Write) -> HdbResult<()> {
+    if trace::is_trace(data) {
+        for record in trace::parse_trace(&mut io::Cursor::new(data))? {
+            writeln!(
+                w,
+                "=== connection {}: {} ===",
+                record.connection_no,
+                if record.is_request() {
+                    "request"
+                } else {
+                    "reply"
+                }
+            )?;
+            dump_stream(&record.data, w)?;
+        }
+        Ok(())
+    } else {
+        dump_stream(data, w)
+    }
+}
+
+// Dumps a sequence of messages, which may start with the initial request or its reply.
+fn dump_stream(data: &[u8], w: &mut dyn io::Write) -> HdbResult<()> {
+    let mut pos = 0;
+    while pos < data.len() {
+        let rest = &data[pos..];
+        pos += if rest.len() >= INITIAL_REQUEST_SIZE && rest[..4] == [255, 255, 255, 255] {
+            dump_initial_request(&rest[..INITIAL_REQUEST_SIZE], w)?;
+            INITIAL_REQUEST_SIZE
+        } else if let Some(message_length) = message_length(rest) {
+            dump_message(&rest[..message_length], w)?;
+            message_length
+        } else if pos == 0 && rest.len() >= INITIAL_REPLY_SIZE {
+            writeln!(w, "initial reply:")?;
+            write_hex(&rest[..INITIAL_REPLY_SIZE], w)?;
+            INITIAL_REPLY_SIZE
+        } else {
+            writeln!(w, "unrecognized data:")?;
+            write_hex(rest, w)?;
+            rest.len()
+        };
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+// The total length of the message at the start of data, if data start with a
+// plausible and complete message.
+fn message_length(data: &[u8]) -> Option<usize> {
+    if data.len() < MESSAGE_HEADER_SIZE || LittleEndian::read_i16(&data[20..]) != 1 {
+        return None;
+    }
+    let length = MESSAGE_HEADER_SIZE + LittleEndian::read_u32(&data[12..]) as usize;
+    if length <= data.len() {
+        Some(length)
+    } else {
+        None
+    }
+}
+
+fn dump_initial_request(data: &[u8], w: &mut dyn io::Write) -> HdbResult<()> {
+    writeln!(
+        w,
+        "initial request: product version = {}.{}, protocol version = {}.{}, \
+         option {} = {}",
+        data[4],
+        BigEndian::read_i16(&data[5..]),
+        data[7],
+        BigEndian::read_i16(&data[8..]),
+        data[12],
+        data[13],
+    )?;
+    Ok(())
+}
+
+fn dump_message(data: &[u8], w: &mut dyn io::Write) -> HdbResult<()> {
+    // MESSAGE HEADER: 32 bytes
+    let mut rdr = io::Cursor::new(data);
+    let session_id = rdr.read_i64::<LittleEndian>()?; // I8
+    let packet_seq_number = rdr.read_i32::<LittleEndian>()?; // I4
+    let varpart_length = rdr.read_u32::<LittleEndian>()?; // UI4
+    let remaining_bufsize = rdr.read_u32::<LittleEndian>()?; // UI4
+    let no_of_segs = rdr.read_i16::<LittleEndian>()?; // I2
+    let packet_options = rdr.read_u8()?; // I1
+    util::skip_bytes(1, &mut rdr)?; // B[1]
+    let uncompressed_varpart_length = rdr.read_u32::<LittleEndian>()?; // UI4
+    writeln!(
+        w,
+        "message header: session_id = {}, packet_seq_number = {}, varpart_length = {}, \
+         remaining_bufsize = {}, no_of_segs = {}, packet_options = {:#b}",
+        session_id,
+        packet_seq_number,
+        varpart_length,
+        remaining_bufsize,
+        no_of_segs,
+        packet_options,
+    )?;
+
+    let varpart = &data[MESSAGE_HEADER_SIZE..];
+    if packet_options & 0b10 == 0 {
+        dump_segment(varpart, w)
+    } else {
+        writeln!(
+            w,
+            "varpart is compressed, uncompressed varpart_length = {}",
+            uncompressed_varpart_length
+        )?;
+        #[cfg(feature = "compression")]
+        return dump_segment(
+            &compression::decompress(varpart, uncompressed_varpart_length as usize)?,
+            w,
+        );
+
+        #[cfg(not(feature = "compression"))]
+        {
+            writeln!(w, "(decompression requires the feature 'compression')")?;
+            write_hex(varpart, w)?;
+            Ok(())
+        }
+    }
+}
+
+fn dump_segment(varpart: &[u8], w: &mut dyn io::Write) -> HdbResult<()> {
+    // SEGMENT HEADER: 24 bytes
+    let mut rdr = io::Cursor::new(varpart);
+    let seg_size = rdr.read_i32::<LittleEndian>()?; // I4
+    let seg_offset = rdr.read_i32::<LittleEndian>()?; // I4
+    let no_of_parts = rdr.read_i16::<LittleEndian>()?; // I2
+    let seg_number = rdr.read_i16::<LittleEndian>()?; // I2
+    let seg_kind = rdr.read_i8()?; // I1
+    write!(
+        w,
+        "segment header: seg_size = {}, seg_offset = {}, no_of_parts = {}, seg_number = {}, ",
+        seg_size, seg_offset, no_of_parts, seg_number,
+    )?;
+    match seg_kind {
+        1 => {
+            let message_type = rdr.read_i8()?; // I1
+            let commit = rdr.read_i8()?; // I1
+            let command_options = rdr.read_u8()?; // I1
+            writeln!(
+                w,
+                "seg_kind = request, message_type = {} ({}), commit = {}, \
+                 command_options = {:#b}",
+                message_type,
+                RequestType::from_i8(message_type)
+                    .map(|t| format!("{:?}", t))
+                    .unwrap_or_else(|_| "unknown".to_owned()),
+                commit,
+                command_options,
+            )?;
+        }
+        2 | 5 => {
+            util::skip_bytes(1, &mut rdr)?; // I1 reserved2
+            let function_code = rdr.read_i16::<LittleEndian>()?; // I2
+            writeln!(
+                w,
+                "seg_kind = {}, function_code = {} ({})",
+                if seg_kind == 2 { "reply" } else { "error" },
+                function_code,
+                ReplyType::from_i16(function_code)
+                    .map(|t| format!("{:?}", t))
+                    .unwrap_or_else(|_| "unknown".to_owned()),
+            )?;
+        }
+        _ => {
+            writeln!(w, "seg_kind = {} (unknown)", seg_kind)?;
+            write_hex(varpart, w)?;
+            return Ok(());
+        }
+    }
+
+    let mut pos = 24;
+    for i in 0..no_of_parts {
+        if varpart.len() < pos + PART_HEADER_SIZE {
+            writeln!(w, "segment is truncated")?;
+            break;
+        }
+        pos = dump_part(i, i == no_of_parts - 1, &varpart[pos..], w)? + pos;
+    }
+    if pos < varpart.len() {
+        writeln!(w, "trailing data:")?;
+        write_hex(&varpart[pos..], w)?;
+    }
+    Ok(())
+}
+
+// Returns the number of bytes that the part occupies.
+fn dump_part(i: i16, last: bool, data: &[u8], w: &mut dyn io::Write) -> HdbResult<usize> {
+    // PART HEADER: 16 bytes
+    let kind_code = data[0] as i8;
+    let attributes = PartAttributes::new(data[1]);
+    let no_of_args = max(
+        i32::from(LittleEndian::read_i16(&data[2..])),
+        LittleEndian::read_i32(&data[4..]),
+    );
+    let arg_size = LittleEndian::read_i32(&data[8..]).max(0) as usize;
+    let o_kind = PartKind::from_i8(kind_code).ok();
+    writeln!(
+        w,
+        "part {}: kind = {} ({}), attributes = {:?}, no_of_args = {}, arg_size = {}",
+        i + 1,
+        kind_code,
+        o_kind.map_or_else(|| "unknown".to_owned(), |k| format!("{:?}", k)),
+        attributes,
+        no_of_args,
+        arg_size
+    )?;
+
+    let arg_end = (PART_HEADER_SIZE + arg_size).min(data.len());
+    let arg_data = &data[PART_HEADER_SIZE..arg_end];
+    let padded_end = match (o_kind, last) {
+        (Some(PartKind::ResultSet), true)
+        | (Some(PartKind::ResultSetId), true)
+        | (Some(PartKind::ReadLobReply), true) => arg_end,
+        _ => (arg_end + 7 - (arg_size + 7) % 8).min(data.len()),
+    };
+
+    match o_kind {
+        // is borrowed from the request, and thus not parsed by Argument
+        Some(PartKind::Command) => {
+            let command = util::string_from_cesu8(arg_data.to_vec())?;
+            write_indented(&format!("{:?}", command), w)?;
+            Ok(padded_end)
+        }
+        Some(kind) => {
+            let mut rdr = io::Cursor::new(data);
+            match Part::parse(
+                &mut Parts::default(),
+                None,
+                None,
+                None,
+                &mut None,
+                last,
+                &mut rdr,
+            ) {
+                Ok(part) => {
+                    write_indented(&format!("{:#?}", part.arg()), w)?;
+                    // server errors are padded individually
+                    Ok(match kind {
+                        PartKind::Error => rdr.position() as usize,
+                        _ => padded_end,
+                    })
+                }
+                Err(e) => {
+                    write_indented(&format!("not decoded: {}", e), w)?;
+                    write_hex(arg_data, w)?;
+                    Ok(padded_end)
+                }
+            }
+        }
+        None => {
+            write_hex(arg_data, w)?;
+            Ok(padded_end)
+        }
+    }
+}
+
+fn write_indented(s: &str, w: &mut dyn io::Write) -> io::Result<()> {
+    for line in s.lines() {
+        writeln!(w, "    {}", line)?;
+    }
+    Ok(())
+}
+
+fn write_hex(data: &[u8], w: &mut dyn io::Write) -> io::Result<()> {
+    for (i, chunk) in data.chunks(16).enumerate() {
+        write!(w, "    {:06x}:", i * 16)?;
+        for b in chunk {
+            write!(w, " {:02x}", b)?;
+        }
+        let text: String = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        writeln!(w, "{:width$}  {}", "", text, width = 3 * (16 - chunk.len()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dump_packets;
+    use crate::protocol::argument::Argument;
+    use crate::protocol::part::Part;
+    use crate::protocol::partkind::PartKind;
+    use crate::protocol::request::Request;
+    use crate::protocol::request_type::RequestType;
+
+    #[test]
+    fn test_dump_request() {
+        let mut request = Request::new(RequestType::ExecuteDirect, 0);
+        request.push(Part::new(
+            PartKind::Command,
+            Argument::Command("select * from dummy"),
+        ));
+        request.push(Part::new(PartKind::FetchSize, Argument::FetchSize(32)));
+
+        let mut data = vec![255, 255, 255, 255, 4, 0, 20, 4, 0, 1, 0, 1, 1, 1];
+        request.emit(4711, 2, 0, false, None, &mut data).unwrap();
+        data.extend_from_slice(&[1, 2, 3]);
+
+        let mut dump = Vec::<u8>::new();
+        dump_packets(&data, &mut dump).unwrap();
+        let dump = String::from_utf8(dump).unwrap();
+        assert!(dump.starts_with(
+            "initial request: product version = 4.20, protocol version = 4.1, option 1 = 1\n"
+        ));
+        assert!(dump.contains("session_id = 4711, packet_seq_number = 2,"));
+        assert!(dump.contains("message_type = 2 (ExecuteDirect)"));
+        assert!(dump.contains("part 1: kind = 3 (Command)"));
+        assert!(dump.contains("    \"select * from dummy\"\n"));
+        assert!(dump.contains("part 2: kind = 45 (FetchSize)"));
+        assert!(dump.contains("    FetchSize(\n        32,\n    )\n"));
+        assert!(dump.contains("unrecognized data:\n    000000: 01 02 03"));
+    }
+}
@@ -29,6 +29,9 @@ impl<'a> Part<'a> {
     pub fn arg(&self) -> &Argument {
         &self.arg
     }
+    pub fn arg_mut(&mut self) -> &mut Argument<'a> {
+        &mut self.arg
+    }
     pub fn into_elements(self) -> (PartKind, Argument<'a>) {
         (self.kind, self.arg)
     }
@@ -191,6 +194,10 @@ impl<'a> Parts<'a> {
     pub fn swap_remove(&mut self, index: usize) -> Part {
         self.0.swap_remove(index)
     }
+
+    pub fn iter_mut(&mut self) -> ::std::slice::IterMut<'_, Part<'a>> {
+        self.0.iter_mut()
+    }
 }
 
 impl<'a> IntoIterator for Parts<'a> {
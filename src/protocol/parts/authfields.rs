@@ -81,7 +81,10 @@ impl AuthField {
     }
 
     fn size(&self) -> usize {
-        1 + self.0.len()
+        match self.0.len() {
+            l if l <= 250_usize => 1 + l,
+            l => 3 + l,
+        }
     }
 
     fn parse<T: io::BufRead>(rdr: &mut T) -> HdbResult<AuthField> {
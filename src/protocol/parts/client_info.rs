@@ -7,7 +7,7 @@ use std::env;
 use std::io;
 use std::path::Path;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ClientInfo(HashMap<ClientInfoKey, String>);
 
 impl Default for ClientInfo {
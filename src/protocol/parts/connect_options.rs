@@ -6,17 +6,36 @@ use crate::protocol::parts::option_value::OptionValue;
 // It is used both in requests and replies.
 pub type ConnectOptions = OptionPart<ConnOptId>;
 
+// The highest data format version the driver supports; it is proposed to the server,
+// which answers with the version that it is going to use.
+const MAX_DATAFORMAT_VERSION2: i32 = 8;
+
 // Methods to send information to the server.
 impl ConnectOptions {
-    pub fn for_server(locale: &Option<String>, os_user: String) -> ConnectOptions {
+    pub fn for_server(
+        locale: &Option<String>,
+        os_user: String,
+        compression: bool,
+    ) -> ConnectOptions {
         let connopts = ConnectOptions::default()
             .set_complete_array_execution(true)
-            .set_dataformat_version2(8)
+            .set_dataformat_version2(MAX_DATAFORMAT_VERSION2)
             .set_client_locale(locale)
             .set_enable_array_type(true)
             .set_select_for_update_ok(true)
             .set_row_slot_image_parameter(true)
+            .set_query_timeout_ok(true)
             .set_os_user(os_user);
+        #[cfg(feature = "compression")]
+        let connopts = if compression {
+            connopts.set_compression_level_and_flags(
+                crate::protocol::compression::COMPRESSION_LEVEL_AND_FLAGS,
+            )
+        } else {
+            connopts
+        };
+        #[cfg(not(feature = "compression"))]
+        debug_assert!(!compression);
         if cfg!(feature = "alpha_routing") {
             warn!("Feature alpha_routing is active!");
             connopts
@@ -80,10 +99,21 @@ impl ConnectOptions {
         self
     }
 
+    fn set_query_timeout_ok(mut self, b: bool) -> ConnectOptions {
+        self.set_to_server(ConnOptId::QueryTimeoutOK, OptionValue::BOOLEAN(b));
+        self
+    }
+
     fn set_os_user(mut self, s: String) -> ConnectOptions {
         self.set_to_server(ConnOptId::OSUser, OptionValue::STRING(s));
         self
     }
+
+    #[cfg(feature = "compression")]
+    fn set_compression_level_and_flags(mut self, v: i32) -> ConnectOptions {
+        self.set_to_server(ConnOptId::CompressionLevelAndFlags, OptionValue::INT(v));
+        self
+    }
     fn set_to_server(&mut self, id: ConnOptId, value: OptionValue) -> Option<OptionValue> {
         debug!("Sending ConnectionOption to server: {:?} = {:?}", id, value);
         self.set_value(id, value)
@@ -121,6 +151,7 @@ impl ConnectOptions {
                 | ConnOptId::ItabParameter
                 | ConnOptId::ClientDistributionMode
                 | ConnOptId::ClientInfoNullValueOK
+                | ConnOptId::CompressionLevelAndFlags
                 | ConnOptId::FlagSet1 => {
                     let old_value = old_co.get_value(&k);
                     match old_value {
@@ -243,6 +274,15 @@ impl ConnectOptions {
         self.get_integer(&ConnOptId::DataFormatVersion2, "DataFormatVersion2")
     }
 
+    // The data format version that the server agreed to.
+    // Old servers might only answer with DataFormatVersion; servers that answer
+    // with neither use the baseline format.
+    pub fn get_effective_dataformat_version(&self) -> i32 {
+        self.get_dataformat_version2()
+            .or_else(|| self.get_dataformat_version())
+            .unwrap_or(1)
+    }
+
     // NonTransactionalPrepare
     pub fn get_nontransactional_prepare(&self) -> Option<&bool> {
         self.get_bool(
@@ -340,6 +380,15 @@ impl ConnectOptions {
         self.get_bool(&ConnOptId::ClientInfoNullValueOK, "ClientInfoNullValueOK")
     }
 
+    // Is returned by the server with the value sent by the client if it compresses
+    // message bodies, and with 0 otherwise.
+    pub fn get_compression_level_and_flags(&self) -> Option<i32> {
+        self.get_integer(
+            &ConnOptId::CompressionLevelAndFlags,
+            "CompressionLevelAndFlags",
+        )
+    }
+
     pub fn get_hold_cursor_over_rollback_supported(&self) -> Option<bool> {
         self.get_integer(&ConnOptId::FlagSet1, "FlagSet1")
             .map(|i| (i & 0b1) == 0b1)
@@ -1,10 +1,17 @@
 use crate::conn_core::AmConnCore;
+use crate::impl_serde_db::de::ValueDeserializer;
 use crate::protocol::parts::parameter_descriptor::ParameterDescriptor;
 use crate::protocol::parts::type_id::TypeId;
 use crate::protocol::util;
 use crate::types::{BLob, CLob, DayDate, LongDate, NCLob, SecondDate, SecondTime};
 use crate::types_impl::daydate::parse_daydate;
+#[cfg(feature = "rust_decimal")]
+use crate::types_impl::decimal::{emit_rust_decimal, parse_rust_decimal};
 use crate::types_impl::decimal::{emit_decimal, parse_decimal};
+use crate::types_impl::legacy_dates::{
+    emit_date, emit_seconddate_as_timestamp, emit_time, emit_timestamp, parse_date, parse_time,
+    parse_timestamp,
+};
 use crate::types_impl::lob::{
     emit_blob_header, emit_clob_header, emit_nclob_header, parse_blob, parse_clob, parse_nclob,
 };
@@ -15,9 +22,13 @@ use crate::{HdbError, HdbResult};
 use bigdecimal::BigDecimal;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use cesu8;
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
 use serde;
 use serde_db::de::{ConversionError, DbValue};
 use std::fmt;
+use std::io;
+use std::sync::{Arc, Mutex};
 
 const MAX_1_BYTE_LENGTH: u8 = 245;
 const MAX_2_BYTE_LENGTH: i16 = std::i16::MAX;
@@ -27,7 +38,7 @@ const LENGTH_INDICATOR_NULL: u8 = 255;
 
 /// Enum for all supported database value types.
 #[allow(non_camel_case_types)]
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub enum HdbValue {
     /// Is swapped in where a real value (any of the others) is swapped out.
     NOTHING,
@@ -50,6 +61,18 @@ pub enum HdbValue {
     /// Representation for fixed-point decimal values.
     DECIMAL(BigDecimal),
 
+    /// Representation for fixed-point decimal values, with the feature `rust_decimal`.
+    ///
+    /// Values of the types DECIMAL, FIXED8, FIXED12, and FIXED16 are only parsed
+    /// into this variant if the connection was opened with
+    /// [`ConnectParams::rust_decimal()`](struct.ConnectParams.html#method.rust_decimal),
+    /// and if they fit into a `rust_decimal::Decimal`,
+    /// i.e., if they have at most 28 digits; other values are
+    /// represented as `HdbValue::DECIMAL`.
+    /// Deserialization into `Decimal` works with either variant.
+    #[cfg(feature = "rust_decimal")]
+    RUST_DECIMAL(Decimal),
+
     /// Stores a single-precision 32-bit floating-point number.
     REAL(f32),
     /// Stores a double-precision 64-bit floating-point number.
@@ -66,6 +89,34 @@ pub enum HdbValue {
     NCLOB(NCLob),
     /// Stores a large binary string.
     BLOB(BLob),
+    /// Provides the data of a BLOB, CLOB, or NCLOB parameter from a reader.
+    ///
+    /// Can only be used as input parameter, with
+    /// [`PreparedStatement::execute_row()`](struct.PreparedStatement.html#method.execute_row)
+    /// or [`PreparedStatement::add_row_to_batch()`](struct.PreparedStatement.html#method.add_row_to_batch);
+    /// the serde-based methods like `PreparedStatement::execute()` cannot take a reader.
+    /// The data are not materialized in memory: the first chunk is sent together with
+    /// the other parameters, the rest is streamed to the database in
+    /// subsequent roundtrips; the chunk size is defined by
+    /// [`Connection::set_lob_write_length()`](struct.Connection.html#method.set_lob_write_length).
+    ///
+    /// For NCLOB parameters the reader must provide UTF-8, for CLOB parameters ASCII.
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbResult, HdbValue, IntoConnectParams};
+    /// # use std::sync::{Arc, Mutex};
+    /// # fn foo() -> HdbResult<()> {
+    /// # let mut connection = Connection::new("".into_connect_params()?)?;
+    /// let file = std::fs::File::open("big_document.pdf")?;
+    /// let mut stmt = connection.prepare("insert into DOCUMENTS (id, doc) values(?, ?)")?;
+    /// stmt.execute_row(vec![
+    ///     HdbValue::INT(42),
+    ///     HdbValue::LOBSTREAM(Arc::new(Mutex::new(file))),
+    /// ])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    LOBSTREAM(Arc<Mutex<dyn io::Read + Send>>),
 
     /// BOOLEAN stores boolean values, which are TRUE or FALSE.
     BOOLEAN(bool),
@@ -79,6 +130,14 @@ pub enum HdbValue {
     /// column of type NCLOB.
     TEXT(String),
 
+    /// Stores a variable-length character string with alpha-numeric characters.
+    ///
+    /// Numeric values, i.e. values that consist only of digits, are padded with
+    /// leading zeros to the length of the column, and can be converted into
+    /// numeric types.
+    /// Is sent to the database as STRING.
+    ALPHANUM(String),
+
     /// Timestamp with 10^-7 seconds precision, uses eight bytes.
     LONGDATE(LongDate),
     /// TIMESTAMP with second precision.
@@ -92,10 +151,48 @@ pub enum HdbValue {
     GEOMETRY(Vec<u8>),
     /// Spatial type POINT.
     POINT(Vec<u8>),
+
+    /// Stores an array of values of the same type.
+    ///
+    /// An array can be converted with [`HdbValue::try_into()`](#method.try_into)
+    /// into a `Vec` of a suitable element type, e.g. `Vec<i32>`.
+    /// When a resultset is deserialized into structs, only arrays of TINYINT values can
+    /// be mapped to fields (e.g. of type `serde_bytes::ByteBuf`).
+    /// Array parameters must be provided as `HdbValue::ARRAY`.
+    /// LOBs cannot be array elements.
+    ARRAY(Vec<HdbValue>),
 }
 
 impl HdbValue {
     pub(crate) fn type_id_for_emit(&self, requested_type_id: TypeId) -> HdbResult<TypeId> {
+        Ok(match *self {
+            HdbValue::DECIMAL(_) => decimal_type_id_for_emit(requested_type_id)?,
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(_) => decimal_type_id_for_emit(requested_type_id)?,
+            HdbValue::LOBSTREAM(_) => match requested_type_id {
+                TypeId::BLOB | TypeId::CLOB | TypeId::NCLOB => requested_type_id,
+                _ => {
+                    return Err(HdbError::Impl(format!(
+                        "Can't send {} type for requested {} type",
+                        "LOBSTREAM", requested_type_id
+                    )));
+                }
+            },
+            // old servers use the legacy date types
+            HdbValue::LONGDATE(_) | HdbValue::SECONDDATE(_)
+                if requested_type_id == TypeId::TIMESTAMP =>
+            {
+                TypeId::TIMESTAMP
+            }
+            HdbValue::DAYDATE(_) if requested_type_id == TypeId::DATE => TypeId::DATE,
+            HdbValue::SECONDTIME(_) if requested_type_id == TypeId::TIME => TypeId::TIME,
+            _ => self.own_type_id_for_emit()?,
+        })
+    }
+
+    // The type id with which the value is sent if no parameter type is requested,
+    // as it is the case for array elements.
+    pub(crate) fn own_type_id_for_emit(&self) -> HdbResult<TypeId> {
         Ok(match *self {
             HdbValue::NOTHING => {
                 return Err(HdbError::Impl(
@@ -105,6 +202,7 @@ impl HdbValue {
             HdbValue::NULL(type_id) => match type_id {
                 // work around a bug in HANA: it doesn't accept NULL SECONDTIME values
                 TypeId::SECONDTIME => TypeId::SECONDDATE,
+                TypeId::ALPHANUM => TypeId::STRING,
                 btid => btid,
             },
 
@@ -112,38 +210,41 @@ impl HdbValue {
             HdbValue::SMALLINT(_) => TypeId::SMALLINT,
             HdbValue::INT(_) => TypeId::INT,
             HdbValue::BIGINT(_) => TypeId::BIGINT,
-            HdbValue::DECIMAL(_) => match requested_type_id {
-                TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 | TypeId::DECIMAL => {
-                    requested_type_id
-                }
-                _ => {
-                    return Err(HdbError::Impl(format!(
-                        "Can't send {} type for requested {} type",
-                        "DECIMAL", requested_type_id
-                    )));
-                }
-            },
+            HdbValue::DECIMAL(_) => TypeId::DECIMAL,
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(_) => TypeId::DECIMAL,
             HdbValue::REAL(_) => TypeId::REAL,
             HdbValue::DOUBLE(_) => TypeId::DOUBLE,
             HdbValue::BINARY(_) => TypeId::BINARY,
             HdbValue::CLOB(_) => TypeId::CLOB,
             HdbValue::NCLOB(_) => TypeId::NCLOB,
             HdbValue::BLOB(_) => TypeId::BLOB,
+            HdbValue::LOBSTREAM(_) => {
+                return Err(HdbError::Impl(
+                    "Can't send LOBSTREAM without a requested LOB type".to_string(),
+                ));
+            }
             HdbValue::BOOLEAN(_) => TypeId::BOOLEAN,
             HdbValue::STRING(_) => TypeId::STRING,
             HdbValue::TEXT(_) => TypeId::TEXT,
+            HdbValue::ALPHANUM(_) => TypeId::STRING,
             HdbValue::LONGDATE(_) => TypeId::LONGDATE,
             HdbValue::SECONDDATE(_) => TypeId::SECONDDATE,
             HdbValue::DAYDATE(_) => TypeId::DAYDATE,
             HdbValue::SECONDTIME(_) => TypeId::SECONDTIME,
             HdbValue::GEOMETRY(_) => TypeId::BINARY, // TypeId::GEOMETRY,
             HdbValue::POINT(_) => TypeId::BINARY,    // TypeId::POINT,
+            HdbValue::ARRAY(_) => TypeId::ARRAY,
         })
     }
 
     /// Deserialize into a rust type
     pub fn try_into<'x, T: serde::Deserialize<'x>>(self) -> HdbResult<T> {
-        Ok(DbValue::into_typed(self)?)
+        match self {
+            // serde_db cannot deserialize sequences
+            HdbValue::ARRAY(_) => Ok(T::deserialize(ValueDeserializer::new(self))?),
+            _ => Ok(DbValue::into_typed(self)?),
+        }
     }
 
     /// Convert into hdbconnect::BLob
@@ -194,43 +295,62 @@ impl HdbValue {
         w: &mut T,
     ) -> HdbResult<()> {
         if !self.emit_type_id(descriptor.type_id(), w)? {
-            match *self {
-                HdbValue::NULL(_) => {}
-                HdbValue::TINYINT(u) => w.write_u8(u)?,
-                HdbValue::SMALLINT(i) => w.write_i16::<LittleEndian>(i)?,
-                HdbValue::INT(i) => w.write_i32::<LittleEndian>(i)?,
-                HdbValue::BIGINT(i) => w.write_i64::<LittleEndian>(i)?,
-                HdbValue::DECIMAL(ref bigdec) => {
-                    emit_decimal(bigdec, descriptor.type_id(), descriptor.scale(), w)?
-                }
-                HdbValue::REAL(f) => w.write_f32::<LittleEndian>(f)?,
-                HdbValue::DOUBLE(f) => w.write_f64::<LittleEndian>(f)?,
-                HdbValue::BOOLEAN(b) => emit_bool(b, w)?,
-                HdbValue::LONGDATE(ref ld) => w.write_i64::<LittleEndian>(*ld.ref_raw())?,
-                HdbValue::SECONDDATE(ref sd) => w.write_i64::<LittleEndian>(*sd.ref_raw())?,
-                HdbValue::DAYDATE(ref dd) => w.write_i32::<LittleEndian>(*dd.ref_raw())?,
-                HdbValue::SECONDTIME(ref st) => w.write_u32::<LittleEndian>(*st.ref_raw())?,
-                HdbValue::CLOB(ref clob) => emit_clob_header(clob.len()?, data_pos, w)?,
-                HdbValue::NCLOB(ref nclob) => emit_nclob_header(nclob.len()?, data_pos, w)?,
-                HdbValue::BLOB(ref blob) => emit_blob_header(blob.len_alldata(), data_pos, w)?,
-                HdbValue::STRING(ref s) => emit_length_and_string(s, w)?,
-                HdbValue::BINARY(ref v) | HdbValue::GEOMETRY(ref v) | HdbValue::POINT(ref v) => {
-                    emit_length_and_bytes(v, w)?
-                }
+            self.emit_value(data_pos, descriptor.type_id(), descriptor.scale(), w)?;
+        }
+        Ok(())
+    }
 
-                HdbValue::NOTHING | HdbValue::TEXT(_) => {
-                    return Err(HdbError::Impl(format!(
-                        "HdbValue::emit() not implemented for type {}",
-                        self
-                    )));
-                }
+    fn emit_value(
+        &self,
+        data_pos: &mut i32,
+        type_id: TypeId,
+        scale: i16,
+        w: &mut dyn std::io::Write,
+    ) -> HdbResult<()> {
+        match *self {
+            HdbValue::NULL(_) => {}
+            HdbValue::TINYINT(u) => w.write_u8(u)?,
+            HdbValue::SMALLINT(i) => w.write_i16::<LittleEndian>(i)?,
+            HdbValue::INT(i) => w.write_i32::<LittleEndian>(i)?,
+            HdbValue::BIGINT(i) => w.write_i64::<LittleEndian>(i)?,
+            HdbValue::DECIMAL(ref bigdec) => emit_decimal(bigdec, type_id, scale, w)?,
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(ref dec) => emit_rust_decimal(dec, type_id, scale, w)?,
+            HdbValue::REAL(f) => w.write_f32::<LittleEndian>(f)?,
+            HdbValue::DOUBLE(f) => w.write_f64::<LittleEndian>(f)?,
+            HdbValue::BOOLEAN(b) => emit_bool(b, w)?,
+            HdbValue::LONGDATE(ref ld) if type_id == TypeId::TIMESTAMP => emit_timestamp(ld, w)?,
+            HdbValue::SECONDDATE(ref sd) if type_id == TypeId::TIMESTAMP => {
+                emit_seconddate_as_timestamp(sd, w)?
+            }
+            HdbValue::DAYDATE(ref dd) if type_id == TypeId::DATE => emit_date(dd, w)?,
+            HdbValue::SECONDTIME(ref st) if type_id == TypeId::TIME => emit_time(st, w)?,
+            HdbValue::LONGDATE(ref ld) => w.write_i64::<LittleEndian>(*ld.ref_raw())?,
+            HdbValue::SECONDDATE(ref sd) => w.write_i64::<LittleEndian>(*sd.ref_raw())?,
+            HdbValue::DAYDATE(ref dd) => w.write_i32::<LittleEndian>(*dd.ref_raw())?,
+            HdbValue::SECONDTIME(ref st) => w.write_u32::<LittleEndian>(*st.ref_raw())?,
+            HdbValue::CLOB(ref clob) => emit_clob_header(clob.len()?, data_pos, w)?,
+            HdbValue::NCLOB(ref nclob) => emit_nclob_header(nclob.len()?, data_pos, w)?,
+            HdbValue::BLOB(ref blob) => emit_blob_header(blob.len_alldata(), data_pos, w)?,
+            HdbValue::STRING(ref s) | HdbValue::ALPHANUM(ref s) => emit_length_and_string(s, w)?,
+            HdbValue::BINARY(ref v) | HdbValue::GEOMETRY(ref v) | HdbValue::POINT(ref v) => {
+                emit_length_and_bytes(v, w)?
+            }
+            HdbValue::ARRAY(ref values) => emit_array(values, scale, w)?,
+
+            // LOBSTREAMs are emitted by the ParameterRow
+            HdbValue::NOTHING | HdbValue::TEXT(_) | HdbValue::LOBSTREAM(_) => {
+                return Err(HdbError::Impl(format!(
+                    "HdbValue::emit() not implemented for type {}",
+                    self
+                )));
             }
         }
         Ok(())
     }
 
     // returns true if the value is a null value, false otherwise
-    fn emit_type_id(&self, requested_type_id: TypeId, w: &mut std::io::Write) -> HdbResult<bool> {
+    pub(crate) fn emit_type_id(&self, requested_type_id: TypeId, w: &mut std::io::Write) -> HdbResult<bool> {
         let is_null = self.is_null();
         let type_code = self.type_id_for_emit(requested_type_id)?.type_code(is_null);
         w.write_u8(type_code)?;
@@ -243,18 +363,9 @@ impl HdbValue {
             HdbValue::NOTHING | HdbValue::NULL(_) => 0,
             HdbValue::BOOLEAN(_) | HdbValue::TINYINT(_) => 1,
             HdbValue::SMALLINT(_) => 2,
-            HdbValue::DECIMAL(_) => match type_id {
-                TypeId::DECIMAL => 16,
-                TypeId::FIXED8 => 8,
-                TypeId::FIXED12 => 12,
-                TypeId::FIXED16 => 16,
-                tid => {
-                    return Err(HdbError::Impl(format!(
-                        "invalid TypeId {} for DECIMAL",
-                        tid
-                    )));
-                }
-            },
+            HdbValue::DECIMAL(_) => decimal_size(type_id)?,
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(_) => decimal_size(type_id)?,
 
             HdbValue::INT(_)
             | HdbValue::REAL(_)
@@ -269,12 +380,18 @@ impl HdbValue {
             HdbValue::CLOB(ref clob) => 9 + clob.len()?,
             HdbValue::NCLOB(ref nclob) => 9 + nclob.len()?,
             HdbValue::BLOB(ref blob) => 9 + blob.len_alldata(),
+            // the size of the inline data chunk is added by the ParameterRow
+            HdbValue::LOBSTREAM(_) => 9,
 
-            HdbValue::STRING(ref s) | HdbValue::TEXT(ref s) => binary_length(util::cesu8_length(s)),
+            HdbValue::STRING(ref s) | HdbValue::TEXT(ref s) | HdbValue::ALPHANUM(ref s) => {
+                binary_length(util::cesu8_length(s))
+            }
 
             HdbValue::BINARY(ref v) | HdbValue::GEOMETRY(ref v) | HdbValue::POINT(ref v) => {
                 binary_length(v.len())
             }
+
+            HdbValue::ARRAY(ref values) => binary_length(array_content_size(values)?),
         })
     }
 
@@ -297,6 +414,12 @@ impl HdbValue {
             TypeId::BOOLEAN => Ok(parse_bool(nullable, rdr)?),
 
             TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 => {
+                #[cfg(feature = "rust_decimal")]
+                {
+                    if am_conn_core.is_rust_decimal() {
+                        return parse_rust_decimal(nullable, t, scale, rdr);
+                    }
+                }
                 Ok(parse_decimal(nullable, t, scale, rdr)?)
             }
 
@@ -308,6 +431,8 @@ impl HdbValue {
             | TypeId::NSTRING
             | TypeId::SHORTTEXT => Ok(parse_string(nullable, t, rdr)?),
 
+            TypeId::ALPHANUM => Ok(parse_alphanum(nullable, rdr)?),
+
             TypeId::BINARY
             | TypeId::VARBINARY
             | TypeId::BSTRING
@@ -323,6 +448,12 @@ impl HdbValue {
             TypeId::SECONDDATE => Ok(parse_seconddate(nullable, rdr)?),
             TypeId::DAYDATE => Ok(parse_daydate(nullable, rdr)?),
             TypeId::SECONDTIME => Ok(parse_secondtime(nullable, rdr)?),
+
+            TypeId::DATE => Ok(parse_date(nullable, rdr)?),
+            TypeId::TIME => Ok(parse_time(nullable, rdr)?),
+            TypeId::TIMESTAMP => Ok(parse_timestamp(nullable, rdr)?),
+
+            TypeId::ARRAY => Ok(parse_array(nullable, scale, rdr)?),
         }
     }
 }
@@ -470,6 +601,42 @@ fn parse_string(
     }
 }
 
+// The value bytes start with a byte that contains the length of the column
+// (bits 0 - 6), and the information whether the value is numeric (bit 7).
+// Numeric values are sent without leading zeros.
+fn parse_alphanum(nullable: bool, rdr: &mut dyn std::io::BufRead) -> HdbResult<HdbValue> {
+    let l8 = rdr.read_u8()?; // B1
+    let is_null = l8 == LENGTH_INDICATOR_NULL;
+
+    if is_null {
+        if nullable {
+            Ok(HdbValue::NULL(TypeId::ALPHANUM))
+        } else {
+            Err(HdbError::Impl(
+                "found NULL value for NOT NULL alphanum column".to_owned(),
+            ))
+        }
+    } else {
+        let mut bytes = _read_bytes(l8, rdr)?;
+        if bytes.is_empty() {
+            return Ok(HdbValue::ALPHANUM(String::new()));
+        }
+        let flags = bytes.remove(0);
+        let s = util::string_from_cesu8(bytes)?;
+        let is_numeric = flags & 0b_1000_0000 != 0;
+        let field_length = (flags & 0b_0111_1111) as usize;
+        if is_numeric && s.len() < field_length {
+            Ok(HdbValue::ALPHANUM(format!(
+                "{:0>width$}",
+                s,
+                width = field_length
+            )))
+        } else {
+            Ok(HdbValue::ALPHANUM(s))
+        }
+    }
+}
+
 fn parse_binary(
     nullable: bool,
     type_id: TypeId,
@@ -497,6 +664,121 @@ fn parse_binary(
     }
 }
 
+// An ARRAY value is sent like a binary value, with the length indicator
+// (and LENGTH_INDICATOR_NULL for NULL values in resultsets).
+// The bytes contain the number of elements (I4), followed by the elements, each
+// with its type code and its value in the format that is used for parameters.
+// Decimal elements use the DECIMAL format, with the scale of the column.
+// The array format is not publicly documented: after the length indicator,
+// the number of elements follows as I4, then each element with its type code,
+// which has the highest bit set for NULL, and its value.
+// tests/test_039_arrays.rs checks this against the bytes a server sends.
+fn parse_array(nullable: bool, scale: i16, rdr: &mut dyn std::io::BufRead) -> HdbResult<HdbValue> {
+    let l8 = rdr.read_u8()?; // B1
+    let is_null = l8 == LENGTH_INDICATOR_NULL;
+
+    if is_null {
+        if nullable {
+            Ok(HdbValue::NULL(TypeId::ARRAY))
+        } else {
+            Err(HdbError::Impl(
+                "found NULL value for NOT NULL array column".to_owned(),
+            ))
+        }
+    } else {
+        let mut content = io::Cursor::new(_read_bytes(l8, rdr)?);
+        let count = content.read_i32::<LittleEndian>()?;
+        // each element needs at least one byte for its type code
+        let remaining = content.get_ref().len() as u64 - content.position();
+        if count < 0 || count as u64 > remaining {
+            return Err(HdbError::Impl(format!(
+                "invalid number of array elements: {}",
+                count
+            )));
+        }
+        let mut values = Vec::<HdbValue>::with_capacity(count as usize);
+        for _ in 0..count {
+            values.push(parse_array_element(scale, &mut content)?);
+        }
+        Ok(HdbValue::ARRAY(values))
+    }
+}
+
+fn parse_array_element(scale: i16, rdr: &mut dyn std::io::BufRead) -> HdbResult<HdbValue> {
+    let type_code = rdr.read_u8()?;
+    let type_id = TypeId::try_new(type_code & 0b_0111_1111)?;
+    if type_code & 0b_1000_0000 != 0 {
+        return Ok(HdbValue::NULL(type_id));
+    }
+    match type_id {
+        TypeId::TINYINT => Ok(HdbValue::TINYINT(rdr.read_u8()?)),
+        TypeId::SMALLINT => Ok(HdbValue::SMALLINT(rdr.read_i16::<LittleEndian>()?)),
+        TypeId::INT => Ok(HdbValue::INT(rdr.read_i32::<LittleEndian>()?)),
+        TypeId::BIGINT => Ok(HdbValue::BIGINT(rdr.read_i64::<LittleEndian>()?)),
+        TypeId::REAL => Ok(HdbValue::REAL(rdr.read_f32::<LittleEndian>()?)),
+        TypeId::DOUBLE => Ok(HdbValue::DOUBLE(rdr.read_f64::<LittleEndian>()?)),
+        TypeId::BOOLEAN => Ok(parse_bool(false, rdr)?),
+        TypeId::DECIMAL => Ok(parse_decimal(false, type_id, scale, rdr)?),
+
+        TypeId::CHAR
+        | TypeId::VARCHAR
+        | TypeId::NCHAR
+        | TypeId::NVARCHAR
+        | TypeId::STRING
+        | TypeId::NSTRING
+        | TypeId::SHORTTEXT => Ok(parse_string(false, type_id, rdr)?),
+
+        TypeId::BINARY | TypeId::VARBINARY | TypeId::BSTRING => {
+            Ok(parse_binary(false, type_id, rdr)?)
+        }
+
+        TypeId::LONGDATE => Ok(parse_longdate(false, rdr)?),
+        TypeId::SECONDDATE => Ok(parse_seconddate(false, rdr)?),
+        TypeId::DAYDATE => Ok(parse_daydate(false, rdr)?),
+        TypeId::SECONDTIME => Ok(parse_secondtime(false, rdr)?),
+        TypeId::ARRAY => Ok(parse_array(false, scale, rdr)?),
+        _ => Err(HdbError::Impl(format!(
+            "unexpected type id {} for array element",
+            type_id
+        ))),
+    }
+}
+
+// Writes the content of an ARRAY value, in the format that parse_array() expects.
+pub(crate) fn emit_array(
+    values: &[HdbValue],
+    scale: i16,
+    w: &mut dyn std::io::Write,
+) -> HdbResult<()> {
+    let mut content = Vec::<u8>::with_capacity(array_content_size(values)?);
+    content.write_i32::<LittleEndian>(values.len() as i32)?;
+    for value in values {
+        match *value {
+            HdbValue::CLOB(_) | HdbValue::NCLOB(_) | HdbValue::BLOB(_) | HdbValue::LOBSTREAM(_) => {
+                return Err(HdbError::Usage(format!(
+                    "{} cannot be an array element",
+                    value
+                )));
+            }
+            _ => {
+                let type_id = value.own_type_id_for_emit()?;
+                if !value.emit_type_id(type_id, &mut content)? {
+                    value.emit_value(&mut 0, type_id, scale, &mut content)?;
+                }
+            }
+        }
+    }
+    emit_length_and_bytes(&content, w)
+}
+
+fn array_content_size(values: &[HdbValue]) -> HdbResult<usize> {
+    let mut size = 4;
+    for value in values {
+        size += value.size(value.own_type_id_for_emit()?)?;
+    }
+    Ok(size)
+}
+
 fn _read_bytes(l8: u8, rdr: &mut std::io::BufRead) -> HdbResult<Vec<u8>> {
     let len = match l8 {
         l if l <= MAX_1_BYTE_LENGTH => l8 as usize,
@@ -524,11 +806,36 @@ pub(crate) fn binary_length(l: usize) -> usize {
     }
 }
 
+fn decimal_type_id_for_emit(requested_type_id: TypeId) -> HdbResult<TypeId> {
+    match requested_type_id {
+        TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 | TypeId::DECIMAL => {
+            Ok(requested_type_id)
+        }
+        _ => Err(HdbError::Impl(format!(
+            "Can't send {} type for requested {} type",
+            "DECIMAL", requested_type_id
+        ))),
+    }
+}
+
+fn decimal_size(type_id: TypeId) -> HdbResult<usize> {
+    match type_id {
+        TypeId::DECIMAL => Ok(16),
+        TypeId::FIXED8 => Ok(8),
+        TypeId::FIXED12 => Ok(12),
+        TypeId::FIXED16 => Ok(16),
+        tid => Err(HdbError::Impl(format!(
+            "invalid TypeId {} for DECIMAL",
+            tid
+        ))),
+    }
+}
+
 pub(crate) fn emit_length_and_string(s: &str, w: &mut std::io::Write) -> HdbResult<()> {
     emit_length_and_bytes(&cesu8::to_cesu8(s), w)
 }
 
-fn emit_length_and_bytes(v: &[u8], w: &mut std::io::Write) -> HdbResult<()> {
+pub(crate) fn emit_length_and_bytes(v: &[u8], w: &mut std::io::Write) -> HdbResult<()> {
     match v.len() {
         l if l <= MAX_1_BYTE_LENGTH as usize => {
             w.write_u8(l as u8)?; // B1           LENGTH OF VALUE
@@ -557,6 +864,8 @@ impl fmt::Display for HdbValue {
             HdbValue::BIGINT(value) => write!(fmt, "{}", value),
 
             HdbValue::DECIMAL(ref value) => write!(fmt, "{}", value),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(ref value) => write!(fmt, "{}", value),
 
             HdbValue::REAL(value) => write!(fmt, "{}", value),
             HdbValue::DOUBLE(value) => write!(fmt, "{}", value),
@@ -568,6 +877,7 @@ impl fmt::Display for HdbValue {
                     write!(fmt, "<STRING length = {}>", value.len())
                 }
             }
+            HdbValue::ALPHANUM(ref value) => write!(fmt, "{}", value),
             HdbValue::BINARY(ref vec) => write!(fmt, "<BINARY length = {}>", vec.len()),
 
             HdbValue::CLOB(_) => write!(fmt, "<CLOB>"),
@@ -579,6 +889,8 @@ impl fmt::Display for HdbValue {
                 blob.len_readdata()
             ),
 
+            HdbValue::LOBSTREAM(_) => write!(fmt, "<LOBSTREAM>"),
+
             HdbValue::BOOLEAN(value) => write!(fmt, "{}", value),
             HdbValue::LONGDATE(ref value) => write!(fmt, "{}", value),
             HdbValue::SECONDDATE(ref value) => write!(fmt, "{}", value),
@@ -586,6 +898,41 @@ impl fmt::Display for HdbValue {
             HdbValue::SECONDTIME(ref value) => write!(fmt, "{}", value),
             HdbValue::GEOMETRY(ref vec) => write!(fmt, "<GEOMETRY length = {}>", vec.len()),
             HdbValue::POINT(ref vec) => write!(fmt, "<POINT length = {}>", vec.len()),
+            HdbValue::ARRAY(ref vec) => write!(fmt, "<ARRAY length = {}>", vec.len()),
+        }
+    }
+}
+
+impl fmt::Debug for HdbValue {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HdbValue::NOTHING => write!(fmt, "NOTHING"),
+            HdbValue::NULL(ref type_id) => write!(fmt, "NULL({:?})", type_id),
+            HdbValue::TINYINT(ref v) => write!(fmt, "TINYINT({:?})", v),
+            HdbValue::SMALLINT(ref v) => write!(fmt, "SMALLINT({:?})", v),
+            HdbValue::INT(ref v) => write!(fmt, "INT({:?})", v),
+            HdbValue::BIGINT(ref v) => write!(fmt, "BIGINT({:?})", v),
+            HdbValue::DECIMAL(ref v) => write!(fmt, "DECIMAL({:?})", v),
+            #[cfg(feature = "rust_decimal")]
+            HdbValue::RUST_DECIMAL(ref v) => write!(fmt, "RUST_DECIMAL({:?})", v),
+            HdbValue::REAL(ref v) => write!(fmt, "REAL({:?})", v),
+            HdbValue::DOUBLE(ref v) => write!(fmt, "DOUBLE({:?})", v),
+            HdbValue::BINARY(ref v) => write!(fmt, "BINARY({:?})", v),
+            HdbValue::CLOB(ref v) => write!(fmt, "CLOB({:?})", v),
+            HdbValue::NCLOB(ref v) => write!(fmt, "NCLOB({:?})", v),
+            HdbValue::BLOB(ref v) => write!(fmt, "BLOB({:?})", v),
+            HdbValue::LOBSTREAM(_) => write!(fmt, "LOBSTREAM(..)"),
+            HdbValue::BOOLEAN(ref v) => write!(fmt, "BOOLEAN({:?})", v),
+            HdbValue::STRING(ref v) => write!(fmt, "STRING({:?})", v),
+            HdbValue::TEXT(ref v) => write!(fmt, "TEXT({:?})", v),
+            HdbValue::ALPHANUM(ref v) => write!(fmt, "ALPHANUM({:?})", v),
+            HdbValue::LONGDATE(ref v) => write!(fmt, "LONGDATE({:?})", v),
+            HdbValue::SECONDDATE(ref v) => write!(fmt, "SECONDDATE({:?})", v),
+            HdbValue::DAYDATE(ref v) => write!(fmt, "DAYDATE({:?})", v),
+            HdbValue::SECONDTIME(ref v) => write!(fmt, "SECONDTIME({:?})", v),
+            HdbValue::GEOMETRY(ref v) => write!(fmt, "GEOMETRY({:?})", v),
+            HdbValue::POINT(ref v) => write!(fmt, "POINT({:?})", v),
+            HdbValue::ARRAY(ref v) => write!(fmt, "ARRAY({:?})", v),
         }
     }
 }
@@ -610,3 +957,69 @@ impl std::cmp::PartialEq<&str> for HdbValue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_array, HdbValue};
+    use crate::protocol::parts::type_id::TypeId;
+    use bigdecimal::BigDecimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_array_roundtrip() {
+        let array = HdbValue::ARRAY(vec![
+            HdbValue::DECIMAL(BigDecimal::from_str("12.5").unwrap()),
+            HdbValue::NULL(TypeId::DECIMAL),
+            HdbValue::BOOLEAN(true),
+            HdbValue::STRING("Grüße".to_string()),
+            HdbValue::ARRAY(vec![HdbValue::SMALLINT(7)]),
+        ]);
+
+        let mut buf = Vec::<u8>::new();
+        assert!(!array.emit_type_id(TypeId::ARRAY, &mut buf).unwrap());
+        array
+            .emit_value(&mut 0, TypeId::ARRAY, 1, &mut buf)
+            .unwrap();
+        assert_eq!(buf.len(), array.size(TypeId::ARRAY).unwrap());
+        assert_eq!(buf[0], TypeId::ARRAY.type_code(false));
+
+        let value = parse_array(false, 1, &mut std::io::Cursor::new(&buf[1..])).unwrap();
+        assert_eq!(format!("{:?}", value), format!("{:?}", array));
+
+        let mut values = match value {
+            HdbValue::ARRAY(values) => values,
+            _ => unreachable!(),
+        };
+        let nested: Vec<i32> = values.pop().unwrap().try_into().unwrap();
+        assert_eq!(nested, vec![7]);
+    }
+
+    // the bytes that tests/test_039_arrays.rs expects from the server
+    #[test]
+    fn test_array_bytes() {
+        #[rustfmt::skip]
+        let bytes: &[u8] = &[
+            19,
+            3, 0, 0, 0,
+            3, 1, 0, 0, 0,
+            3, 0xFE, 0xFF, 0xFF, 0xFF,
+            3, 3, 0, 0, 0,
+        ];
+        let array = HdbValue::ARRAY(vec![HdbValue::INT(1), HdbValue::INT(-2), HdbValue::INT(3)]);
+
+        let mut buf = Vec::<u8>::new();
+        array.emit_value(&mut 0, TypeId::ARRAY, 0, &mut buf).unwrap();
+        assert_eq!(buf, bytes);
+        let value = parse_array(false, 0, &mut std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(format!("{:?}", value), format!("{:?}", array));
+    }
+
+    #[test]
+    fn test_array_with_invalid_count() {
+        for count in &[-1_i32, 1000] {
+            let mut buf = vec![4_u8];
+            buf.extend_from_slice(&count.to_le_bytes());
+            assert!(parse_array(false, 0, &mut std::io::Cursor::new(&buf)).is_err());
+        }
+    }
+}
@@ -29,4 +29,8 @@ impl<T: OptionId<T> + Eq + PartialEq + Hash> MultilineOptionPart<T> {
         }
         size
     }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, OptionPart<T>> {
+        self.0.iter()
+    }
 }
@@ -1,12 +1,18 @@
 use super::hdb_value::HdbValue;
 use crate::protocol::parts::parameter_descriptor::{ParameterDescriptor, ParameterDirection};
+use crate::types_impl::lob::{emit_lob_stream_header, LobWriter};
 use crate::{HdbError, HdbResult};
 
 use std::io;
+use std::sync::Arc;
 
 // A single row of parameters.
 #[derive(Default, Debug, Clone)]
-pub(crate) struct ParameterRow(Vec<HdbValue>);
+pub(crate) struct ParameterRow {
+    values: Vec<HdbValue>,
+    // the inline data chunks of the LOBSTREAM values, and if they are complete
+    lob_chunks: Vec<(Vec<u8>, bool)>,
+}
 
 impl ParameterRow {
     /// Constructor.
@@ -16,18 +22,7 @@ impl ParameterRow {
     ) -> HdbResult<ParameterRow> {
         let mut iter = descriptors.iter();
         for value in &(hdb_values) {
-            // find next IN or INOUT descriptor:: FIXME
-            let mut o_descriptor: Option<&ParameterDescriptor> = None;
-            while let Some(descr) = iter.next() {
-                match descr.direction() {
-                    ParameterDirection::OUT => {}
-                    ParameterDirection::IN | ParameterDirection::INOUT => {
-                        o_descriptor = Some(descr);
-                        break;
-                    }
-                }
-            }
-            match o_descriptor {
+            match next_input_descriptor(&mut iter) {
                 Some(descriptor) => {
                     descriptor
                         .type_id()
@@ -41,25 +36,43 @@ impl ParameterRow {
             }
         }
 
-        Ok(ParameterRow(hdb_values))
+        Ok(ParameterRow {
+            values: hdb_values,
+            lob_chunks: Vec::<(Vec<u8>, bool)>::new(),
+        })
     }
 
-    pub(crate) fn size(&self, descriptors: &[ParameterDescriptor]) -> HdbResult<usize> {
-        let mut size = 0;
+    // Reads the first chunk of all LOBSTREAM values, which is sent inline.
+    // Returns the LobWriters of those streams that have more data.
+    pub(crate) fn read_lob_streams(
+        &mut self,
+        descriptors: &[ParameterDescriptor],
+        chunk_size: usize,
+    ) -> HdbResult<Vec<LobWriter>> {
+        let mut lob_writers = Vec::<LobWriter>::new();
+        self.lob_chunks.clear();
         let mut iter = descriptors.iter();
-        for value in &(self.0) {
-            // find next IN or INOUT descriptor
-            let mut o_descriptor: Option<&ParameterDescriptor> = None;
-            while let Some(descr) = iter.next() {
-                match descr.direction() {
-                    ParameterDirection::OUT => {}
-                    ParameterDirection::IN | ParameterDirection::INOUT => {
-                        o_descriptor = Some(descr);
-                        break;
-                    }
+        for value in &(self.values) {
+            let descriptor = next_input_descriptor(&mut iter).ok_or_else(|| {
+                HdbError::impl_("ParameterRow::read_lob_streams(): Not enough metadata")
+            })?;
+            if let HdbValue::LOBSTREAM(ref reader) = *value {
+                let mut lob_writer = LobWriter::new(Arc::clone(reader), descriptor.type_id());
+                let (chunk, is_last_data) = lob_writer.next_chunk(chunk_size)?;
+                self.lob_chunks.push((chunk, is_last_data));
+                if !is_last_data {
+                    lob_writers.push(lob_writer);
                 }
             }
-            match o_descriptor {
+        }
+        Ok(lob_writers)
+    }
+
+    pub(crate) fn size(&self, descriptors: &[ParameterDescriptor]) -> HdbResult<usize> {
+        let mut size = 0;
+        let mut iter = descriptors.iter();
+        for value in &(self.values) {
+            match next_input_descriptor(&mut iter) {
                 Some(descriptor) => {
                     size += value.size(descriptor.type_id())?;
                 }
@@ -70,6 +83,9 @@ impl ParameterRow {
                 }
             }
         }
+        for (chunk, _) in &(self.lob_chunks) {
+            size += chunk.len();
+        }
 
         Ok(size)
     }
@@ -81,22 +97,20 @@ impl ParameterRow {
     ) -> HdbResult<()> {
         let mut data_pos = 0_i32;
         let mut iter = descriptors.iter();
-        for value in &(self.0) {
-            // find next IN or INOUT descriptor
-            let mut o_descriptor: Option<&ParameterDescriptor> = None;
-            while let Some(descr) = iter.next() {
-                match descr.direction() {
-                    ParameterDirection::OUT => {}
-                    ParameterDirection::IN | ParameterDirection::INOUT => {
-                        o_descriptor = Some(descr);
-                        break;
-                    }
-                }
-            }
+        let mut lob_chunks = self.lob_chunks.iter();
+        for value in &(self.values) {
             // emit the value
-            match o_descriptor {
+            match next_input_descriptor(&mut iter) {
                 Some(descriptor) => {
-                    value.emit(&mut data_pos, descriptor, w)?;
+                    if let HdbValue::LOBSTREAM(_) = *value {
+                        let (chunk, is_last_data) = lob_chunks
+                            .next()
+                            .ok_or_else(|| HdbError::impl_("LOBSTREAM was not read"))?;
+                        value.emit_type_id(descriptor.type_id(), w)?;
+                        emit_lob_stream_header(chunk.len(), *is_last_data, &mut data_pos, w)?;
+                    } else {
+                        value.emit(&mut data_pos, descriptor, w)?;
+                    }
                 }
                 None => {
                     return Err(HdbError::Impl(
@@ -106,16 +120,33 @@ impl ParameterRow {
             }
         }
 
-        // BLOBs only emitted their header, the data now
-        for value in &(self.0) {
-            if let HdbValue::BLOB(ref blob) = *value {
-                w.write_all(blob.ref_to_bytes()?)?;
+        // BLOBs and LOBSTREAMs only emitted their header, the data now
+        let mut lob_chunks = self.lob_chunks.iter();
+        for value in &(self.values) {
+            match *value {
+                HdbValue::BLOB(ref blob) => w.write_all(blob.ref_to_bytes()?)?,
+                HdbValue::LOBSTREAM(_) => {
+                    if let Some((chunk, _)) = lob_chunks.next() {
+                        w.write_all(chunk)?;
+                    }
+                }
+                _ => {}
             }
         }
         Ok(())
     }
 }
 
+// Finds the next IN or INOUT descriptor.
+fn next_input_descriptor<'a>(
+    iter: &mut std::slice::Iter<'a, ParameterDescriptor>,
+) -> Option<&'a ParameterDescriptor> {
+    iter.find(|descr| match descr.direction() {
+        ParameterDirection::OUT => false,
+        ParameterDirection::IN | ParameterDirection::INOUT => true,
+    })
+}
+
 // A part that contains input parameters.
 //
 // The argument count of the part defines how many rows of parameters are
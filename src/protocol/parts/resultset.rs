@@ -3,6 +3,8 @@ use crate::protocol::argument::Argument;
 use crate::protocol::part::{Part, Parts};
 use crate::protocol::part_attributes::PartAttributes;
 use crate::protocol::partkind::PartKind;
+use crate::protocol::parts::fetch_options::{FetchOptions, FetchOptionsId};
+use crate::protocol::parts::option_value::OptionValue;
 use crate::protocol::parts::resultset_metadata::ResultSetMetadata;
 use crate::protocol::parts::row::Row;
 use crate::protocol::parts::statement_context::StatementContext;
@@ -38,6 +40,7 @@ pub struct ResultSetCore {
     o_am_conn_core: Option<AmConnCore>,
     attributes: PartAttributes,
     resultset_id: u64,
+    is_scrollable: bool,
 }
 
 impl ResultSetCore {
@@ -50,6 +53,7 @@ impl ResultSetCore {
             o_am_conn_core: Some(am_conn_core.clone()),
             attributes,
             resultset_id,
+            is_scrollable: false,
         }))
     }
 
@@ -66,7 +70,7 @@ impl ResultSetCore {
                     ));
 
                     if let Ok(mut reply) =
-                        conn_guard.roundtrip(request, conn_core, None, None, &mut None)
+                        conn_guard.roundtrip(request, Some(conn_core), None, None, &mut None)
                     {
                         let _ = reply.parts.pop_arg_if_kind(PartKind::StatementContext);
                         for part in &reply.parts {
@@ -199,6 +203,24 @@ impl ResultSet {
         }
     }
 
+    // Removes the next row and returns it, if it is already available on the client side.
+    // Never fetches from the database.
+    #[cfg(feature = "async")]
+    pub(crate) fn next_buffered_row(&mut self) -> Option<Row> {
+        match self.row_iter.next() {
+            Some(r) => Some(r),
+            None => {
+                if self.next_rows.is_empty() {
+                    return None;
+                }
+                let mut tmp_vec = Vec::<Row>::new();
+                std::mem::swap(&mut tmp_vec, &mut self.next_rows);
+                self.row_iter = tmp_vec.into_iter();
+                self.row_iter.next()
+            }
+        }
+    }
+
     // Returns true if the resultset contains more than one row.
     pub(crate) fn has_multiple_rows(&mut self) -> bool {
         let is_complete = match self.is_complete() {
@@ -220,8 +242,71 @@ impl ResultSet {
         Ok(())
     }
 
+    /// Moves the cursor to the given row and fetches the rows from there.
+    ///
+    /// Positive values count from the start of the resultset (1 is the first row),
+    /// negative values count from its end (-1 is the last row).
+    /// Rows that were fetched before and not yet consumed are discarded;
+    /// [`next_row()`](#method.next_row) and the iterator then continue from the new position.
+    ///
+    /// Requires a scrollable cursor, see
+    /// [`Connection::query_scrollable()`](struct.Connection.html#method.query_scrollable)
+    /// and
+    /// [`PreparedStatement::set_scrollable_cursor()`](struct.PreparedStatement.html#method.set_scrollable_cursor).
+    pub fn fetch_absolute(&mut self, position: i32) -> HdbResult<()> {
+        self.fetch_scrolling(RequestType::FetchAbsolute, Some(position))
+    }
+
+    /// Moves the cursor by the given number of rows, relative to its current
+    /// server-side position, and fetches the rows from there.
+    ///
+    /// Note that the server-side position is behind the last row that was fetched,
+    /// which can differ from the position up to which the rows were consumed.
+    /// Requires a scrollable cursor, see [`fetch_absolute()`](#method.fetch_absolute).
+    pub fn fetch_relative(&mut self, delta: i32) -> HdbResult<()> {
+        self.fetch_scrolling(RequestType::FetchRelative, Some(delta))
+    }
+
+    /// Moves the cursor to the first row and fetches the rows from there.
+    ///
+    /// Requires a scrollable cursor, see [`fetch_absolute()`](#method.fetch_absolute).
+    pub fn fetch_first(&mut self) -> HdbResult<()> {
+        self.fetch_scrolling(RequestType::FetchFirst, None)
+    }
+
+    /// Moves the cursor to the last row and fetches it.
+    ///
+    /// Requires a scrollable cursor, see [`fetch_absolute()`](#method.fetch_absolute).
+    pub fn fetch_last(&mut self) -> HdbResult<()> {
+        self.fetch_scrolling(RequestType::FetchLast, None)
+    }
+
+    pub(crate) fn set_scrollable(&self) -> HdbResult<()> {
+        self.core_ref.lock()?.is_scrollable = true;
+        Ok(())
+    }
+
+    fn fetch_scrolling(
+        &mut self,
+        request_type: RequestType,
+        o_position: Option<i32>,
+    ) -> HdbResult<()> {
+        if !self.core_ref.lock()?.is_scrollable {
+            return Err(HdbError::usage_(
+                "Scrolling requires a resultset with a scrollable cursor",
+            ));
+        }
+        self.next_rows.clear();
+        self.row_iter = Vec::<Row>::new().into_iter();
+        self.fetch(request_type, o_position)
+    }
+
     fn fetch_next(&mut self) -> HdbResult<()> {
-        trace!("ResultSet::fetch_next()");
+        self.fetch(RequestType::FetchNext, None)
+    }
+
+    fn fetch(&mut self, request_type: RequestType, o_position: Option<i32>) -> HdbResult<()> {
+        trace!("ResultSet::fetch() with {:?}", request_type);
         let (mut conn_core, resultset_id, fetch_size) = {
             // scope the borrow
             let guard = self.core_ref.lock()?;
@@ -239,13 +324,24 @@ impl ResultSet {
             (conn_core, rs_core.resultset_id, fetch_size)
         };
 
-        // build the request, provide resultset id, define FetchSize
-        debug!("ResultSet::fetch_next() with fetch_size = {}", fetch_size);
-        let mut request = Request::new(RequestType::FetchNext, 0);
+        // build the request, provide resultset id, position, and FetchSize
+        debug!("ResultSet::fetch() with fetch_size = {}", fetch_size);
+        let mut request = Request::new(request_type, 0);
         request.push(Part::new(
             PartKind::ResultSetId,
             Argument::ResultSetId(resultset_id),
         ));
+        if let Some(position) = o_position {
+            let mut fetch_options = FetchOptions::default();
+            fetch_options.set_value(
+                FetchOptionsId::ResultsetPosition,
+                OptionValue::INT(position),
+            );
+            request.push(Part::new(
+                PartKind::FetchOptions,
+                Argument::FetchOptions(fetch_options),
+            ));
+        }
         request.push(Part::new(
             PartKind::FetchSize,
             Argument::FetchSize(fetch_size),
@@ -257,7 +353,8 @@ impl ResultSet {
 
         let mut guard = self.core_ref.lock()?;
         let rs_core = &mut *guard;
-        if rs_core.attributes.is_last_packet() {
+        // a scrollable cursor can be repositioned, so it keeps the connection
+        if rs_core.attributes.is_last_packet() && !rs_core.is_scrollable {
             rs_core.o_am_conn_core = None;
         }
         Ok(())
@@ -266,7 +363,12 @@ impl ResultSet {
     fn is_complete(&self) -> HdbResult<bool> {
         let guard = self.core_ref.lock()?;
         let rs_core = &*guard;
-        if (!rs_core.attributes.is_last_packet())
+        if rs_core.is_scrollable {
+            // scrolling beyond the end is no error
+            Ok(rs_core.attributes.is_last_packet()
+                || rs_core.attributes.row_not_found()
+                || rs_core.attributes.resultset_is_closed())
+        } else if (!rs_core.attributes.is_last_packet())
             && (rs_core.attributes.row_not_found() || rs_core.attributes.resultset_is_closed())
         {
             Err(HdbError::impl_(
@@ -3,7 +3,8 @@ use crate::protocol::parts::option_value::OptionValue;
 
 // An options part that is populated from previously received statement context
 // information. The binary option content is opaque to the client.
-// Assumption: it's used to support automatic reconnects (but we don't make use of that: FIXME)
+// Assumption: it's used to support automatic reconnects; we only evaluate
+// the ClientReconnectionWaitTimeout.
 pub type StatementContext = OptionPart<StatementContextId>;
 
 impl StatementContext {
@@ -21,6 +22,11 @@ impl StatementContext {
         );
     }
 
+    // The query timeout is transmitted in seconds.
+    pub fn set_query_timeout(&mut self, value: i64) {
+        self.set_value(StatementContextId::QueryTimeout, OptionValue::BIGINT(value));
+    }
+
     pub fn get_server_processing_time(&self) -> Option<i32> {
         match self.get_value(&StatementContextId::ServerProcessingTime) {
             Some(&OptionValue::INT(value)) => Some(value),
@@ -28,6 +34,13 @@ impl StatementContext {
         }
     }
 
+    pub fn get_client_reconnection_wait_timeout(&self) -> Option<i32> {
+        match self.get_value(&StatementContextId::ClientReconnectionWaitTimeout) {
+            Some(&OptionValue::INT(value)) => Some(value),
+            _ => None,
+        }
+    }
+
     pub fn get_server_cpu_time(&self) -> Option<i32> {
         match self.get_value(&StatementContextId::ServerCPUTime) {
             Some(&OptionValue::INT(value)) => Some(value),
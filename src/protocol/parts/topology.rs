@@ -1,8 +1,37 @@
 use crate::protocol::parts::multiline_option_part::MultilineOptionPart;
 use crate::protocol::parts::option_part::OptionId;
+#[cfg(feature = "alpha_routing")]
+use crate::protocol::parts::option_value::OptionValue;
 
 pub type Topology = MultilineOptionPart<TopologyAttrId>;
 
+#[cfg(feature = "alpha_routing")]
+impl Topology {
+    // Returns host and port of the index server that owns the given volume,
+    // or None if the volume is unknown or is served by the current session.
+    pub fn location_of_volume(&self, volume_id: i32) -> Option<(String, i32)> {
+        let line = self.iter().find(|line| {
+            line.get_value(&TopologyAttrId::VolumeID) == Some(&OptionValue::INT(volume_id))
+        })?;
+        if let Some(OptionValue::BOOLEAN(true)) = line.get_value(&TopologyAttrId::IsCurrentSession)
+        {
+            return None;
+        }
+        match (
+            line.get_value(&TopologyAttrId::HostName),
+            line.get_value(&TopologyAttrId::HostPortNumber),
+        ) {
+            (Some(OptionValue::STRING(host)), Some(OptionValue::INT(port))) => {
+                Some((host.clone(), *port))
+            }
+            _ => {
+                warn!("Topology: incomplete location for volume {}", volume_id);
+                None
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum TopologyAttrId {
     HostName,         //  1 // host name
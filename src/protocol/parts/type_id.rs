@@ -43,6 +43,15 @@ pub enum TypeId {
     /// For database type VARBINARY;
     /// used with [`HdbValue::BINARY`](enum.HdbValue.html#variant.BINARY).
     VARBINARY,
+    /// Transport format for database type DATE with data format version 1;
+    /// used with [`HdbValue::DAYDATE`](enum.HdbValue.html#variant.DAYDATE).
+    DATE,
+    /// Transport format for database type TIME with data format version 1;
+    /// used with [`HdbValue::SECONDTIME`](enum.HdbValue.html#variant.SECONDTIME).
+    TIME,
+    /// Transport format for database type TIMESTAMP with data format version 1;
+    /// used with [`HdbValue::LONGDATE`](enum.HdbValue.html#variant.LONGDATE).
+    TIMESTAMP,
     /// For database type CLOB;
     /// used with [`HdbValue::CLOB`](enum.HdbValue.html#variant.CLOB).
     CLOB,
@@ -64,12 +73,18 @@ pub enum TypeId {
     /// For database type BSTRING;
     /// used with [`HdbValue::BINARY`](enum.HdbValue.html#variant.BINARY).
     BSTRING,
+    /// For database type ARRAY;
+    /// used with [`HdbValue::ARRAY`](enum.HdbValue.html#variant.ARRAY).
+    ARRAY,
     /// For database type TEXT;
     /// used with [`HdbValue::TEXT`](enum.HdbValue.html#variant.TEXT).
     TEXT,
     /// For database type SHORTTEXT;
     /// used with [`HdbValue::STRING`](enum.HdbValue.html#variant.STRING).
     SHORTTEXT,
+    /// For database type ALPHANUM;
+    /// used with [`HdbValue::ALPHANUM`](enum.HdbValue.html#variant.ALPHANUM).
+    ALPHANUM,
     /// For database type LONGDATE;
     /// used with [`HdbValue::LONGDATE`](enum.HdbValue.html#variant.LONGDATE).
     LONGDATE,
@@ -115,7 +130,11 @@ impl TypeId {
             11 => TypeId::NVARCHAR,
             12 => TypeId::BINARY,
             13 => TypeId::VARBINARY,
-            // DATE: 14, TIME: 15, TIMESTAMP: 16 (all deprecated with protocol version 3)
+            // DATE, TIME, TIMESTAMP are deprecated with data format version 3,
+            // but are still used by servers that answer with data format version 1
+            14 => TypeId::DATE,
+            15 => TypeId::TIME,
+            16 => TypeId::TIMESTAMP,
             // 17 - 24: reserved, do not use
             25 => TypeId::CLOB,
             26 => TypeId::NCLOB,
@@ -129,11 +148,11 @@ impl TypeId {
             // 34 - 46: docu unclear, likely unused
             // 47 => SMALLDECIMAL not needed on client-side
             // 48, 49: ABAP only?
-            // ARRAY: 50  FIXME not yet implemented
+            50 => TypeId::ARRAY,
             51 => TypeId::TEXT,
             52 => TypeId::SHORTTEXT,
             // 53, 54: Reserved, do not use
-            // 55: ALPHANUM  FIXME not yet implemented
+            55 => TypeId::ALPHANUM,
             // 56: Reserved, do not use
             // 57 - 60: not documented
             61 => TypeId::LONGDATE,
@@ -176,6 +195,9 @@ impl TypeId {
                 TypeId::NVARCHAR => 11,
                 TypeId::BINARY => 12,
                 TypeId::VARBINARY => 13,
+                TypeId::DATE => 14,
+                TypeId::TIME => 15,
+                TypeId::TIMESTAMP => 16,
                 TypeId::CLOB => 25,
                 TypeId::NCLOB => 26,
                 TypeId::BLOB => 27,
@@ -183,8 +205,10 @@ impl TypeId {
                 TypeId::STRING => 29,
                 TypeId::NSTRING => 30,
                 TypeId::BSTRING => 33,
+                TypeId::ARRAY => 50,
                 TypeId::TEXT => 51,
                 TypeId::SHORTTEXT => 52,
+                TypeId::ALPHANUM => 55,
                 TypeId::LONGDATE => 61,
                 TypeId::SECONDDATE => 62,
                 TypeId::DAYDATE => 63,
@@ -212,6 +236,7 @@ impl TypeId {
                 | TypeId::NSTRING
                 | TypeId::TEXT
                 | TypeId::SHORTTEXT
+                | TypeId::ALPHANUM
                 | TypeId::CLOB
                 | TypeId::NCLOB => Ok(()),
                 _ => Err(HdbError::Impl(format!(
@@ -272,6 +297,9 @@ impl std::fmt::Display for TypeId {
                 TypeId::NVARCHAR => "NVARCHAR",
                 TypeId::BINARY => "BINARY",
                 TypeId::VARBINARY => "VARBINARY",
+                TypeId::DATE => "DATE",
+                TypeId::TIME => "TIME",
+                TypeId::TIMESTAMP => "TIMESTAMP",
                 TypeId::CLOB => "CLOB",
                 TypeId::NCLOB => "NCLOB",
                 TypeId::BLOB => "BLOB",
@@ -279,8 +307,10 @@ impl std::fmt::Display for TypeId {
                 TypeId::STRING => "STRING",
                 TypeId::NSTRING => "NSTRING",
                 TypeId::BSTRING => "BSTRING",
+                TypeId::ARRAY => "ARRAY",
                 TypeId::TEXT => "TEXT",
                 TypeId::SHORTTEXT => "SHORTTEXT",
+                TypeId::ALPHANUM => "ALPHANUM",
                 TypeId::LONGDATE => "LONGDATE",
                 TypeId::SECONDDATE => "SECONDDATE",
                 TypeId::DAYDATE => "DAYDATE",
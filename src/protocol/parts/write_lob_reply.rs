@@ -0,0 +1,25 @@
+use crate::HdbResult;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::BufRead;
+
+// Contains the locator ids of those lobs that were not completely written yet.
+#[derive(Debug)]
+pub struct WriteLobReply {
+    locator_ids: Vec<u64>,
+}
+impl WriteLobReply {
+    pub fn into_locator_ids(self) -> Vec<u64> {
+        self.locator_ids
+    }
+}
+
+impl WriteLobReply {
+    pub fn parse<T: BufRead>(no_of_args: usize, rdr: &mut T) -> HdbResult<WriteLobReply> {
+        let mut locator_ids = Vec::<u64>::with_capacity(no_of_args);
+        for _ in 0..no_of_args {
+            locator_ids.push(rdr.read_u64::<LittleEndian>()?); // I8
+        }
+        Ok(WriteLobReply { locator_ids })
+    }
+}
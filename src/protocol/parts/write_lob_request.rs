@@ -0,0 +1,45 @@
+use crate::hdb_error::HdbResult;
+use byteorder::{LittleEndian, WriteBytesExt};
+use std::io;
+
+#[derive(Debug)]
+pub struct WriteLobRequest {
+    locator_id: u64,
+    offset: i64,
+    data: Vec<u8>,
+    is_last_data: bool,
+}
+impl WriteLobRequest {
+    // The data are appended to what was already written to the lob (offset -1).
+    pub fn new(locator_id: u64, data: Vec<u8>, is_last_data: bool) -> WriteLobRequest {
+        WriteLobRequest {
+            locator_id,
+            offset: -1,
+            data,
+            is_last_data,
+        }
+    }
+    pub fn emit<T: io::Write>(&self, w: &mut T) -> HdbResult<()> {
+        trace!(
+            "write_lob_request::emit() locator_id = {}, length = {}, is_last_data = {}",
+            self.locator_id,
+            self.data.len(),
+            self.is_last_data
+        );
+        // bit 0: not used; bit 1: data is included; bit 2: no more data remaining
+        let options = if self.is_last_data {
+            0b_110_u8
+        } else {
+            0b_010_u8
+        };
+        w.write_u64::<LittleEndian>(self.locator_id)?; // I8
+        w.write_u8(options)?; // I1
+        w.write_i64::<LittleEndian>(self.offset)?; // I8
+        w.write_i32::<LittleEndian>(self.data.len() as i32)?; // I4
+        w.write_all(&self.data)?; // B[length]
+        Ok(())
+    }
+    pub fn size(&self) -> usize {
+        21 + self.data.len()
+    }
+}
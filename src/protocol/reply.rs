@@ -1,6 +1,8 @@
 use crate::conn_core::AmConnCore;
 use crate::hdb_response::InternalReturnValue;
 use crate::protocol::argument::Argument;
+#[cfg(feature = "compression")]
+use crate::protocol::compression;
 use crate::protocol::part::{Part, Parts};
 use crate::protocol::part_attributes::PartAttributes;
 use crate::protocol::parts::parameter_descriptor::ParameterDescriptor;
@@ -48,21 +50,47 @@ impl Reply {
         rdr: &mut T,
     ) -> HdbResult<Reply> {
         trace!("Reply::parse()");
-        let (no_of_parts, mut reply) = parse_message_and_sequence_header(rdr)?;
-
-        for i in 0..no_of_parts {
-            let part = Part::parse(
-                &mut (reply.parts),
-                o_am_conn_core,
-                o_rs_md,
-                o_par_md,
-                o_rs,
-                i == no_of_parts - 1,
-                rdr,
-            )?;
-            reply.push(part);
+        let message_header = parse_message_header(rdr)?;
+        if message_header.is_compressed() {
+            #[cfg(feature = "compression")]
+            {
+                let mut compressed = vec![0_u8; message_header.varpart_length as usize];
+                rdr.read_exact(&mut compressed)?;
+                let varpart = compression::decompress(
+                    &compressed,
+                    message_header.uncompressed_varpart_length as usize,
+                )?;
+                trace!(
+                    "Decompressed varpart from {} to {} bytes",
+                    compressed.len(),
+                    varpart.len()
+                );
+                return Reply::parse_segment(
+                    message_header.session_id,
+                    o_rs_md,
+                    o_par_md,
+                    o_rs,
+                    o_am_conn_core,
+                    &mut io::Cursor::new(varpart),
+                );
+            }
+            #[cfg(not(feature = "compression"))]
+            return Err(HdbError::Impl(format!(
+                "Got a compressed reply ({} bytes, uncompressed {} bytes), \
+                 but feature 'compression' is not active",
+                message_header.varpart_length, message_header.uncompressed_varpart_length
+            )));
         }
 
+        let reply = Reply::parse_segment(
+            message_header.session_id,
+            o_rs_md,
+            o_par_md,
+            o_rs,
+            o_am_conn_core,
+            rdr,
+        )?;
+
         // Make sure that here (after parsing) the buffer is empty.
         // The following only works with nightly, because `.buffer()`
         // is on its way, but not yet in stable (https://github.com/rust-lang/rust/pull/49139)
@@ -89,6 +117,31 @@ impl Reply {
         Ok(reply)
     }
 
+    fn parse_segment<T: io::BufRead>(
+        session_id: i64,
+        o_rs_md: Option<&ResultSetMetadata>,
+        o_par_md: Option<&[ParameterDescriptor]>,
+        o_rs: &mut Option<&mut ResultSet>,
+        o_am_conn_core: Option<&AmConnCore>,
+        rdr: &mut T,
+    ) -> HdbResult<Reply> {
+        let (no_of_parts, mut reply) = parse_segment_header(session_id, rdr)?;
+
+        for i in 0..no_of_parts {
+            let part = Part::parse(
+                &mut (reply.parts),
+                o_am_conn_core,
+                o_rs_md,
+                o_par_md,
+                o_rs,
+                i == no_of_parts - 1,
+                rdr,
+            )?;
+            reply.push(part);
+        }
+        Ok(reply)
+    }
+
     pub fn assert_expected_reply_type(&self, reply_type: &ReplyType) -> HdbResult<()> {
         if self.replytype == *reply_type {
             Ok(()) // we got what we expected
@@ -219,11 +272,23 @@ impl Drop for Reply {
     }
 }
 
-fn parse_message_and_sequence_header<T: io::BufRead>(rdr: &mut T) -> HdbResult<(i16, Reply)> {
+struct MessageHeader {
+    session_id: i64,
+    varpart_length: u32,
+    packet_options: u8,
+    uncompressed_varpart_length: u32,
+}
+impl MessageHeader {
+    fn is_compressed(&self) -> bool {
+        self.packet_options & 0b10 != 0 // the varpart is compressed
+    }
+}
+
+fn parse_message_header<T: io::BufRead>(rdr: &mut T) -> HdbResult<MessageHeader> {
     // MESSAGE HEADER: 32 bytes
     let session_id: i64 = rdr.read_i64::<LittleEndian>()?; // I8
     let packet_seq_number: i32 = rdr.read_i32::<LittleEndian>()?; // I4
-    let varpart_size: u32 = rdr.read_u32::<LittleEndian>()?; // UI4  not needed?
+    let varpart_length: u32 = rdr.read_u32::<LittleEndian>()?; // UI4
     let remaining_bufsize: u32 = rdr.read_u32::<LittleEndian>()?; // UI4  not needed?
     let no_of_segs = rdr.read_i16::<LittleEndian>()?; // I2
     if no_of_segs == 0 {
@@ -236,23 +301,36 @@ fn parse_message_and_sequence_header<T: io::BufRead>(rdr: &mut T) -> HdbResult<(
         return Err(HdbError::Impl(format!("no_of_segs = {} > 1", no_of_segs)));
     }
 
-    util::skip_bytes(10, rdr)?; // (I1 + B[9])
+    let packet_options = rdr.read_u8()?; // I1
+    util::skip_bytes(1, rdr)?; // B[1]
+    let uncompressed_varpart_length: u32 = rdr.read_u32::<LittleEndian>()?; // UI4
+    util::skip_bytes(4, rdr)?; // B[4]
 
+    trace!(
+        "message header: {{ packet_seq_number = {}, varpart_length = {}, \
+         remaining_bufsize = {}, packet_options = {}, uncompressed_varpart_length = {} }}",
+        packet_seq_number,
+        varpart_length,
+        remaining_bufsize,
+        packet_options,
+        uncompressed_varpart_length,
+    );
+    Ok(MessageHeader {
+        session_id,
+        varpart_length,
+        packet_options,
+        uncompressed_varpart_length,
+    })
+}
+
+fn parse_segment_header<T: io::BufRead>(session_id: i64, rdr: &mut T) -> HdbResult<(i16, Reply)> {
     // SEGMENT HEADER: 24 bytes
     rdr.read_i32::<LittleEndian>()?; // I4 seg_size
     rdr.read_i32::<LittleEndian>()?; // I4 seg_offset
     let no_of_parts: i16 = rdr.read_i16::<LittleEndian>()?; // I2
     rdr.read_i16::<LittleEndian>()?; // I2 seg_number
     let seg_kind = Kind::from_i8(rdr.read_i8()?)?; // I1
-
-    trace!(
-        "message and segment header: {{ packet_seq_number = {}, varpart_size = {}, \
-         remaining_bufsize = {}, no_of_parts = {} }}",
-        packet_seq_number,
-        varpart_size,
-        remaining_bufsize,
-        no_of_parts
-    );
+    trace!("segment header: {{ no_of_parts = {} }}", no_of_parts);
 
     match seg_kind {
         Kind::Request => Err(HdbError::Usage("Cannot _parse_ a request".to_string())),
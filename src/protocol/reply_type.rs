@@ -66,4 +66,35 @@ impl ReplyType {
             ))),
         }
     }
+
+    // Is needed only for the mock server, which must produce replies.
+    #[cfg(feature = "mock_server")]
+    pub fn to_i16(&self) -> i16 {
+        match *self {
+            ReplyType::Nil => 0,
+            ReplyType::Ddl => 1,
+            ReplyType::Insert => 2,
+            ReplyType::Update => 3,
+            ReplyType::Delete => 4,
+            ReplyType::Select => 5,
+            ReplyType::SelectForUpdate => 6,
+            ReplyType::Explain => 7,
+            ReplyType::DbProcedureCall => 8,
+            ReplyType::DbProcedureCallWithResult => 9,
+            ReplyType::Fetch => 10,
+            ReplyType::Commit => 11,
+            ReplyType::Rollback => 12,
+            ReplyType::Connect => 14,
+            ReplyType::WriteLob => 15,
+            ReplyType::ReadLob => 16,
+            ReplyType::Disconnect => 18,
+            ReplyType::CloseCursor => 19,
+            ReplyType::FindLob => 20,
+            ReplyType::XaStart => 22,
+            ReplyType::XaJoin => 23,
+            ReplyType::XAControl => 25,
+            ReplyType::XAPrepare => 26,
+            ReplyType::XARecover => 27,
+        }
+    }
 }
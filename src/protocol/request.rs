@@ -2,6 +2,8 @@
 //! we model message and segment together.
 //! But we differentiate explicitly between request messages and reply messages.
 use super::argument::Argument;
+#[cfg(feature = "compression")]
+use super::compression;
 use super::part::{Part, Parts};
 use super::partkind::PartKind;
 use super::request_type::RequestType;
@@ -10,10 +12,12 @@ use crate::protocol::parts::statement_context::StatementContext;
 use crate::HdbResult;
 use byteorder::{LittleEndian, WriteBytesExt};
 use std::io;
+use std::time::Duration;
 
 const MESSAGE_HEADER_SIZE: u32 = 32;
 const SEGMENT_HEADER_SIZE: usize = 24; // same for in and out
 pub const HOLD_CURSORS_OVER_COMMIT: u8 = 8;
+pub const SCROLLABLE_CURSOR_ON: u8 = 32;
 
 // Packets having the same sequence number belong to one request/response pair.
 #[derive(Debug)]
@@ -21,6 +25,7 @@ pub(crate) struct Request<'a> {
     pub request_type: RequestType,
     command_options: u8,
     parts: Parts<'a>,
+    o_query_timeout: Option<Duration>,
 }
 // Methods for defining a request
 impl<'a> Request<'a> {
@@ -29,6 +34,7 @@ impl<'a> Request<'a> {
             request_type,
             command_options,
             parts: Parts::default(),
+            o_query_timeout: None,
         }
     }
 
@@ -40,12 +46,33 @@ impl<'a> Request<'a> {
         self.parts.push(part);
     }
 
-    pub fn add_statement_context(&mut self, ssi_value: i64) {
+    // Overrides the query timeout of the connection for this request.
+    pub fn set_query_timeout(&mut self, o_query_timeout: Option<Duration>) {
+        self.o_query_timeout = o_query_timeout;
+    }
+
+    pub fn query_timeout(&self) -> Option<Duration> {
+        self.o_query_timeout
+    }
+
+    pub fn add_statement_context(
+        &mut self,
+        o_ssi_value: Option<i64>,
+        o_query_timeout: Option<Duration>,
+    ) {
         let mut stmt_ctx: StatementContext = Default::default();
-        stmt_ctx.set_statement_sequence_info(ssi_value);
+        if let Some(ssi_value) = o_ssi_value {
+            stmt_ctx.set_statement_sequence_info(ssi_value);
+        }
+        if let Some(query_timeout) = o_query_timeout {
+            // round up to full seconds; 0 switches the timeout off
+            let secs = query_timeout.as_secs() + u64::from(query_timeout.subsec_nanos() > 0);
+            stmt_ctx.set_query_timeout(secs as i64);
+        }
         trace!(
-            "Sending StatementContext with sequence_info = {:?}",
-            ssi_value
+            "Sending StatementContext with sequence_info = {:?}, query_timeout = {:?}",
+            o_ssi_value,
+            o_query_timeout
         );
         self.push(Part::new(
             PartKind::StatementContext,
@@ -53,33 +80,82 @@ impl<'a> Request<'a> {
         ));
     }
 
+    // Replaces outdated statement ids, after the statements were prepared anew
+    // on a new session.
+    pub fn replace_statement_ids(&mut self, replacements: &[(u64, u64)]) {
+        for part in self.parts.iter_mut() {
+            if let Argument::StatementId(ref mut id) = part.arg_mut() {
+                if let Some((_, new_id)) = replacements.iter().find(|(old_id, _)| old_id == id) {
+                    *id = *new_id;
+                }
+            }
+        }
+    }
+
     pub fn emit<T: io::Write>(
-        self,
+        &self,
         session_id: i64,
         seq_number: i32,
         auto_commit_flag: i8,
+        compress: bool,
         o_par_md: Option<&[ParameterDescriptor]>,
         w: &mut T,
     ) -> HdbResult<()> {
         let varpart_size = self.varpart_size(o_par_md)?;
         let total_size = MESSAGE_HEADER_SIZE + varpart_size;
         trace!("Writing request with total size {}", total_size);
-        let mut remaining_bufsize = total_size - MESSAGE_HEADER_SIZE;
 
         debug!(
             "Request::emit() of type {:?} for session_id = {}, seq_number = {}",
             self.request_type, session_id, seq_number
         );
-        // MESSAGE HEADER
-        w.write_i64::<LittleEndian>(session_id)?; // I8
-        w.write_i32::<LittleEndian>(seq_number)?; // I4
-        w.write_u32::<LittleEndian>(varpart_size)?; // UI4
-        w.write_u32::<LittleEndian>(remaining_bufsize)?; // UI4
-        w.write_i16::<LittleEndian>(1)?; // I2    Number of segments
-        for _ in 0..10 {
-            w.write_u8(0)?;
-        } // I1+ B[9]  unused
 
+        #[cfg(feature = "compression")]
+        {
+            if compress && varpart_size >= compression::COMPRESSION_THRESHOLD {
+                let mut varpart = Vec::<u8>::with_capacity(varpart_size as usize);
+                self.emit_varpart(varpart_size, auto_commit_flag, o_par_md, &mut varpart)?;
+                let compressed = compression::compress(&varpart);
+                trace!(
+                    "Compressed varpart from {} to {} bytes",
+                    varpart.len(),
+                    compressed.len()
+                );
+                if compressed.len() < varpart.len() {
+                    emit_message_header(
+                        session_id,
+                        seq_number,
+                        compressed.len() as u32,
+                        compression::PACKET_OPTION_COMPRESSED,
+                        varpart_size,
+                        w,
+                    )?;
+                    w.write_all(&compressed)?;
+                } else {
+                    emit_message_header(session_id, seq_number, varpart_size, 0, 0, w)?;
+                    w.write_all(&varpart)?;
+                }
+                w.flush()?;
+                return Ok(());
+            }
+        }
+        #[cfg(not(feature = "compression"))]
+        debug_assert!(!compress);
+
+        emit_message_header(session_id, seq_number, varpart_size, 0, 0, w)?;
+        self.emit_varpart(varpart_size, auto_commit_flag, o_par_md, w)?;
+        w.flush()?;
+        trace!("Parts are written");
+        Ok(())
+    }
+
+    fn emit_varpart<T: io::Write>(
+        &self,
+        varpart_size: u32,
+        auto_commit_flag: i8,
+        o_par_md: Option<&[ParameterDescriptor]>,
+        w: &mut T,
+    ) -> HdbResult<()> {
         // SEGMENT HEADER
         let parts_len = self.parts.len() as i16;
         let size = self.seg_size(o_par_md)? as i32;
@@ -95,14 +171,12 @@ impl<'a> Request<'a> {
             w.write_u8(0)?;
         } // [B;8] Reserved, do not use
 
-        remaining_bufsize -= SEGMENT_HEADER_SIZE as u32;
+        let mut remaining_bufsize = varpart_size - SEGMENT_HEADER_SIZE as u32;
         trace!("Headers are written");
         // PARTS
         for part in &(self.parts) {
             remaining_bufsize = part.emit(remaining_bufsize, o_par_md, w)?;
         }
-        w.flush()?;
-        trace!("Parts are written");
         Ok(())
     }
 
@@ -123,3 +197,24 @@ impl<'a> Request<'a> {
         Ok(len)
     }
 }
+
+fn emit_message_header<T: io::Write>(
+    session_id: i64,
+    seq_number: i32,
+    varpart_length: u32,
+    packet_options: u8,
+    uncompressed_varpart_length: u32,
+    w: &mut T,
+) -> HdbResult<()> {
+    // MESSAGE HEADER
+    w.write_i64::<LittleEndian>(session_id)?; // I8
+    w.write_i32::<LittleEndian>(seq_number)?; // I4
+    w.write_u32::<LittleEndian>(varpart_length)?; // UI4
+    w.write_u32::<LittleEndian>(varpart_length)?; // UI4 remaining buffer size
+    w.write_i16::<LittleEndian>(1)?; // I2    Number of segments
+    w.write_u8(packet_options)?; // I1 Packet options
+    w.write_u8(0)?; // B[1] reserved
+    w.write_u32::<LittleEndian>(uncompressed_varpart_length)?; // UI4 Length if compressed
+    w.write_u32::<LittleEndian>(0)?; // B[4] reserved
+    Ok(())
+}
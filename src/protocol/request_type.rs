@@ -1,3 +1,5 @@
+use crate::{HdbError, HdbResult};
+
 // Defines the action requested from the database server.
 // Is documented as Message Type.
 // Irrelevant RequestTypes (abap related, "reserved" stuff) are omitted.
@@ -8,9 +10,9 @@ pub enum RequestType {
     Prepare,       // Prepare an SQL statement
     // OldXaStart,      // Start a distributed transaction
     // OldXaJoin,       // Join a distributed transaction
-    Execute, // Execute a previously prepared SQL statement
-    ReadLob, // Reads large object data
-    // WriteLob,        // Writes large object data
+    Execute,  // Execute a previously prepared SQL statement
+    ReadLob,  // Reads large object data
+    WriteLob, // Writes large object data
     // FindLob,         // Finds data in a large object
     Authenticate, // Sends authentication data
     Connect,      // Connects to the database
@@ -19,11 +21,11 @@ pub enum RequestType {
     CloseResultSet,  // Closes resultset
     DropStatementId, // Drops prepared statement identifier
     FetchNext,       // Fetches next data from resultset
-    // FetchAbsolute,   // Moves the cursor to the given row number and fetches the data
-    // FetchRelative,   // Like above, but moves the cursor relative to the current position
-    // FetchFirst,      // Moves the cursor to the first row and fetches the data
-    // FetchLast,       // Moves the cursor to the last row and fetches the data
-    Disconnect, // Disconnects session
+    FetchAbsolute,   // Moves the cursor to the given row number and fetches the data
+    FetchRelative,   // Like above, but moves the cursor relative to the current position
+    FetchFirst,      // Moves the cursor to the first row and fetches the data
+    FetchLast,       // Moves the cursor to the last row and fetches the data
+    Disconnect,      // Disconnects session
     // DbConnectInfo,   // Request/receive database connect information
     XAStart,    // = 83,
     XAEnd,      // = 84,
@@ -35,6 +37,23 @@ pub enum RequestType {
 }
 
 impl RequestType {
+    // Requests that execute a statement, and thus can be subject to a query timeout.
+    pub fn is_execution(&self) -> bool {
+        matches!(*self, RequestType::ExecuteDirect | RequestType::Execute)
+    }
+
+    // Requests that do not refer to server-side resources other than prepared statements
+    // can be sent again on a new session.
+    pub fn is_replayable(&self) -> bool {
+        matches!(
+            *self,
+            RequestType::ExecuteDirect
+                | RequestType::Prepare
+                | RequestType::Execute
+                | RequestType::DropStatementId
+        )
+    }
+
     pub fn to_i8(&self) -> i8 {
         match *self {
             // RequestType::DummyForReply => 1, // for test purposes only
@@ -44,7 +63,7 @@ impl RequestType {
             // RequestType::OldXaJoin => 6,
             RequestType::Execute => 13,
             RequestType::ReadLob => 16,
-            // RequestType::WriteLob => 17,
+            RequestType::WriteLob => 17,
             // RequestType::FindLob => 18,
             RequestType::Authenticate => 65,
             RequestType::Connect => 66,
@@ -53,10 +72,10 @@ impl RequestType {
             RequestType::CloseResultSet => 69,
             RequestType::DropStatementId => 70,
             RequestType::FetchNext => 71,
-            // RequestType::FetchAbsolute => 72,
-            // RequestType::FetchRelative => 73,
-            // RequestType::FetchFirst => 74,
-            // RequestType::FetchLast => 75,
+            RequestType::FetchAbsolute => 72,
+            RequestType::FetchRelative => 73,
+            RequestType::FetchFirst => 74,
+            RequestType::FetchLast => 75,
             RequestType::Disconnect => 77,
             // RequestType::DbConnectInfo => 82,
             RequestType::XAStart => 83,
@@ -68,4 +87,36 @@ impl RequestType {
             RequestType::XAForget => 89,
         }
     }
+
+    // Is needed for packet dumps and for the mock server, which must understand the requests.
+    pub fn from_i8(val: i8) -> HdbResult<RequestType> {
+        match val {
+            2 => Ok(RequestType::ExecuteDirect),
+            3 => Ok(RequestType::Prepare),
+            13 => Ok(RequestType::Execute),
+            16 => Ok(RequestType::ReadLob),
+            17 => Ok(RequestType::WriteLob),
+            65 => Ok(RequestType::Authenticate),
+            66 => Ok(RequestType::Connect),
+            69 => Ok(RequestType::CloseResultSet),
+            70 => Ok(RequestType::DropStatementId),
+            71 => Ok(RequestType::FetchNext),
+            72 => Ok(RequestType::FetchAbsolute),
+            73 => Ok(RequestType::FetchRelative),
+            74 => Ok(RequestType::FetchFirst),
+            75 => Ok(RequestType::FetchLast),
+            77 => Ok(RequestType::Disconnect),
+            83 => Ok(RequestType::XAStart),
+            84 => Ok(RequestType::XAEnd),
+            85 => Ok(RequestType::XAPrepare),
+            86 => Ok(RequestType::XACommit),
+            87 => Ok(RequestType::XARollback),
+            88 => Ok(RequestType::XARecover),
+            89 => Ok(RequestType::XAForget),
+            _ => Err(HdbError::Impl(format!(
+                "Unknown value for RequestType: {}",
+                val
+            ))),
+        }
+    }
 }
@@ -3,8 +3,10 @@ use crate::protocol::parts::type_id::TypeId;
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use chrono::{Datelike, NaiveDate};
+use serde_db::de::ConversionError;
 use serde_derive::Serialize;
 use std::cmp;
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::io;
@@ -19,6 +21,9 @@ const JGREG: i32 = 2_299_161;
 ///
 /// The type is used internally to implement serialization to the wire.
 /// It is agnostic of timezones.
+///
+/// `TryFrom` converts from and into `chrono::NaiveDate`, and, with feature `time`,
+/// `time::Date`.
 #[derive(Clone, Debug, Serialize)]
 pub struct DayDate(i32);
 
@@ -104,7 +109,7 @@ impl DayDate {
 
     /// Parses a `DayDate` from a String.
     ///
-    /// Note that Chrono types serialize as formatted Strings.
+    /// Note that chrono and time types serialize as formatted Strings.
     /// We parse such (and other) Strings and construct a `DayDate`.
     pub fn from_date_string(s: &str) -> HdbResult<DayDate> {
         type FSD = fn(&str) -> HdbResult<DayDate>;
@@ -129,6 +134,62 @@ impl DayDate {
     }
 }
 
+impl TryFrom<NaiveDate> for DayDate {
+    type Error = HdbError;
+    fn try_from(nd: NaiveDate) -> HdbResult<DayDate> {
+        DayDate::from_ymd(nd.year(), nd.month(), nd.day())
+    }
+}
+
+impl TryFrom<DayDate> for NaiveDate {
+    type Error = HdbError;
+    fn try_from(dd: DayDate) -> HdbResult<NaiveDate> {
+        let (year, month, day) = dd.as_ymd();
+        naive_date(year, month, day)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::Date> for DayDate {
+    type Error = HdbError;
+    fn try_from(date: time::Date) -> HdbResult<DayDate> {
+        DayDate::from_ymd(
+            date.year(),
+            u32::from(u8::from(date.month())),
+            u32::from(date.day()),
+        )
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<DayDate> for time::Date {
+    type Error = HdbError;
+    fn try_from(dd: DayDate) -> HdbResult<time::Date> {
+        let (year, month, day) = dd.as_ymd();
+        time_date(year, month, day)
+    }
+}
+
+// HANA uses the julian calendar for dates before 1582-10-15, chrono and time use
+// the proleptic gregorian calendar; dates like 1500-02-29 only exist in the former.
+pub(crate) fn naive_date(year: i32, month: u32, day: u32) -> HdbResult<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| not_gregorian(year, month, day))
+}
+
+#[cfg(feature = "time")]
+pub(crate) fn time_date(year: i32, month: u32, day: u32) -> HdbResult<time::Date> {
+    time::Month::try_from(month as u8)
+        .and_then(|month| time::Date::from_calendar_date(year, month, day as u8))
+        .map_err(|_| not_gregorian(year, month, day))
+}
+
+fn not_gregorian(year: i32, month: u32, day: u32) -> HdbError {
+    HdbError::Conversion(ConversionError::ValueType(format!(
+        "The date {:04}-{:02}-{:02} does not exist in the gregorian calendar",
+        year, month, day
+    )))
+}
+
 fn to_day_number(y: u32, m: u32, d: u32) -> i32 {
     let (yd, md) = to_day(m);
     let y2 = y as i32 + yd;
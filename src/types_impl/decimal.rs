@@ -6,6 +6,10 @@ use bigdecimal::BigDecimal;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use num::bigint::BigInt;
 use num::{FromPrimitive, ToPrimitive};
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
+#[cfg(feature = "rust_decimal")]
+use serde_db::ser::SerializationError;
 use std::io;
 
 pub fn parse_decimal(
@@ -52,6 +56,52 @@ pub fn parse_decimal(
     }
 }
 
+// Parses into HdbValue::RUST_DECIMAL, without the detour via BigDecimal;
+// values that don't fit into a Decimal are parsed into HdbValue::DECIMAL.
+#[cfg(feature = "rust_decimal")]
+pub fn parse_rust_decimal(
+    nullable: bool,
+    type_id: TypeId,
+    scale: i16,
+    rdr: &mut dyn io::BufRead,
+) -> HdbResult<HdbValue> {
+    let mantissa = match type_id {
+        TypeId::DECIMAL => return HdbDecimal::parse_hdb_rust_decimal(nullable, scale, rdr),
+        TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 if parse_null(nullable, rdr)? => {
+            return Ok(HdbValue::NULL(type_id));
+        }
+        TypeId::FIXED8 => {
+            trace!("parse FIXED8");
+            i128::from(rdr.read_i64::<LittleEndian>()?)
+        }
+        TypeId::FIXED12 => {
+            trace!("parse FIXED12");
+            let bytes = crate::protocol::util::parse_bytes(12, rdr)?;
+            // extend the sign to 16 bytes
+            let mut buf = if bytes[11] & 0b_1000_0000_u8 == 0 {
+                [0_u8; 16]
+            } else {
+                [255_u8; 16]
+            };
+            buf[..12].copy_from_slice(&bytes);
+            i128::from_le_bytes(buf)
+        }
+        TypeId::FIXED16 => {
+            trace!("parse FIXED16");
+            rdr.read_i128::<LittleEndian>()?
+        }
+        _ => return Err(HdbError::Impl("unexpected type id for decimal".to_owned())),
+    };
+    if scale >= 0 {
+        if let Ok(dec) = Decimal::try_from_i128_with_scale(mantissa, scale as u32) {
+            return Ok(HdbValue::RUST_DECIMAL(dec));
+        }
+    }
+    let bi = BigInt::from_i128(mantissa)
+        .ok_or_else(|| HdbError::Impl("invalid value of fixed type".to_owned()))?;
+    Ok(HdbValue::DECIMAL(BigDecimal::new(bi, i64::from(scale))))
+}
+
 fn parse_null(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<bool> {
     let is_null = rdr.read_u8()? == 0;
     if is_null && !nullable {
@@ -75,6 +125,7 @@ pub(crate) fn emit_decimal(
             let hdb_decimal = HdbDecimal::from_bigdecimal(bd)?;
             w.write_all(&hdb_decimal.into_raw())?;
         }
+        // the FIXED types use the scale of the column; surplus fractional digits are cut off
         TypeId::FIXED8 => {
             trace!("emit FIXED8");
             let bd = bd.with_scale(i64::from(scale));
@@ -112,3 +163,156 @@ pub(crate) fn emit_decimal(
     }
     Ok(())
 }
+
+#[cfg(feature = "rust_decimal")]
+pub(crate) fn emit_rust_decimal(
+    dec: &Decimal,
+    type_id: TypeId,
+    scale: i16,
+    w: &mut dyn io::Write,
+) -> HdbResult<()> {
+    match type_id {
+        TypeId::DECIMAL => {
+            trace!("emit DECIMAL");
+            w.write_all(&HdbDecimal::from_rust_decimal(dec).into_raw())?;
+        }
+        TypeId::FIXED8 => {
+            trace!("emit FIXED8");
+            let mantissa = mantissa_with_scale(dec, type_id, scale)?;
+            if !(i128::from(i64::MIN)..=i128::from(i64::MAX)).contains(&mantissa) {
+                return Err(range_error(type_id));
+            }
+            w.write_i64::<LittleEndian>(mantissa as i64)?;
+        }
+        TypeId::FIXED12 => {
+            trace!("emit FIXED12");
+            let mantissa = mantissa_with_scale(dec, type_id, scale)?;
+            if !(-(1_i128 << 95)..(1_i128 << 95)).contains(&mantissa) {
+                return Err(range_error(type_id));
+            }
+            w.write_all(&mantissa.to_le_bytes()[..12])?;
+        }
+        TypeId::FIXED16 => {
+            trace!("emit FIXED16");
+            w.write_i128::<LittleEndian>(mantissa_with_scale(dec, type_id, scale)?)?;
+        }
+        _ => return Err(HdbError::Impl("unexpected type id for decimal".to_owned())),
+    }
+    Ok(())
+}
+
+// The mantissa of the value with the scale of the column; as with BigDecimals,
+// surplus fractional digits are cut off.
+#[cfg(feature = "rust_decimal")]
+fn mantissa_with_scale(dec: &Decimal, type_id: TypeId, scale: i16) -> HdbResult<i128> {
+    let shift = i32::from(scale) - dec.scale() as i32;
+    if shift >= 0 {
+        10_i128
+            .checked_pow(shift as u32)
+            .and_then(|factor| dec.mantissa().checked_mul(factor))
+            .ok_or_else(|| range_error(type_id))
+    } else {
+        Ok(match 10_i128.checked_pow(-shift as u32) {
+            Some(divisor) => dec.mantissa() / divisor,
+            None => 0,
+        })
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+fn range_error(type_id: TypeId) -> HdbError {
+    HdbError::Serialization(SerializationError::Range("Decimal", type_id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{emit_decimal, parse_decimal};
+    #[cfg(feature = "rust_decimal")]
+    use super::{emit_rust_decimal, parse_rust_decimal};
+    use crate::protocol::parts::hdb_value::HdbValue;
+    use crate::protocol::parts::type_id::TypeId;
+    use bigdecimal::BigDecimal;
+    #[cfg(feature = "rust_decimal")]
+    use rust_decimal::Decimal;
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_fixed_scale() {
+        for type_id in &[TypeId::FIXED8, TypeId::FIXED12, TypeId::FIXED16] {
+            for (input, output) in &[("1.23456", "1.23"), ("-1.23456", "-1.23"), ("7", "7.00")] {
+                let mut buf = vec![1_u8]; // not null
+                emit_decimal(&BigDecimal::from_str(input).unwrap(), *type_id, 2, &mut buf).unwrap();
+                match parse_decimal(false, *type_id, 2, &mut Cursor::new(buf)).unwrap() {
+                    HdbValue::DECIMAL(bd) => assert_eq!(bd.to_string(), *output),
+                    value => panic!("unexpected value {:?}", value),
+                }
+
+                #[cfg(feature = "rust_decimal")]
+                {
+                    let mut buf = vec![1_u8]; // not null
+                    emit_rust_decimal(&Decimal::from_str(input).unwrap(), *type_id, 2, &mut buf)
+                        .unwrap();
+                    match parse_rust_decimal(false, *type_id, 2, &mut Cursor::new(buf)).unwrap() {
+                        HdbValue::RUST_DECIMAL(dec) => assert_eq!(dec.to_string(), *output),
+                        value => panic!("unexpected value {:?}", value),
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_rust_decimal() {
+        for (type_id, scale) in &[
+            (TypeId::DECIMAL, i16::MAX),
+            (TypeId::DECIMAL, 4),
+            (TypeId::FIXED8, 4),
+            (TypeId::FIXED12, 4),
+            (TypeId::FIXED16, 4),
+        ] {
+            for s in &[
+                "0",
+                "1234.5678",
+                "-1234.5",
+                "-0.0001",
+                "922337203685477.5807",
+            ] {
+                let dec = Decimal::from_str(s).unwrap();
+                let mut buf = Vec::<u8>::new();
+                if *type_id != TypeId::DECIMAL {
+                    buf.push(1); // not null
+                }
+                emit_rust_decimal(&dec, *type_id, *scale, &mut buf).unwrap();
+                match parse_rust_decimal(false, *type_id, *scale, &mut Cursor::new(buf.clone()))
+                    .unwrap()
+                {
+                    HdbValue::RUST_DECIMAL(value) => assert_eq!(value, dec),
+                    value => panic!("unexpected value {:?}", value),
+                }
+                // the wire format is the same as with BigDecimals
+                match parse_decimal(false, *type_id, *scale, &mut Cursor::new(buf)).unwrap() {
+                    HdbValue::DECIMAL(bd) => assert_eq!(bd, BigDecimal::from_str(s).unwrap()),
+                    value => panic!("unexpected value {:?}", value),
+                }
+            }
+        }
+
+        // values that don't fit are rejected, or parsed into BigDecimals
+        let dec = Decimal::from_str("1234567890123456.7").unwrap();
+        assert!(emit_rust_decimal(&dec, TypeId::FIXED8, 4, &mut Vec::<u8>::new()).is_err());
+        let mut buf = vec![1_u8];
+        buf.extend_from_slice(&i128::MAX.to_le_bytes());
+        match parse_rust_decimal(false, TypeId::FIXED16, 4, &mut Cursor::new(buf)).unwrap() {
+            HdbValue::DECIMAL(_) => {}
+            value => panic!("unexpected value {:?}", value),
+        }
+
+        // null values
+        match parse_rust_decimal(true, TypeId::FIXED8, 4, &mut Cursor::new(vec![0_u8])).unwrap() {
+            HdbValue::NULL(TypeId::FIXED8) => {}
+            value => panic!("unexpected value {:?}", value),
+        }
+    }
+}
@@ -4,6 +4,8 @@ use crate::protocol::parts::type_id::TypeId;
 use bigdecimal::{BigDecimal, Zero};
 use byteorder::{ByteOrder, LittleEndian};
 use num::bigint::{BigInt, Sign};
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
 use serde_db::ser::SerializationError;
 
 // MANTISSA     113-bit     Integer mantissa
@@ -31,6 +33,36 @@ impl HdbDecimal {
         scale: i16,
         rdr: &mut std::io::BufRead,
     ) -> HdbResult<HdbValue> {
+        Ok(match HdbDecimal::parse(nullable, rdr)? {
+            None => HdbValue::NULL(TypeId::DECIMAL),
+            Some(hdb_decimal) => {
+                trace!("parse DECIMAL");
+                HdbValue::DECIMAL(hdb_decimal.into_bigdecimal_with_scale(scale))
+            }
+        })
+    }
+
+    // Values that don't fit into a Decimal are parsed into HdbValue::DECIMAL.
+    #[cfg(feature = "rust_decimal")]
+    pub fn parse_hdb_rust_decimal(
+        nullable: bool,
+        scale: i16,
+        rdr: &mut dyn std::io::BufRead,
+    ) -> HdbResult<HdbValue> {
+        Ok(match HdbDecimal::parse(nullable, rdr)? {
+            None => HdbValue::NULL(TypeId::DECIMAL),
+            Some(hdb_decimal) => {
+                trace!("parse DECIMAL");
+                match hdb_decimal.to_rust_decimal_with_scale(scale) {
+                    Some(dec) => HdbValue::RUST_DECIMAL(dec),
+                    None => HdbValue::DECIMAL(hdb_decimal.into_bigdecimal_with_scale(scale)),
+                }
+            }
+        })
+    }
+
+    // Returns None for the NULL value.
+    fn parse(nullable: bool, rdr: &mut dyn std::io::BufRead) -> HdbResult<Option<HdbDecimal>> {
         let mut raw = [0_u8; 16];
         rdr.read_exact(&mut raw[..])?;
         let is_null = raw[15] == 112
@@ -52,16 +84,14 @@ impl HdbDecimal {
 
         if is_null {
             if nullable {
-                Ok(HdbValue::NULL(TypeId::DECIMAL))
+                Ok(None)
             } else {
                 Err(HdbError::Impl(
                     "found null value for not-null column".to_owned(),
                 ))
             }
         } else {
-            trace!("parse DECIMAL");
-            let bd = HdbDecimal::new(raw).into_bigdecimal_with_scale(scale);
-            Ok(HdbValue::DECIMAL(bd))
+            Ok(Some(HdbDecimal::new(raw)))
         }
     }
 
@@ -116,6 +146,61 @@ impl HdbDecimal {
         Ok(hdbdecimal)
     }
 
+    // Creates an HdbDecimal from a Decimal, which always fits.
+    #[cfg(feature = "rust_decimal")]
+    pub fn from_rust_decimal(dec: &Decimal) -> HdbDecimal {
+        let mut mantissa = dec.mantissa().abs();
+        let mut exponent = -(dec.scale() as i32);
+
+        // HANA does not like mantissas that are multiples of 10
+        while mantissa != 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            exponent += 1;
+        }
+
+        // the mantissa has at most 96 bits, and the exponent is between -28 and 0
+        let mut raw = mantissa.to_le_bytes();
+        let biased_exponent: u16 = (exponent + 6176) as u16;
+        LittleEndian::write_u16(&mut raw[14..=15], biased_exponent * 2);
+
+        if dec.is_sign_negative() && mantissa != 0 {
+            raw[15] |= 0b_1000_0000_u8;
+        }
+        HdbDecimal { raw }
+    }
+
+    // Returns None if the value does not fit into a Decimal.
+    #[cfg(feature = "rust_decimal")]
+    pub fn to_rust_decimal_with_scale(&self, scale: i16) -> Option<Decimal> {
+        let is_negative = (self.raw[15] & 0b_1000_0000_u8) != 0;
+        let exponent = i32::from((LittleEndian::read_u16(&self.raw[14..=15]) & 0x7FFF) >> 1) - 6176;
+        let mut bytes = [0_u8; 16];
+        bytes[..15].copy_from_slice(&self.raw[..15]);
+        bytes[14] &= 0b_0000_0001_u8;
+        let mantissa = i128::from_le_bytes(bytes);
+
+        // the scale of the column, if given, otherwise the one implied by the exponent
+        let scale = if scale < i16::MAX {
+            i32::from(scale)
+        } else {
+            std::cmp::max(-exponent, 0)
+        };
+        if scale < 0 {
+            return None;
+        }
+        let shift = exponent + scale;
+        let mantissa = if shift >= 0 {
+            mantissa.checked_mul(10_i128.checked_pow(shift as u32)?)?
+        } else {
+            match 10_i128.checked_pow(-shift as u32) {
+                Some(divisor) => mantissa / divisor,
+                None => 0,
+            }
+        };
+        let mantissa = if is_negative { -mantissa } else { mantissa };
+        Decimal::try_from_i128_with_scale(mantissa, scale as u32).ok()
+    }
+
     pub fn into_bigdecimal_with_scale(self, scale: i16) -> BigDecimal {
         let mut bd = self.into_bigdecimal();
         if scale < std::i16::MAX {
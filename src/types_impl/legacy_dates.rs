@@ -0,0 +1,176 @@
+// The date and time formats of data format version 1, which are used by old servers
+// for the database types DATE, TIME, and TIMESTAMP.
+//
+// DATE:      I2 year, with bit 15 set for non-null values; I1 month (0 - 11); I1 day
+// TIME:      I1 hour, with bit 7 set for non-null values; I1 minute; I2 milliseconds
+// TIMESTAMP: DATE followed by TIME
+//
+// The values are mapped to DAYDATE, SECONDTIME, and LONGDATE.
+use crate::protocol::parts::hdb_value::HdbValue;
+use crate::protocol::parts::type_id::TypeId;
+use crate::types::{DayDate, LongDate, SecondDate, SecondTime};
+use crate::{HdbError, HdbResult};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io;
+
+const DATE_NOT_NULL: u16 = 0b_1000_0000_0000_0000;
+const TIME_NOT_NULL: u8 = 0b_1000_0000;
+
+pub fn parse_date(nullable: bool, rdr: &mut dyn io::BufRead) -> HdbResult<HdbValue> {
+    match read_date(rdr)? {
+        Some((year, month, day)) => Ok(HdbValue::DAYDATE(DayDate::from_ymd(year, month, day)?)),
+        None => null_value(nullable, TypeId::DATE),
+    }
+}
+
+pub fn parse_time(nullable: bool, rdr: &mut dyn io::BufRead) -> HdbResult<HdbValue> {
+    match read_time(rdr)? {
+        Some((hour, minute, millis)) => Ok(HdbValue::SECONDTIME(SecondTime::from_hms(
+            hour,
+            minute,
+            millis / 1000,
+        )?)),
+        None => null_value(nullable, TypeId::TIME),
+    }
+}
+
+pub fn parse_timestamp(nullable: bool, rdr: &mut dyn io::BufRead) -> HdbResult<HdbValue> {
+    match (read_date(rdr)?, read_time(rdr)?) {
+        (Some((year, month, day)), Some((hour, minute, millis))) => {
+            Ok(HdbValue::LONGDATE(LongDate::from_ymd_hms_n(
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                millis / 1000,
+                (millis % 1000) * 1_000_000,
+            )?))
+        }
+        _ => null_value(nullable, TypeId::TIMESTAMP),
+    }
+}
+
+fn null_value(nullable: bool, type_id: TypeId) -> HdbResult<HdbValue> {
+    if nullable {
+        Ok(HdbValue::NULL(type_id))
+    } else {
+        Err(HdbError::Impl(format!(
+            "found NULL value for NOT NULL {} column",
+            type_id
+        )))
+    }
+}
+
+fn read_date(rdr: &mut dyn io::BufRead) -> HdbResult<Option<(i32, u32, u32)>> {
+    let year = rdr.read_u16::<LittleEndian>()?;
+    let month = rdr.read_u8()?;
+    let day = rdr.read_u8()?;
+    Ok(if year & DATE_NOT_NULL == 0 {
+        None
+    } else {
+        Some((
+            i32::from(year & !DATE_NOT_NULL),
+            u32::from(month) + 1,
+            u32::from(day),
+        ))
+    })
+}
+
+fn read_time(rdr: &mut dyn io::BufRead) -> HdbResult<Option<(u32, u32, u32)>> {
+    let hour = rdr.read_u8()?;
+    let minute = rdr.read_u8()?;
+    let millis = rdr.read_u16::<LittleEndian>()?;
+    Ok(if hour & TIME_NOT_NULL == 0 {
+        None
+    } else {
+        Some((
+            u32::from(hour & !TIME_NOT_NULL),
+            u32::from(minute),
+            u32::from(millis),
+        ))
+    })
+}
+
+pub(crate) fn emit_date(dd: &DayDate, w: &mut dyn io::Write) -> HdbResult<()> {
+    let (year, month, day) = dd.as_ymd();
+    write_date(year, month, day, w)
+}
+
+pub(crate) fn emit_time(st: &SecondTime, w: &mut dyn io::Write) -> HdbResult<()> {
+    let (hour, minute, second) = st.as_hms();
+    write_time(hour, minute, second * 1000, w)
+}
+
+pub(crate) fn emit_timestamp(ld: &LongDate, w: &mut dyn io::Write) -> HdbResult<()> {
+    let (year, month, day, hour, minute, second, fraction) = ld.as_ymd_hms_f();
+    write_date(year, month, day, w)?;
+    // the fraction is given in 100 nanoseconds
+    write_time(hour, minute, second * 1000 + fraction / 10_000, w)
+}
+
+pub(crate) fn emit_seconddate_as_timestamp(
+    sd: &SecondDate,
+    w: &mut dyn io::Write,
+) -> HdbResult<()> {
+    let (year, month, day, hour, minute, second) = sd.as_ymd_hms();
+    write_date(year, month, day, w)?;
+    write_time(hour, minute, second * 1000, w)
+}
+
+fn write_date(year: i32, month: u32, day: u32, w: &mut dyn io::Write) -> HdbResult<()> {
+    w.write_u16::<LittleEndian>(year as u16 | DATE_NOT_NULL)?; // I2
+    w.write_u8(month as u8 - 1)?; // I1
+    w.write_u8(day as u8)?; // I1
+    Ok(())
+}
+
+fn write_time(hour: u32, minute: u32, millis: u32, w: &mut dyn io::Write) -> HdbResult<()> {
+    w.write_u8(hour as u8 | TIME_NOT_NULL)?; // I1
+    w.write_u8(minute as u8)?; // I1
+    w.write_u16::<LittleEndian>(millis as u16)?; // I2
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{emit_date, emit_timestamp, parse_date, parse_time, parse_timestamp};
+    use crate::protocol::parts::hdb_value::HdbValue;
+    use crate::protocol::parts::type_id::TypeId;
+    use crate::types::{DayDate, LongDate};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_legacy_dates() {
+        let mut buf = Vec::<u8>::new();
+        emit_date(&DayDate::from_ymd(2012, 2, 29).unwrap(), &mut buf).unwrap();
+        assert_eq!(buf, vec![0xDC, 0x87, 1, 29]);
+        match parse_date(false, &mut Cursor::new(&buf)).unwrap() {
+            HdbValue::DAYDATE(dd) => assert_eq!(dd.as_ymd(), (2012, 2, 29)),
+            v => panic!("unexpected value {:?}", v),
+        }
+
+        let ld = LongDate::from_ymd_hms_n(1999, 12, 31, 23, 59, 58, 123_000_000).unwrap();
+        let mut buf = Vec::<u8>::new();
+        emit_timestamp(&ld, &mut buf).unwrap();
+        match parse_timestamp(false, &mut Cursor::new(&buf)).unwrap() {
+            HdbValue::LONGDATE(value) => assert_eq!(value, ld),
+            v => panic!("unexpected value {:?}", v),
+        }
+        match parse_time(false, &mut Cursor::new(&buf[4..])).unwrap() {
+            HdbValue::SECONDTIME(st) => assert_eq!(st.as_hms(), (23, 59, 58)),
+            v => panic!("unexpected value {:?}", v),
+        }
+
+        // null values have the flag bits cleared
+        let buf = [0_u8; 8];
+        assert!(parse_timestamp(true, &mut Cursor::new(&buf))
+            .unwrap()
+            .is_null());
+        assert!(parse_time(false, &mut Cursor::new(&buf)).is_err());
+        match parse_date(true, &mut Cursor::new(&buf)).unwrap() {
+            HdbValue::NULL(TypeId::DATE) => {}
+            v => panic!("unexpected value {:?}", v),
+        }
+    }
+}
@@ -3,11 +3,15 @@ mod clob;
 mod fetch;
 mod nclob;
 mod wire;
+mod write;
 
 pub(crate) use self::blob::new_blob_to_db;
 pub use self::blob::BLob;
 pub use self::clob::CLob;
 pub(crate) use self::fetch::fetch_a_lob_chunk;
 pub use self::nclob::NCLob;
-pub(crate) use self::wire::{emit_blob_header, emit_clob_header, emit_nclob_header};
+pub(crate) use self::wire::{
+    emit_blob_header, emit_clob_header, emit_lob_stream_header, emit_nclob_header,
+};
 pub(crate) use self::wire::{parse_blob, parse_clob, parse_nclob};
+pub(crate) use self::write::{write_lob_chunks, LobWriter};
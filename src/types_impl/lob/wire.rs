@@ -161,3 +161,19 @@ pub(crate) fn emit_nclob_header(
     *data_pos += s_len as i32;
     Ok(())
 }
+
+// Header of a LOB parameter whose data are streamed:
+// only the first chunk is included, the rest follows with WRITELOB requests.
+pub(crate) fn emit_lob_stream_header(
+    chunk_len: usize,
+    is_last_data: bool,
+    data_pos: &mut i32,
+    w: &mut dyn io::Write,
+) -> HdbResult<()> {
+    // bit 0: not used; bit 1: data is included; bit 2: no more data remaining
+    w.write_u8(if is_last_data { 0b_110_u8 } else { 0b_010_u8 })?; // I1 Bit set for options
+    w.write_i32::<LittleEndian>(chunk_len as i32)?; // I4           LENGTH OF VALUE
+    w.write_i32::<LittleEndian>(*data_pos)?; // I4           position
+    *data_pos += chunk_len as i32;
+    Ok(())
+}
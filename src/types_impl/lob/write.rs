@@ -0,0 +1,171 @@
+use crate::conn_core::AmConnCore;
+use crate::protocol::argument::Argument;
+use crate::protocol::part::Part;
+use crate::protocol::partkind::PartKind;
+use crate::protocol::parts::type_id::TypeId;
+use crate::protocol::parts::write_lob_request::WriteLobRequest;
+use crate::protocol::reply_type::ReplyType;
+use crate::protocol::request::Request;
+use crate::protocol::request_type::RequestType;
+use crate::{HdbError, HdbResult};
+use cesu8;
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+
+// Reads the data of a streamed LOB parameter in chunks.
+//
+// For NCLOBs the reader provides UTF-8, which is converted to CESU-8;
+// incomplete characters at the end of a chunk are kept back for the next chunk.
+pub(crate) struct LobWriter {
+    reader: Arc<Mutex<dyn io::Read + Send>>,
+    type_id: TypeId,
+    utf8_tail: Vec<u8>,
+}
+
+impl LobWriter {
+    pub fn new(reader: Arc<Mutex<dyn io::Read + Send>>, type_id: TypeId) -> LobWriter {
+        LobWriter {
+            reader,
+            type_id,
+            utf8_tail: Vec::<u8>::new(),
+        }
+    }
+
+    // Returns the next chunk, and true if no more data are remaining.
+    pub fn next_chunk(&mut self, chunk_size: usize) -> HdbResult<(Vec<u8>, bool)> {
+        let mut raw = std::mem::take(&mut self.utf8_tail);
+        let to_read = chunk_size.saturating_sub(raw.len()).max(1) as u64;
+        let read = {
+            let mut guard = self.reader.lock()?;
+            (&mut *guard).take(to_read).read_to_end(&mut raw)?
+        };
+        let is_last_data = (read as u64) < to_read;
+
+        match self.type_id {
+            TypeId::NCLOB => {
+                let valid_up_to = match std::str::from_utf8(&raw) {
+                    Ok(_) => raw.len(),
+                    Err(e) => {
+                        if e.error_len().is_none() && !is_last_data {
+                            e.valid_up_to()
+                        } else {
+                            return Err(HdbError::Usage(
+                                "The reader of an NCLOB parameter provided invalid UTF-8"
+                                    .to_owned(),
+                            ));
+                        }
+                    }
+                };
+                self.utf8_tail = raw.split_off(valid_up_to);
+                let s = std::str::from_utf8(&raw).unwrap(/* checked above */);
+                Ok((cesu8::to_cesu8(s).into_owned(), is_last_data))
+            }
+            _ => Ok((raw, is_last_data)),
+        }
+    }
+}
+
+impl std::fmt::Debug for LobWriter {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "LobWriter {{ type_id: {} }}", self.type_id)
+    }
+}
+
+// Sends the remaining data of the streamed LOB parameters with WRITELOB requests.
+//
+// The locator ids are those from the WriteLobReply of the execute call,
+// and have to correspond to the given LobWriters.
+pub(crate) fn write_lob_chunks(
+    am_conn_core: &mut AmConnCore,
+    locator_ids: Vec<u64>,
+    lob_writers: Vec<LobWriter>,
+) -> HdbResult<()> {
+    if locator_ids.len() != lob_writers.len() {
+        return Err(HdbError::Impl(format!(
+            "write_lob_chunks(): got {} locator ids for {} incomplete lobs",
+            locator_ids.len(),
+            lob_writers.len()
+        )));
+    }
+    let chunk_size = am_conn_core.lock()?.get_lob_write_length();
+
+    for (locator_id, mut lob_writer) in locator_ids.into_iter().zip(lob_writers) {
+        loop {
+            let (data, is_last_data) = lob_writer.next_chunk(chunk_size)?;
+            trace!(
+                "Sending WriteLobRequest for locator_id {} with {} bytes",
+                locator_id,
+                data.len()
+            );
+            let mut request = Request::new(RequestType::WriteLob, 0);
+            request.push(Part::new(
+                PartKind::WriteLobRequest,
+                Argument::WriteLobRequest(WriteLobRequest::new(locator_id, data, is_last_data)),
+            ));
+
+            let mut reply = am_conn_core.send(request)?;
+            reply.assert_expected_reply_type(&ReplyType::WriteLob)?;
+
+            while let Some(part) = reply.parts.pop() {
+                let (_kind, arg) = part.into_elements();
+                match arg {
+                    Argument::WriteLobReply(_) => {}
+                    Argument::StatementContext(ref stmt_ctx) => {
+                        am_conn_core.lock()?.evaluate_statement_context(stmt_ctx)?;
+                    }
+                    Argument::TransactionFlags(ta_flags) => {
+                        am_conn_core.lock()?.evaluate_ta_flags(ta_flags)?;
+                    }
+                    arg => warn!("write_lob_chunks(): ignoring unexpected part {:?}", arg),
+                }
+            }
+
+            if is_last_data {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LobWriter;
+    use crate::protocol::parts::type_id::TypeId;
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_nclob_chunks_keep_characters_intact() {
+        let text = "aä😀b".repeat(100);
+        let reader = Arc::new(Mutex::new(Cursor::new(text.clone().into_bytes())));
+        let mut lob_writer = LobWriter::new(reader, TypeId::NCLOB);
+
+        let mut cesu8 = Vec::<u8>::new();
+        loop {
+            let (chunk, is_last_data) = lob_writer.next_chunk(7).unwrap();
+            // every chunk must be decodable on its own
+            cesu8::from_cesu8(&chunk).unwrap();
+            cesu8.extend(chunk);
+            if is_last_data {
+                break;
+            }
+        }
+        assert_eq!(cesu8::from_cesu8(&cesu8).unwrap(), text);
+    }
+
+    #[test]
+    fn test_blob_chunks() {
+        let data: Vec<u8> = (0..=255).collect();
+        let reader = Arc::new(Mutex::new(Cursor::new(data.clone())));
+        let mut lob_writer = LobWriter::new(reader, TypeId::BLOB);
+
+        let (chunk1, is_last_data) = lob_writer.next_chunk(200).unwrap();
+        assert_eq!(chunk1.len(), 200);
+        assert!(!is_last_data);
+        let (chunk2, is_last_data) = lob_writer.next_chunk(200).unwrap();
+        assert_eq!(chunk2.len(), 56);
+        assert!(is_last_data);
+        assert_eq!([chunk1, chunk2].concat(), data);
+    }
+}
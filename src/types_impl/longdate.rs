@@ -1,10 +1,15 @@
 use crate::protocol::parts::hdb_value::HdbValue;
 use crate::protocol::parts::type_id::TypeId;
+use crate::types_impl::daydate::naive_date;
+#[cfg(feature = "time")]
+use crate::types_impl::daydate::time_date;
+use crate::types_impl::secondtime::illegal_time;
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
-use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike, Utc};
 use serde_derive::Serialize;
 use std::cmp;
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::io;
@@ -24,6 +29,9 @@ const JGREG: i64 = 2_299_161;
 ///
 /// The type is used internally to implement serialization to the wire.
 /// It is agnostic of timezones.
+///
+/// `TryFrom` converts from and into `chrono::NaiveDateTime` and `chrono::DateTime<Utc>`,
+/// and, with feature `time`, `time::PrimitiveDateTime` and `time::OffsetDateTime`.
 #[derive(Clone, Debug, Serialize)]
 pub struct LongDate(i64);
 
@@ -153,7 +161,7 @@ impl LongDate {
 
     /// Parses a `LongDate` from a String.
     ///
-    /// Note that Chrono types serialize as formatted Strings.
+    /// Note that chrono and time types serialize as formatted Strings.
     /// We parse such (and other) Strings and construct a `LongDate`.
     pub fn from_date_string(s: &str) -> HdbResult<LongDate> {
         type FSD = fn(&str) -> HdbResult<LongDate>;
@@ -163,6 +171,7 @@ impl LongDate {
             LongDate::from_string_second,
             LongDate::from_string_day,
             LongDate::from_utc_string,
+            LongDate::from_offset_string,
         ];
 
         for func in funcs {
@@ -226,6 +235,151 @@ impl LongDate {
             ndt.nanosecond(),
         )
     }
+
+    fn from_offset_string(s: &str) -> HdbResult<LongDate> {
+        LongDate::try_from(parse_offset_datetime(s)?)
+    }
+}
+
+// Parses the format of time's OffsetDateTime, e.g. 2012-02-02 02:02:02.2 +01:00:00
+pub(crate) fn parse_offset_datetime(s: &str) -> HdbResult<DateTime<Utc>> {
+    let invalid = || HdbError::Usage(format!("Cannot parse date with offset from \"{}\"", s));
+    let pos = s.rfind(' ').ok_or_else(invalid)?;
+    let (datetime, offset) = (&s[..pos], &s[pos + 1..]);
+    let ndt =
+        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S%.f").map_err(|_| invalid())?;
+    let sign = match offset.get(..1) {
+        Some("+") => 1,
+        Some("-") => -1,
+        _ => return Err(invalid()),
+    };
+    let hms = offset[1..]
+        .split(':')
+        .map(str::parse::<i64>)
+        .collect::<Result<Vec<i64>, _>>()
+        .map_err(|_| invalid())?;
+    if hms.len() != 3 {
+        return Err(invalid());
+    }
+    let offset_seconds = sign * (hms[0] * 3_600 + hms[1] * 60 + hms[2]);
+    Ok(DateTime::<Utc>::from_utc(
+        ndt - chrono::Duration::seconds(offset_seconds),
+        Utc,
+    ))
+}
+
+// Nanoseconds are truncated to the precision of 100 nanoseconds.
+impl TryFrom<NaiveDateTime> for LongDate {
+    type Error = HdbError;
+    fn try_from(ndt: NaiveDateTime) -> HdbResult<LongDate> {
+        LongDate::from_ymd_hms_n(
+            ndt.year(),
+            ndt.month(),
+            ndt.day(),
+            ndt.hour(),
+            ndt.minute(),
+            ndt.second(),
+            // leap seconds are represented by chrono with nanoseconds > 999_999_999
+            ndt.nanosecond().min(999_999_999),
+        )
+    }
+}
+
+impl TryFrom<DateTime<Utc>> for LongDate {
+    type Error = HdbError;
+    fn try_from(dt: DateTime<Utc>) -> HdbResult<LongDate> {
+        LongDate::try_from(dt.naive_utc())
+    }
+}
+
+impl TryFrom<LongDate> for NaiveDateTime {
+    type Error = HdbError;
+    fn try_from(ld: LongDate) -> HdbResult<NaiveDateTime> {
+        let (year, month, day, hour, minute, second, fraction) = ld.as_ymd_hms_f();
+        naive_date_time(year, month, day, hour, minute, second, fraction * 100)
+    }
+}
+
+impl TryFrom<LongDate> for DateTime<Utc> {
+    type Error = HdbError;
+    fn try_from(ld: LongDate) -> HdbResult<DateTime<Utc>> {
+        Ok(DateTime::from_utc(NaiveDateTime::try_from(ld)?, Utc))
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::PrimitiveDateTime> for LongDate {
+    type Error = HdbError;
+    fn try_from(pdt: time::PrimitiveDateTime) -> HdbResult<LongDate> {
+        LongDate::from_ymd_hms_n(
+            pdt.year(),
+            u32::from(u8::from(pdt.month())),
+            u32::from(pdt.day()),
+            u32::from(pdt.hour()),
+            u32::from(pdt.minute()),
+            u32::from(pdt.second()),
+            pdt.nanosecond(),
+        )
+    }
+}
+
+// The value is converted to UTC.
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for LongDate {
+    type Error = HdbError;
+    fn try_from(odt: time::OffsetDateTime) -> HdbResult<LongDate> {
+        let utc = odt.to_offset(time::UtcOffset::UTC);
+        LongDate::try_from(time::PrimitiveDateTime::new(utc.date(), utc.time()))
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<LongDate> for time::PrimitiveDateTime {
+    type Error = HdbError;
+    fn try_from(ld: LongDate) -> HdbResult<time::PrimitiveDateTime> {
+        let (year, month, day, hour, minute, second, fraction) = ld.as_ymd_hms_f();
+        primitive_date_time(year, month, day, hour, minute, second, fraction * 100)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<LongDate> for time::OffsetDateTime {
+    type Error = HdbError;
+    fn try_from(ld: LongDate) -> HdbResult<time::OffsetDateTime> {
+        Ok(time::PrimitiveDateTime::try_from(ld)?.assume_utc())
+    }
+}
+
+pub(crate) fn naive_date_time(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+) -> HdbResult<NaiveDateTime> {
+    naive_date(year, month, day)?
+        .and_hms_nano_opt(hour, minute, second, nanosecond)
+        .ok_or_else(|| illegal_time(hour, minute, second))
+}
+
+#[cfg(feature = "time")]
+pub(crate) fn primitive_date_time(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+) -> HdbResult<time::PrimitiveDateTime> {
+    let time = time::Time::from_hms_nano(hour as u8, minute as u8, second as u8, nanosecond)
+        .map_err(|_| illegal_time(hour, minute, second))?;
+    Ok(time::PrimitiveDateTime::new(
+        time_date(year, month, day)?,
+        time,
+    ))
 }
 
 fn to_day_number(y: u32, m: u32, d: u32) -> i64 {
@@ -269,3 +423,182 @@ pub fn parse_longdate(nullable: bool, rdr: &mut io::BufRead) -> HdbResult<HdbVal
         Ok(HdbValue::LONGDATE(LongDate::new(i)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{DayDate, LongDate, SecondDate, SecondTime};
+    #[cfg(feature = "time")]
+    use crate::HdbValue;
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_chrono_conversions() {
+        let ndt = NaiveDate::from_ymd(2012, 2, 29).and_hms_nano(23, 59, 58, 123_456_789);
+        let ld = LongDate::try_from(ndt).unwrap();
+        assert_eq!(ld.as_ymd_hms_f(), (2012, 2, 29, 23, 59, 58, 1_234_567));
+        assert_eq!(
+            NaiveDateTime::try_from(ld.clone()).unwrap(),
+            NaiveDate::from_ymd(2012, 2, 29).and_hms_nano(23, 59, 58, 123_456_700)
+        );
+        let dt: DateTime<Utc> = DateTime::try_from(ld.clone()).unwrap();
+        assert_eq!(LongDate::try_from(dt).unwrap(), ld);
+
+        let sd = SecondDate::try_from(ndt).unwrap();
+        assert_eq!(sd.as_ymd_hms(), (2012, 2, 29, 23, 59, 58));
+        let dt: DateTime<Utc> = DateTime::try_from(sd.clone()).unwrap();
+        assert_eq!(dt.to_rfc3339(), "2012-02-29T23:59:58+00:00");
+        assert_eq!(SecondDate::try_from(dt).unwrap(), sd);
+
+        let dd = DayDate::try_from(ndt.date()).unwrap();
+        assert_eq!(dd.as_ymd(), (2012, 2, 29));
+        assert_eq!(NaiveDate::try_from(dd).unwrap(), ndt.date());
+
+        let st = SecondTime::try_from(ndt.time()).unwrap();
+        assert_eq!(st.as_hms(), (23, 59, 58));
+        assert_eq!(
+            NaiveTime::try_from(st).unwrap(),
+            NaiveTime::from_hms(23, 59, 58)
+        );
+
+        // chrono has no year 0, and no leap day in the julian year 1500
+        assert!(DayDate::try_from(NaiveDate::from_ymd(0, 1, 1)).is_err());
+        assert!(NaiveDate::try_from(DayDate::from_ymd(1500, 2, 29).unwrap()).is_err());
+
+        // the strings that chrono types serialize to
+        assert_eq!(
+            SecondTime::from_date_string("23:59:58").unwrap(),
+            SecondTime::from_hms(23, 59, 58).unwrap()
+        );
+        assert_eq!(
+            SecondTime::from_date_string("23:59:58.123456").unwrap(),
+            SecondTime::from_hms(23, 59, 58).unwrap()
+        );
+        assert_eq!(
+            SecondDate::from_date_string("2012-02-29T23:59:58.123456789").unwrap(),
+            SecondDate::from_ymd_hms(2012, 2, 29, 23, 59, 58).unwrap()
+        );
+        assert_eq!(
+            LongDate::from_date_string("2012-02-29T23:59:58.123456789Z").unwrap(),
+            LongDate::from_ymd_hms_n(2012, 2, 29, 23, 59, 58, 123_456_700).unwrap()
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_conversions() {
+        let date = time::Date::from_calendar_date(2012, time::Month::February, 29).unwrap();
+        let pdt = date.with_hms_nano(23, 59, 58, 123_456_789).unwrap();
+        let ld = LongDate::try_from(pdt).unwrap();
+        assert_eq!(ld.as_ymd_hms_f(), (2012, 2, 29, 23, 59, 58, 1_234_567));
+        assert_eq!(
+            time::PrimitiveDateTime::try_from(ld.clone()).unwrap(),
+            date.with_hms_nano(23, 59, 58, 123_456_700).unwrap()
+        );
+        let odt = pdt.assume_offset(time::UtcOffset::from_hms(2, 0, 0).unwrap());
+        assert_eq!(
+            LongDate::try_from(odt).unwrap().as_ymd_hms_f(),
+            (2012, 2, 29, 21, 59, 58, 1_234_567)
+        );
+        assert_eq!(
+            time::OffsetDateTime::try_from(ld).unwrap(),
+            date.with_hms_nano(23, 59, 58, 123_456_700)
+                .unwrap()
+                .assume_utc()
+        );
+
+        let sd = SecondDate::try_from(odt).unwrap();
+        assert_eq!(sd.as_ymd_hms(), (2012, 2, 29, 21, 59, 58));
+        assert_eq!(
+            time::PrimitiveDateTime::try_from(sd).unwrap(),
+            date.with_hms(21, 59, 58).unwrap()
+        );
+
+        let dd = DayDate::try_from(date).unwrap();
+        assert_eq!(time::Date::try_from(dd).unwrap(), date);
+        assert!(time::Date::try_from(DayDate::from_ymd(1500, 2, 29).unwrap()).is_err());
+
+        let st = SecondTime::try_from(pdt.time()).unwrap();
+        assert_eq!(
+            time::Time::try_from(st).unwrap(),
+            time::Time::from_hms(23, 59, 58).unwrap()
+        );
+
+        // the strings that time types serialize to
+        let to_string = |v: serde_json::Value| v.as_str().unwrap().to_string();
+        assert_eq!(
+            LongDate::from_date_string(&to_string(serde_json::json!(pdt))).unwrap(),
+            LongDate::from_ymd_hms_n(2012, 2, 29, 23, 59, 58, 123_456_700).unwrap()
+        );
+        assert_eq!(
+            LongDate::from_date_string(&to_string(serde_json::json!(odt))).unwrap(),
+            LongDate::from_ymd_hms_n(2012, 2, 29, 21, 59, 58, 123_456_700).unwrap()
+        );
+        assert_eq!(
+            SecondDate::from_date_string(&to_string(serde_json::json!(odt))).unwrap(),
+            SecondDate::from_ymd_hms(2012, 2, 29, 21, 59, 58).unwrap()
+        );
+        assert_eq!(
+            DayDate::from_date_string(&to_string(serde_json::json!(date))).unwrap(),
+            DayDate::from_ymd(2012, 2, 29).unwrap()
+        );
+        assert_eq!(
+            SecondTime::from_date_string(&to_string(serde_json::json!(pdt.time()))).unwrap(),
+            SecondTime::from_hms(23, 59, 58).unwrap()
+        );
+
+        // deserialization into time types, from the strings that HdbValues provide
+        // and from the strings that time types serialize to
+        #[derive(Debug, serde_derive::Deserialize)]
+        struct Data {
+            #[serde(with = "crate::time_serde::primitive_date_time")]
+            ld: time::PrimitiveDateTime,
+            #[serde(with = "crate::time_serde::offset_date_time")]
+            odt: time::OffsetDateTime,
+            #[serde(with = "crate::time_serde::date")]
+            date: time::Date,
+            #[serde(with = "crate::time_serde::time")]
+            time: time::Time,
+            #[serde(with = "crate::time_serde::date::option")]
+            none: Option<time::Date>,
+        }
+        let values = vec![
+            HdbValue::LONGDATE(LongDate::try_from(pdt).unwrap()),
+            HdbValue::SECONDDATE(SecondDate::try_from(odt).unwrap()),
+            HdbValue::DAYDATE(DayDate::try_from(date).unwrap()),
+            HdbValue::SECONDTIME(SecondTime::try_from(pdt.time()).unwrap()),
+        ]
+        .into_iter()
+        .map(|value| value.try_into::<String>().unwrap())
+        .collect::<Vec<String>>();
+        let data: Data = serde_json::from_value(serde_json::json!({
+            "ld": values[0], "odt": values[1], "date": values[2], "time": values[3], "none": null
+        }))
+        .unwrap();
+        assert_eq!(
+            data.ld,
+            date.with_hms_nano(23, 59, 58, 123_456_700).unwrap()
+        );
+        assert_eq!(data.odt, date.with_hms(21, 59, 58).unwrap().assume_utc());
+        assert_eq!(data.date, date);
+        assert_eq!(data.time, time::Time::from_hms(23, 59, 58).unwrap());
+        assert_eq!(data.none, None);
+
+        let data: Data = serde_json::from_value(serde_json::json!({
+            "ld": pdt, "odt": odt, "date": date, "time": pdt.time(), "none": date
+        }))
+        .unwrap();
+        assert_eq!(
+            data.ld,
+            date.with_hms_nano(23, 59, 58, 123_456_700).unwrap()
+        );
+        assert_eq!(
+            data.odt,
+            date.with_hms_nano(21, 59, 58, 123_456_700)
+                .unwrap()
+                .assume_utc()
+        );
+        assert_eq!(data.time, time::Time::from_hms(23, 59, 58).unwrap());
+        assert_eq!(data.none, Some(date));
+    }
+}
@@ -2,8 +2,13 @@ pub mod decimal;
 mod hdb_decimal;
 
 pub mod daydate;
+pub mod legacy_dates;
 pub mod longdate;
 pub mod seconddate;
 pub mod secondtime;
+#[cfg(feature = "geo")]
+pub mod spatial;
+#[cfg(feature = "time")]
+pub mod time_serde;
 
 pub mod lob;
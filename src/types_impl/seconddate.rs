@@ -1,10 +1,14 @@
 use crate::protocol::parts::hdb_value::HdbValue;
 use crate::protocol::parts::type_id::TypeId;
+#[cfg(feature = "time")]
+use crate::types_impl::longdate::primitive_date_time;
+use crate::types_impl::longdate::{naive_date_time, parse_offset_datetime};
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
-use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike, Utc};
 use serde_derive::Serialize;
 use std::cmp;
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::io;
@@ -24,6 +28,9 @@ const JGREG: i64 = 2_299_161;
 ///
 /// The type is used internally to implement serialization to the wire.
 /// It is agnostic of timezones.
+///
+/// `TryFrom` converts from and into `chrono::NaiveDateTime` and `chrono::DateTime<Utc>`,
+/// and, with feature `time`, `time::PrimitiveDateTime` and `time::OffsetDateTime`.
 #[derive(Clone, Debug, Serialize)]
 pub struct SecondDate(i64);
 
@@ -138,7 +145,7 @@ impl SecondDate {
 
     /// Parses a `SecondDate` from a String.
     ///
-    /// Note that Chrono types serialize as formatted Strings.
+    /// Note that chrono and time types serialize as formatted Strings.
     /// We parse such (and other) Strings and construct a `SecondDate`.
     pub fn from_date_string(s: &str) -> HdbResult<SecondDate> {
         type FSD = fn(&str) -> HdbResult<SecondDate>;
@@ -147,6 +154,7 @@ impl SecondDate {
             SecondDate::from_string_second,
             SecondDate::from_string_day,
             SecondDate::from_utc_string,
+            SecondDate::from_offset_string,
         ];
 
         for func in funcs {
@@ -160,9 +168,10 @@ impl SecondDate {
         )))
     }
 
+    // fractions of seconds, as written by chrono's NaiveDateTime, are truncated
     fn from_string_second(s: &str) -> HdbResult<SecondDate> {
-        let ndt = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
-            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
+        let ndt = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f"))
             .map_err(|e| HdbError::Usage(e.description().to_owned()))?;
         SecondDate::from_ymd_hms(
             ndt.year(),
@@ -194,6 +203,90 @@ impl SecondDate {
             ndt.second(),
         )
     }
+
+    fn from_offset_string(s: &str) -> HdbResult<SecondDate> {
+        SecondDate::try_from(parse_offset_datetime(s)?)
+    }
+}
+
+// Fractions of seconds are truncated.
+impl TryFrom<NaiveDateTime> for SecondDate {
+    type Error = HdbError;
+    fn try_from(ndt: NaiveDateTime) -> HdbResult<SecondDate> {
+        SecondDate::from_ymd_hms(
+            ndt.year(),
+            ndt.month(),
+            ndt.day(),
+            ndt.hour(),
+            ndt.minute(),
+            ndt.second(),
+        )
+    }
+}
+
+impl TryFrom<DateTime<Utc>> for SecondDate {
+    type Error = HdbError;
+    fn try_from(dt: DateTime<Utc>) -> HdbResult<SecondDate> {
+        SecondDate::try_from(dt.naive_utc())
+    }
+}
+
+impl TryFrom<SecondDate> for NaiveDateTime {
+    type Error = HdbError;
+    fn try_from(sd: SecondDate) -> HdbResult<NaiveDateTime> {
+        let (year, month, day, hour, minute, second) = sd.as_ymd_hms();
+        naive_date_time(year, month, day, hour, minute, second, 0)
+    }
+}
+
+impl TryFrom<SecondDate> for DateTime<Utc> {
+    type Error = HdbError;
+    fn try_from(sd: SecondDate) -> HdbResult<DateTime<Utc>> {
+        Ok(DateTime::from_utc(NaiveDateTime::try_from(sd)?, Utc))
+    }
+}
+
+// Fractions of seconds are truncated.
+#[cfg(feature = "time")]
+impl TryFrom<time::PrimitiveDateTime> for SecondDate {
+    type Error = HdbError;
+    fn try_from(pdt: time::PrimitiveDateTime) -> HdbResult<SecondDate> {
+        SecondDate::from_ymd_hms(
+            pdt.year(),
+            u32::from(u8::from(pdt.month())),
+            u32::from(pdt.day()),
+            u32::from(pdt.hour()),
+            u32::from(pdt.minute()),
+            u32::from(pdt.second()),
+        )
+    }
+}
+
+// The value is converted to UTC.
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for SecondDate {
+    type Error = HdbError;
+    fn try_from(odt: time::OffsetDateTime) -> HdbResult<SecondDate> {
+        let utc = odt.to_offset(time::UtcOffset::UTC);
+        SecondDate::try_from(time::PrimitiveDateTime::new(utc.date(), utc.time()))
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<SecondDate> for time::PrimitiveDateTime {
+    type Error = HdbError;
+    fn try_from(sd: SecondDate) -> HdbResult<time::PrimitiveDateTime> {
+        let (year, month, day, hour, minute, second) = sd.as_ymd_hms();
+        primitive_date_time(year, month, day, hour, minute, second, 0)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<SecondDate> for time::OffsetDateTime {
+    type Error = HdbError;
+    fn try_from(sd: SecondDate) -> HdbResult<time::OffsetDateTime> {
+        Ok(time::PrimitiveDateTime::try_from(sd)?.assume_utc())
+    }
 }
 
 fn to_day_number(y: u32, m: u32, d: u32) -> i64 {
@@ -3,8 +3,10 @@ use crate::protocol::parts::type_id::TypeId;
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use chrono::{NaiveTime, Timelike};
+use serde_db::de::ConversionError;
 use serde_derive::Serialize;
 use std::cmp;
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::io;
@@ -22,6 +24,9 @@ const HOUR_FACTOR: u32 = 3_600;
 /// Hours * 60*60 + Minutes * 60 + Seconds  + 1 < 86400.
 ///
 /// When reading, we treat 0 and 1 as "00:00:00".
+///
+/// `TryFrom` converts from and into `chrono::NaiveTime`, and, with feature `time`,
+/// `time::Time`.
 #[derive(Clone, Debug, Serialize)]
 pub struct SecondTime(u32);
 
@@ -75,7 +80,7 @@ impl SecondTime {
 
     /// Parses a `SecondTime` from a String.
     ///
-    /// Note that Chrono types serialize as formatted Strings.
+    /// Note that chrono and time types serialize as formatted Strings.
     /// We parse such (and other) Strings and construct a `SecondTime`.
     pub fn from_date_string(s: &str) -> HdbResult<SecondTime> {
         type FSD = fn(&str) -> HdbResult<SecondTime>;
@@ -93,13 +98,61 @@ impl SecondTime {
         )))
     }
 
+    // chrono's NaiveTime serializes with fractions of seconds, if they are not zero
     fn from_string_second(s: &str) -> HdbResult<SecondTime> {
-        let nt = NaiveTime::parse_from_str(s, "%H:%M:%S")
+        let nt = NaiveTime::parse_from_str(s, "%H:%M:%S%.f")
             .map_err(|e| HdbError::Usage(e.description().to_owned()))?;
         SecondTime::from_hms(nt.hour(), nt.minute(), nt.second())
     }
 }
 
+// Fractions of seconds are truncated.
+impl TryFrom<NaiveTime> for SecondTime {
+    type Error = HdbError;
+    fn try_from(nt: NaiveTime) -> HdbResult<SecondTime> {
+        SecondTime::from_hms(nt.hour(), nt.minute(), nt.second())
+    }
+}
+
+impl TryFrom<SecondTime> for NaiveTime {
+    type Error = HdbError;
+    fn try_from(st: SecondTime) -> HdbResult<NaiveTime> {
+        let (hour, minute, second) = st.as_hms();
+        NaiveTime::from_hms_opt(hour, minute, second)
+            .ok_or_else(|| illegal_time(hour, minute, second))
+    }
+}
+
+// Fractions of seconds are truncated.
+#[cfg(feature = "time")]
+impl TryFrom<time::Time> for SecondTime {
+    type Error = HdbError;
+    fn try_from(time: time::Time) -> HdbResult<SecondTime> {
+        SecondTime::from_hms(
+            u32::from(time.hour()),
+            u32::from(time.minute()),
+            u32::from(time.second()),
+        )
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<SecondTime> for time::Time {
+    type Error = HdbError;
+    fn try_from(st: SecondTime) -> HdbResult<time::Time> {
+        let (hour, minute, second) = st.as_hms();
+        time::Time::from_hms(hour as u8, minute as u8, second as u8)
+            .map_err(|_| illegal_time(hour, minute, second))
+    }
+}
+
+pub(crate) fn illegal_time(hour: u32, minute: u32, second: u32) -> HdbError {
+    HdbError::Conversion(ConversionError::ValueType(format!(
+        "The time {:02}:{:02}:{:02} is not a valid time of day",
+        hour, minute, second
+    )))
+}
+
 pub fn parse_secondtime(nullable: bool, rdr: &mut io::BufRead) -> HdbResult<HdbValue> {
     let i = rdr.read_i32::<LittleEndian>()?;
     if i == NULL_REPRESENTATION {
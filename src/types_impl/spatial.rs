@@ -0,0 +1,753 @@
+use crate::{HdbError, HdbResult};
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use geo_types::{
+    Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon,
+};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+// Geometry type codes of WKB
+const WKB_POINT: u32 = 1;
+const WKB_LINESTRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTIPOINT: u32 = 4;
+const WKB_MULTILINESTRING: u32 = 5;
+const WKB_MULTIPOLYGON: u32 = 6;
+const WKB_GEOMETRYCOLLECTION: u32 = 7;
+
+// Flags of the EWKB type code; ISO WKB uses offsets of 1000 (Z), 2000 (M), 3000 (ZM) instead
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+const EWKB_FLAGS: u32 = EWKB_Z | EWKB_M | EWKB_SRID;
+
+/// A value of the spatial types `ST_GEOMETRY` and `ST_POINT`.
+///
+/// The geometry is represented with the types of the
+/// [geo-types](https://docs.rs/geo-types) crate, together with the optional
+/// spatial reference system id (SRID).
+///
+/// HANA transfers spatial values in the extended well-known binary format (EWKB).
+/// A `Spatial` can be deserialized from `GEOMETRY` and `POINT` values of a resultset,
+/// and serialized into parameters of these types.
+/// It can also be converted from and into EWKB, and from and into
+/// well-known text (WKT), e.g. `POINT(2.5 3)`, optionally with an SRID prefix,
+/// as in `SRID=4326;POINT(2.5 3)`.
+///
+/// Note that geo-types only supports two dimensions; Z and M coordinates are dropped
+/// when a value is decoded. Empty points are represented with NaN coordinates.
+///
+/// Only available with the feature `geo`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+/// # use hdbconnect::types::Spatial;
+/// # fn foo() -> HdbResult<()> {
+/// # let mut connection = Connection::new("".into_connect_params()?)?;
+/// let shapes: Vec<Spatial> = connection.query("select SHAPE from SHAPES")?.try_into()?;
+///
+/// let mut stmt = connection.prepare("insert into SHAPES (SHAPE) values (?)")?;
+/// stmt.add_batch(&("LINESTRING(3 3,5 4,6 3)".parse::<Spatial>()?))?;
+/// stmt.execute_batch()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spatial {
+    geometry: Geometry<f64>,
+    srid: Option<u32>,
+}
+
+impl Spatial {
+    /// Factory method for a `Spatial` from a geo-types geometry.
+    pub fn new<G: Into<Geometry<f64>>>(geometry: G, srid: Option<u32>) -> Spatial {
+        Spatial {
+            geometry: geometry.into(),
+            srid,
+        }
+    }
+
+    /// The geometry.
+    pub fn geometry(&self) -> &Geometry<f64> {
+        &self.geometry
+    }
+
+    /// Converts into the geometry.
+    pub fn into_geometry(self) -> Geometry<f64> {
+        self.geometry
+    }
+
+    /// The spatial reference system id, if one is specified.
+    pub fn srid(&self) -> Option<u32> {
+        self.srid
+    }
+
+    /// Decodes a value in EWKB format (ISO WKB is accepted as well).
+    pub fn from_ewkb(ewkb: &[u8]) -> HdbResult<Spatial> {
+        let mut parser = EwkbParser {
+            rdr: io::Cursor::new(ewkb),
+            big_endian: false,
+            dimensions: 2,
+            srid: None,
+        };
+        let geometry = parser.parse_geometry()?;
+        if parser.rdr.position() as usize != ewkb.len() {
+            return Err(HdbError::Usage(
+                "EWKB value is followed by unexpected data".to_owned(),
+            ));
+        }
+        Ok(Spatial {
+            geometry,
+            srid: parser.srid,
+        })
+    }
+
+    /// Encodes the value in EWKB format, with little endian byte order.
+    ///
+    /// The SRID is included if one is specified.
+    pub fn to_ewkb(&self) -> Vec<u8> {
+        let mut w = Vec::<u8>::new();
+        emit_geometry(&self.geometry, self.srid, &mut w);
+        w
+    }
+
+    /// Parses well-known text, with an optional prefix `SRID=<srid>;`.
+    pub fn from_wkt(wkt: &str) -> HdbResult<Spatial> {
+        let (srid, wkt) = match wkt.find(';') {
+            Some(idx) if wkt[..idx].trim().to_uppercase().starts_with("SRID=") => {
+                let srid = wkt[..idx].trim()[5..].trim();
+                let srid = u32::from_str(srid)
+                    .map_err(|_| HdbError::Usage(format!("invalid SRID \"{}\" in WKT", srid)))?;
+                (Some(srid), &wkt[idx + 1..])
+            }
+            _ => (None, wkt),
+        };
+        let mut parser = WktParser {
+            tokens: tokenize(wkt)?,
+            pos: 0,
+        };
+        let geometry = parser.parse_geometry()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(wkt_error("unexpected text at the end"));
+        }
+        Ok(Spatial { geometry, srid })
+    }
+
+    /// Produces well-known text, without the SRID.
+    pub fn to_wkt(&self) -> String {
+        let mut s = String::new();
+        write_wkt(&self.geometry, &mut s);
+        s
+    }
+}
+
+// Writes the WKT, preceded by `SRID=<srid>;` if an SRID is specified.
+impl fmt::Display for Spatial {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(srid) = self.srid {
+            write!(fmt, "SRID={};", srid)?;
+        }
+        write!(fmt, "{}", self.to_wkt())
+    }
+}
+
+impl FromStr for Spatial {
+    type Err = HdbError;
+    fn from_str(s: &str) -> HdbResult<Spatial> {
+        Spatial::from_wkt(s)
+    }
+}
+
+// Serializes as EWKB, which is what the parameters of spatial types expect.
+impl Serialize for Spatial {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_ewkb())
+    }
+}
+
+// Deserializes from EWKB, and also from WKT.
+impl<'de> Deserialize<'de> for Spatial {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Spatial, D::Error> {
+        deserializer.deserialize_bytes(SpatialVisitor)
+    }
+}
+
+struct SpatialVisitor;
+impl<'de> Visitor<'de> for SpatialVisitor {
+    type Value = Spatial;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "a spatial value in EWKB or WKT format")
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Spatial, E> {
+        Spatial::from_ewkb(value).map_err(E::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Spatial, E> {
+        Spatial::from_wkt(value).map_err(E::custom)
+    }
+}
+
+struct EwkbParser<'a> {
+    rdr: io::Cursor<&'a [u8]>,
+    // byte order and number of coordinates of the current geometry
+    big_endian: bool,
+    dimensions: usize,
+    srid: Option<u32>,
+}
+impl<'a> EwkbParser<'a> {
+    // Nested geometries come with their own header.
+    fn parse_geometry(&mut self) -> HdbResult<Geometry<f64>> {
+        self.big_endian = match self.rdr.read_u8()? {
+            0 => true,
+            1 => false,
+            b => {
+                return Err(HdbError::Usage(format!(
+                    "invalid byte order {} in EWKB value",
+                    b
+                )));
+            }
+        };
+        let type_code = self.read_u32()?;
+        if type_code & EWKB_SRID != 0 {
+            let srid = self.read_u32()?;
+            self.srid.get_or_insert(srid);
+        }
+        let iso_dimensions = (type_code & !EWKB_FLAGS) / 1000;
+        self.dimensions = 2
+            + ((type_code & EWKB_Z != 0) || iso_dimensions == 1 || iso_dimensions == 3) as usize
+            + ((type_code & EWKB_M != 0) || iso_dimensions == 2 || iso_dimensions == 3) as usize;
+
+        Ok(match (type_code & !EWKB_FLAGS) % 1000 {
+            WKB_POINT => Geometry::Point(Point(self.parse_coord()?)),
+            WKB_LINESTRING => Geometry::LineString(self.parse_linestring()?),
+            WKB_POLYGON => Geometry::Polygon(self.parse_polygon()?),
+            WKB_MULTIPOINT => {
+                Geometry::MultiPoint(MultiPoint(self.parse_elements(|g| match g {
+                    Geometry::Point(p) => Some(p),
+                    _ => None,
+                })?))
+            }
+            WKB_MULTILINESTRING => {
+                Geometry::MultiLineString(MultiLineString(self.parse_elements(|g| match g {
+                    Geometry::LineString(ls) => Some(ls),
+                    _ => None,
+                })?))
+            }
+            WKB_MULTIPOLYGON => {
+                Geometry::MultiPolygon(MultiPolygon(self.parse_elements(|g| match g {
+                    Geometry::Polygon(p) => Some(p),
+                    _ => None,
+                })?))
+            }
+            WKB_GEOMETRYCOLLECTION => {
+                Geometry::GeometryCollection(GeometryCollection(self.parse_elements(Some)?))
+            }
+            t => {
+                return Err(HdbError::Usage(format!(
+                    "unsupported geometry type {} in EWKB value",
+                    t
+                )));
+            }
+        })
+    }
+
+    fn parse_elements<T, F>(&mut self, f: F) -> HdbResult<Vec<T>>
+    where
+        F: Fn(Geometry<f64>) -> Option<T>,
+    {
+        let count = self.read_u32()?;
+        let mut elements = Vec::<T>::new();
+        for _ in 0..count {
+            elements.push(f(self.parse_geometry()?).ok_or_else(|| {
+                HdbError::Usage("unexpected element type in EWKB multi geometry".to_owned())
+            })?);
+        }
+        Ok(elements)
+    }
+
+    fn parse_polygon(&mut self) -> HdbResult<Polygon<f64>> {
+        let count = self.read_u32()?;
+        let mut rings = Vec::<LineString<f64>>::new();
+        for _ in 0..count {
+            rings.push(self.parse_linestring()?);
+        }
+        let mut rings = rings.into_iter();
+        let exterior = rings.next().unwrap_or_else(|| LineString(Vec::new()));
+        Ok(Polygon::new(exterior, rings.collect()))
+    }
+
+    fn parse_linestring(&mut self) -> HdbResult<LineString<f64>> {
+        let count = self.read_u32()?;
+        let mut coords = Vec::<Coord<f64>>::new();
+        for _ in 0..count {
+            coords.push(self.parse_coord()?);
+        }
+        Ok(LineString(coords))
+    }
+
+    // Z and M coordinates are skipped
+    fn parse_coord(&mut self) -> HdbResult<Coord<f64>> {
+        let x = self.read_f64()?;
+        let y = self.read_f64()?;
+        for _ in 2..self.dimensions {
+            self.read_f64()?;
+        }
+        Ok(Coord { x, y })
+    }
+
+    fn read_u32(&mut self) -> HdbResult<u32> {
+        Ok(if self.big_endian {
+            self.rdr.read_u32::<BigEndian>()?
+        } else {
+            self.rdr.read_u32::<LittleEndian>()?
+        })
+    }
+
+    fn read_f64(&mut self) -> HdbResult<f64> {
+        Ok(if self.big_endian {
+            self.rdr.read_f64::<BigEndian>()?
+        } else {
+            self.rdr.read_f64::<LittleEndian>()?
+        })
+    }
+}
+
+fn emit_geometry(geometry: &Geometry<f64>, srid: Option<u32>, w: &mut Vec<u8>) {
+    match geometry {
+        Geometry::Point(p) => {
+            emit_header(WKB_POINT, srid, w);
+            emit_coord(p.0, w);
+        }
+        Geometry::Line(line) => {
+            emit_header(WKB_LINESTRING, srid, w);
+            emit_coords(&[line.start, line.end], w);
+        }
+        Geometry::LineString(ls) => {
+            emit_header(WKB_LINESTRING, srid, w);
+            emit_coords(&ls.0, w);
+        }
+        Geometry::Polygon(polygon) => {
+            emit_header(WKB_POLYGON, srid, w);
+            emit_polygon(polygon, w);
+        }
+        Geometry::Rect(rect) => {
+            emit_header(WKB_POLYGON, srid, w);
+            emit_polygon(&rect.to_polygon(), w);
+        }
+        Geometry::Triangle(triangle) => {
+            emit_header(WKB_POLYGON, srid, w);
+            emit_polygon(&triangle.to_polygon(), w);
+        }
+        // the elements of collections are emitted without SRID
+        Geometry::MultiPoint(mp) => {
+            emit_header(WKB_MULTIPOINT, srid, w);
+            emit_count(mp.0.len(), w);
+            for p in &mp.0 {
+                emit_header(WKB_POINT, None, w);
+                emit_coord(p.0, w);
+            }
+        }
+        Geometry::MultiLineString(mls) => {
+            emit_header(WKB_MULTILINESTRING, srid, w);
+            emit_count(mls.0.len(), w);
+            for ls in &mls.0 {
+                emit_header(WKB_LINESTRING, None, w);
+                emit_coords(&ls.0, w);
+            }
+        }
+        Geometry::MultiPolygon(mp) => {
+            emit_header(WKB_MULTIPOLYGON, srid, w);
+            emit_count(mp.0.len(), w);
+            for polygon in &mp.0 {
+                emit_header(WKB_POLYGON, None, w);
+                emit_polygon(polygon, w);
+            }
+        }
+        Geometry::GeometryCollection(gc) => {
+            emit_header(WKB_GEOMETRYCOLLECTION, srid, w);
+            emit_count(gc.0.len(), w);
+            for g in &gc.0 {
+                emit_geometry(g, None, w);
+            }
+        }
+    }
+}
+
+fn emit_header(type_code: u32, srid: Option<u32>, w: &mut Vec<u8>) {
+    w.push(1); // little endian
+    match srid {
+        Some(srid) => {
+            w.extend_from_slice(&(type_code | EWKB_SRID).to_le_bytes());
+            w.extend_from_slice(&srid.to_le_bytes());
+        }
+        None => w.extend_from_slice(&type_code.to_le_bytes()),
+    }
+}
+
+// An empty polygon has no rings.
+fn emit_polygon(polygon: &Polygon<f64>, w: &mut Vec<u8>) {
+    if polygon.exterior().0.is_empty() {
+        emit_count(0, w);
+    } else {
+        emit_count(1 + polygon.interiors().len(), w);
+        emit_coords(&polygon.exterior().0, w);
+        for ring in polygon.interiors() {
+            emit_coords(&ring.0, w);
+        }
+    }
+}
+
+fn emit_coords(coords: &[Coord<f64>], w: &mut Vec<u8>) {
+    emit_count(coords.len(), w);
+    for coord in coords {
+        emit_coord(*coord, w);
+    }
+}
+
+fn emit_coord(coord: Coord<f64>, w: &mut Vec<u8>) {
+    w.extend_from_slice(&coord.x.to_le_bytes());
+    w.extend_from_slice(&coord.y.to_le_bytes());
+}
+
+fn emit_count(count: usize, w: &mut Vec<u8>) {
+    w.extend_from_slice(&(count as u32).to_le_bytes());
+}
+
+fn write_wkt(geometry: &Geometry<f64>, s: &mut String) {
+    match geometry {
+        Geometry::Point(p) if p.x().is_nan() && p.y().is_nan() => s.push_str("POINT EMPTY"),
+        Geometry::Point(p) => {
+            s.push_str("POINT(");
+            write_coord(p.0, s);
+            s.push(')');
+        }
+        Geometry::Line(line) => {
+            s.push_str("LINESTRING");
+            write_coords(&[line.start, line.end], s);
+        }
+        Geometry::LineString(ls) => {
+            s.push_str("LINESTRING");
+            write_coords(&ls.0, s);
+        }
+        Geometry::Polygon(polygon) => {
+            s.push_str("POLYGON");
+            write_polygon(polygon, s);
+        }
+        Geometry::Rect(rect) => {
+            s.push_str("POLYGON");
+            write_polygon(&rect.to_polygon(), s);
+        }
+        Geometry::Triangle(triangle) => {
+            s.push_str("POLYGON");
+            write_polygon(&triangle.to_polygon(), s);
+        }
+        Geometry::MultiPoint(mp) => {
+            s.push_str("MULTIPOINT");
+            write_list(&mp.0, s, |p, s| {
+                s.push('(');
+                write_coord(p.0, s);
+                s.push(')');
+            });
+        }
+        Geometry::MultiLineString(mls) => {
+            s.push_str("MULTILINESTRING");
+            write_list(&mls.0, s, |ls, s| write_coords(&ls.0, s));
+        }
+        Geometry::MultiPolygon(mp) => {
+            s.push_str("MULTIPOLYGON");
+            write_list(&mp.0, s, write_polygon);
+        }
+        Geometry::GeometryCollection(gc) => {
+            s.push_str("GEOMETRYCOLLECTION");
+            write_list(&gc.0, s, write_wkt);
+        }
+    }
+}
+
+fn write_polygon(polygon: &Polygon<f64>, s: &mut String) {
+    if polygon.exterior().0.is_empty() {
+        s.push_str(" EMPTY");
+    } else {
+        s.push('(');
+        write_coords(&polygon.exterior().0, s);
+        for ring in polygon.interiors() {
+            s.push(',');
+            write_coords(&ring.0, s);
+        }
+        s.push(')');
+    }
+}
+
+fn write_coords(coords: &[Coord<f64>], s: &mut String) {
+    write_list(coords, s, |coord, s| write_coord(*coord, s));
+}
+
+// Writes the elements in parentheses, or EMPTY
+fn write_list<T, F>(elements: &[T], s: &mut String, f: F)
+where
+    F: Fn(&T, &mut String),
+{
+    if elements.is_empty() {
+        s.push_str(" EMPTY");
+    } else {
+        s.push('(');
+        for (i, element) in elements.iter().enumerate() {
+            if i > 0 {
+                s.push(',');
+            }
+            f(element, s);
+        }
+        s.push(')');
+    }
+}
+
+fn write_coord(coord: Coord<f64>, s: &mut String) {
+    s.push_str(&format!("{} {}", coord.x, coord.y));
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Number(f64),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+fn tokenize(wkt: &str) -> HdbResult<Vec<Token>> {
+    let mut tokens = Vec::<Token>::new();
+    let mut chars = wkt.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            '(' => tokens.push(Token::LeftParen),
+            ')' => tokens.push(Token::RightParen),
+            ',' => tokens.push(Token::Comma),
+            c if c.is_whitespace() => {}
+            c if c.is_ascii_alphabetic() => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !c.is_ascii_alphabetic() {
+                        break;
+                    }
+                    word.push(c.to_ascii_uppercase());
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+                continue;
+            }
+            _ => {
+                let mut number = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+' || c == 'e') {
+                        break;
+                    }
+                    number.push(c);
+                    chars.next();
+                }
+                if number.is_empty() {
+                    return Err(wkt_error(&format!("unexpected character '{}'", c)));
+                }
+                tokens.push(Token::Number(f64::from_str(&number).map_err(|_| {
+                    wkt_error(&format!("invalid number \"{}\"", number))
+                })?));
+                continue;
+            }
+        }
+        chars.next();
+    }
+    Ok(tokens)
+}
+
+struct WktParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+impl WktParser {
+    fn parse_geometry(&mut self) -> HdbResult<Geometry<f64>> {
+        let geometry_type = match self.tokens.get(self.pos) {
+            Some(Token::Word(word)) => word.clone(),
+            _ => return Err(wkt_error("geometry type expected")),
+        };
+        self.pos += 1;
+        // dimension tags; additional coordinates are skipped
+        if let Some(Token::Word(word)) = self.tokens.get(self.pos) {
+            if word == "Z" || word == "M" || word == "ZM" {
+                self.pos += 1;
+            }
+        }
+
+        Ok(match &geometry_type[..] {
+            "POINT" => Geometry::Point(if self.is_empty() {
+                Point::new(f64::NAN, f64::NAN)
+            } else {
+                self.expect(&Token::LeftParen)?;
+                let coord = self.parse_coord()?;
+                self.expect(&Token::RightParen)?;
+                Point(coord)
+            }),
+            "LINESTRING" => Geometry::LineString(LineString(self.parse_coords()?)),
+            "POLYGON" => Geometry::Polygon(self.parse_polygon()?),
+            // the points may or may not be enclosed in parentheses
+            "MULTIPOINT" => Geometry::MultiPoint(MultiPoint(self.parse_list(|parser| {
+                if parser.tokens.get(parser.pos) == Some(&Token::LeftParen) {
+                    parser.pos += 1;
+                    let coord = parser.parse_coord()?;
+                    parser.expect(&Token::RightParen)?;
+                    Ok(Point(coord))
+                } else {
+                    Ok(Point(parser.parse_coord()?))
+                }
+            })?)),
+            "MULTILINESTRING" => Geometry::MultiLineString(MultiLineString(
+                self.parse_list(|parser| Ok(LineString(parser.parse_coords()?)))?,
+            )),
+            "MULTIPOLYGON" => {
+                Geometry::MultiPolygon(MultiPolygon(self.parse_list(WktParser::parse_polygon)?))
+            }
+            "GEOMETRYCOLLECTION" => Geometry::GeometryCollection(GeometryCollection(
+                self.parse_list(WktParser::parse_geometry)?,
+            )),
+            _ => {
+                return Err(wkt_error(&format!(
+                    "unsupported geometry type {}",
+                    geometry_type
+                )));
+            }
+        })
+    }
+
+    fn parse_polygon(&mut self) -> HdbResult<Polygon<f64>> {
+        let mut rings = self
+            .parse_list(|parser| Ok(LineString(parser.parse_coords()?)))?
+            .into_iter();
+        let exterior = rings.next().unwrap_or_else(|| LineString(Vec::new()));
+        Ok(Polygon::new(exterior, rings.collect()))
+    }
+
+    fn parse_coords(&mut self) -> HdbResult<Vec<Coord<f64>>> {
+        self.parse_list(WktParser::parse_coord)
+    }
+
+    // Parses EMPTY, or a comma separated list in parentheses
+    fn parse_list<T, F>(&mut self, f: F) -> HdbResult<Vec<T>>
+    where
+        F: Fn(&mut WktParser) -> HdbResult<T>,
+    {
+        let mut elements = Vec::<T>::new();
+        if self.is_empty() {
+            return Ok(elements);
+        }
+        self.expect(&Token::LeftParen)?;
+        loop {
+            elements.push(f(self)?);
+            match self.tokens.get(self.pos) {
+                Some(Token::Comma) => self.pos += 1,
+                _ => break,
+            }
+        }
+        self.expect(&Token::RightParen)?;
+        Ok(elements)
+    }
+
+    // Z and M coordinates are skipped
+    fn parse_coord(&mut self) -> HdbResult<Coord<f64>> {
+        let x = self.parse_number()?;
+        let y = self.parse_number()?;
+        while let Some(Token::Number(_)) = self.tokens.get(self.pos) {
+            self.pos += 1;
+        }
+        Ok(Coord { x, y })
+    }
+
+    fn parse_number(&mut self) -> HdbResult<f64> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Number(n)) => {
+                self.pos += 1;
+                Ok(*n)
+            }
+            _ => Err(wkt_error("coordinate expected")),
+        }
+    }
+
+    fn is_empty(&mut self) -> bool {
+        if self.tokens.get(self.pos) == Some(&Token::Word("EMPTY".to_string())) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> HdbResult<()> {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(wkt_error(&format!("{:?} expected", token)))
+        }
+    }
+}
+
+fn wkt_error(s: &str) -> HdbError {
+    HdbError::Usage(format!("invalid WKT: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Spatial;
+    use geo_types::{Geometry, LineString, Point, Polygon};
+
+    #[test]
+    fn test_spatial() {
+        // POINT(2.5 3) with SRID 4326, as sent by the server
+        let ewkb = [
+            1, 1, 0, 0, 0x20, 0xE6, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0x40, 0, 0, 0, 0, 0, 0, 8,
+            0x40,
+        ];
+        let point = Spatial::from_ewkb(&ewkb).unwrap();
+        assert_eq!(point, Spatial::new(Point::new(2.5, 3.0), Some(4326)));
+        assert_eq!(point.to_ewkb(), ewkb.to_vec());
+        assert_eq!(point.to_string(), "SRID=4326;POINT(2.5 3)");
+        assert_eq!(point, "srid=4326; Point (2.5 3.0)".parse().unwrap());
+
+        let wkts = [
+            "LINESTRING(3 3,5 4,6 3)",
+            "POLYGON((1 1,1 6,6 6,6 1,1 1),(2 2,2 3,3 3,2 2))",
+            "POLYGON EMPTY",
+            "MULTIPOINT((0 1),(2 2),(5 3))",
+            "MULTILINESTRING((3 3,5 4),(7 5,9 7))",
+            "MULTIPOLYGON(((4 5,5 3,6 5,4 5)),((1 3,1 4,5 4,1 3)))",
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING EMPTY)",
+        ];
+        for wkt in &wkts {
+            let spatial = Spatial::from_wkt(wkt).unwrap();
+            assert_eq!(spatial.to_wkt(), *wkt);
+            assert_eq!(Spatial::from_ewkb(&spatial.to_ewkb()).unwrap(), spatial);
+        }
+        assert_eq!(
+            Spatial::from_wkt("MULTIPOINT(0 1, 2 2, 5 3)").unwrap(),
+            Spatial::from_wkt(wkts[3]).unwrap()
+        );
+        assert_eq!(
+            Spatial::from_wkt("LINESTRING Z (3 3 1, 5 4 1)")
+                .unwrap()
+                .into_geometry(),
+            Geometry::LineString(LineString::from(vec![(3.0, 3.0), (5.0, 4.0)]))
+        );
+        assert_eq!(
+            Spatial::from_wkt(wkts[2]).unwrap().into_geometry(),
+            Geometry::Polygon(Polygon::new(LineString(vec![]), vec![]))
+        );
+        assert!(Spatial::from_wkt("POINT(1)").is_err());
+        assert!(Spatial::from_wkt("CIRCLE(1 2)").is_err());
+        assert!(Spatial::from_ewkb(&ewkb[..20]).is_err());
+    }
+}
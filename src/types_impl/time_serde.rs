@@ -0,0 +1,97 @@
+//! Helpers for deserializing date and time values into the types of the `time` crate.
+//!
+//! HANA's date and time values are provided to serde as strings (see e.g. the `Display`
+//! implementation of [`LongDate`](../types/struct.LongDate.html)),
+//! which are not in the format that the `time` types expect.
+//! Use the modules of this module with `#[serde(with = ...)]` on the respective fields:
+//!
+//! ```rust,ignore
+//! #[derive(Deserialize)]
+//! struct Data {
+//!     #[serde(with = "hdbconnect::time_serde::primitive_date_time")]
+//!     ts: time::PrimitiveDateTime,
+//!     #[serde(with = "hdbconnect::time_serde::date::option")]
+//!     day: Option<time::Date>,
+//! }
+//! ```
+//!
+//! Serialization is delegated to the `time` types, whose output is accepted as parameter value.
+
+macro_rules! time_serde_module {
+    ($(#[$attr:meta])* $module:ident, $time_type:ty, $hdb_type:ty) => {
+        $(#[$attr])*
+        pub mod $module {
+            use serde::de::Error;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+            use std::convert::TryFrom;
+
+            /// Serializes the value with its own `Serialize` implementation.
+            pub fn serialize<S: Serializer>(
+                value: &$time_type,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                value.serialize(serializer)
+            }
+
+            /// Deserializes the value from its string representation.
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$time_type, D::Error> {
+                from_str(&String::deserialize(deserializer)?)
+            }
+
+            fn from_str<E: Error>(s: &str) -> Result<$time_type, E> {
+                let value = <$hdb_type>::from_date_string(s).map_err(E::custom)?;
+                <$time_type>::try_from(value).map_err(E::custom)
+            }
+
+            /// The same for optional values.
+            pub mod option {
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+                /// Serializes the value with its own `Serialize` implementation.
+                pub fn serialize<S: Serializer>(
+                    value: &Option<$time_type>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    value.serialize(serializer)
+                }
+
+                /// Deserializes the value from its string representation.
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Option<$time_type>, D::Error> {
+                    match Option::<String>::deserialize(deserializer)? {
+                        Some(s) => super::from_str(&s).map(Some),
+                        None => Ok(None),
+                    }
+                }
+            }
+        }
+    };
+}
+
+time_serde_module!(
+    /// For `time::PrimitiveDateTime`.
+    primitive_date_time,
+    time::PrimitiveDateTime,
+    crate::types::LongDate
+);
+time_serde_module!(
+    /// For `time::OffsetDateTime`; the values from the database are interpreted as UTC.
+    offset_date_time,
+    time::OffsetDateTime,
+    crate::types::LongDate
+);
+time_serde_module!(
+    /// For `time::Date`.
+    date,
+    time::Date,
+    crate::types::DayDate
+);
+time_serde_module!(
+    /// For `time::Time`.
+    time,
+    time::Time,
+    crate::types::SecondTime
+);
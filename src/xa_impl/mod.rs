@@ -26,6 +26,8 @@ impl From<HdbError> for RmError {
             | HdbError::DbIssue(s) => RmError::new(ErrorCode::RmError, s),
             HdbError::Impl(s) => RmError::new(ErrorCode::RmError, s.to_string()),
             HdbError::Io(e) => RmError::new(ErrorCode::RmError, e.description().to_string()),
+            HdbError::Timeout(e) => RmError::new(ErrorCode::RmError, e.to_string()),
+            HdbError::TransactionLost(e) => RmError::new(ErrorCode::RmFailure, e.to_string()),
             HdbError::Serialization(e) => {
                 RmError::new(ErrorCode::RmError, e.description().to_string())
             }
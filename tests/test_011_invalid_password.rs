@@ -58,7 +58,7 @@ pub fn test_011_invalid_password() -> HdbResult<()> {
             test_utils::get_wrong_connect_params(Some(&user), Some("Doebcd1234")).unwrap();
 
         assert_eq!(conn_params.dbuser(), &user);
-        assert_eq!(conn_params.password().unsecure(), b"Doebcd1234");
+        assert_eq!(conn_params.password().unwrap().unsecure(), b"Doebcd1234");
 
         let mut doedel_conn = Connection::new(conn_params)?;
         debug!("{} is connected", user);
@@ -1,5 +1,7 @@
 mod test_utils;
 
+#[cfg(feature = "tls")]
+use hdbconnect::{ConnectParams, ServerCerts};
 use hdbconnect::{Connection, HdbResult, IntoConnectParams};
 use log::{debug, info};
 use serde_derive::{Deserialize, Serialize};
@@ -29,6 +31,56 @@ fn test_012_tls() -> HdbResult<()> {
     Ok(())
 }
 
+// cargo test --features tls --test test_012_tls -- --nocapture
+#[cfg(feature = "tls")]
+#[test]
+fn test_012_tls_insecure() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    info!("test tls without verification of the server certificate");
+
+    let std_params = test_utils::get_std_connect_params()?;
+    let port = std_params.addr()[std_params.host().len() + 1..]
+        .parse::<u16>()
+        .unwrap();
+    let params = ConnectParams::builder()
+        .hostname(std_params.host())
+        .port(port)
+        .dbuser(std_params.dbuser())
+        .password(String::from_utf8_lossy(
+            std_params.password().unwrap().unsecure(),
+        ))
+        .tls_with(ServerCerts::None)
+        .build()?;
+
+    let mut connection = Connection::new(params)?;
+    select_version_and_user(&mut connection)?;
+    Ok(())
+}
+
+// Requires a client certificate in ./.private/client_cert, whose subject is mapped
+// to the standard user of the tests.
+// cargo test --features tls --test test_012_tls -- --nocapture
+#[cfg(feature = "tls")]
+#[test]
+fn test_012_tls_x509() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    info!("test logon with client certificate");
+
+    // replace the credentials with a client certificate
+    let url = test_utils::get_std_connect_url()?;
+    let host_and_port = &url[url.rfind('@').unwrap() + 1..];
+    let url = format!(
+        "hdbsqls://{}?tls_certificate_dir=.%2F.private\
+         &tls_client_cert_dir=.%2F.private%2Fclient_cert",
+        host_and_port
+    );
+    debug!("url = {}", url);
+
+    let mut connection = Connection::new(url.into_connect_params()?)?;
+    select_version_and_user(&mut connection)?;
+    Ok(())
+}
+
 fn select_version_and_user(connection: &mut Connection) -> HdbResult<()> {
     #[derive(Serialize, Deserialize, Debug)]
     struct VersionAndUser {
@@ -0,0 +1,73 @@
+mod test_utils;
+
+use hdbconnect::{Connection, HdbError, HdbResult, IntoConnectParams};
+use log::info;
+use std::thread;
+use std::time::Duration;
+
+// cargo test --test test_013_reconnect -- --nocapture
+#[test]
+pub fn test_013_reconnect() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let url = format!(
+        "{}?reconnect=true",
+        test_utils::get_std_connect_url()?.trim()
+    );
+    let mut connection = Connection::new(url.into_connect_params()?)?;
+    let mut sys_connection = test_utils::get_system_connection()?;
+
+    reconnect_transparently(&mut connection, &mut sys_connection)?;
+    lose_transaction(&mut connection, &mut sys_connection)?;
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
+
+fn kill_session(connection: &Connection, sys_connection: &mut Connection) -> HdbResult<()> {
+    let id = connection.id()?;
+    info!("disconnecting session {}", id);
+    sys_connection.exec(format!("ALTER SYSTEM DISCONNECT SESSION '{}'", id))?;
+    thread::sleep(Duration::from_millis(200));
+    Ok(())
+}
+
+fn reconnect_transparently(
+    connection: &mut Connection,
+    sys_connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("the connection and the prepared statement survive a lost session");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_RECONNECT"]);
+    connection.exec("create table TEST_RECONNECT (f1 INT primary key)")?;
+    let mut stmt = connection.prepare("insert into TEST_RECONNECT (f1) values(?)")?;
+    stmt.execute(&1)?;
+
+    let old_id = connection.id()?;
+    kill_session(connection, sys_connection)?;
+
+    stmt.execute(&2)?;
+    assert_ne!(old_id, connection.id()?);
+    let count: usize = connection
+        .query("select count(*) from TEST_RECONNECT")?
+        .try_into()?;
+    assert_eq!(count, 2);
+    Ok(())
+}
+
+fn lose_transaction(connection: &mut Connection, sys_connection: &mut Connection) -> HdbResult<()> {
+    info!("a lost write transaction is reported");
+    connection.set_auto_commit(false)?;
+    connection.dml("insert into TEST_RECONNECT (f1) values(3)")?;
+    kill_session(connection, sys_connection)?;
+
+    match connection.dml("insert into TEST_RECONNECT (f1) values(4)") {
+        Err(HdbError::TransactionLost(_)) => {}
+        other => panic!("expected TransactionLost, got {:?}", other),
+    }
+
+    connection.set_auto_commit(true)?;
+    let count: usize = connection
+        .query("select count(*) from TEST_RECONNECT")?
+        .try_into()?;
+    assert_eq!(count, 2);
+    Ok(())
+}
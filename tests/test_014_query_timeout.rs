@@ -0,0 +1,54 @@
+mod test_utils;
+
+use hdbconnect::{Connection, HdbResult};
+use log::info;
+use std::thread;
+use std::time::Duration;
+
+// a query that runs for a long time
+const EXPENSIVE_QUERY: &str = "select count(*) from objects a, objects b, objects c";
+
+// cargo test --test test_014_query_timeout -- --nocapture
+#[test]
+pub fn test_014_query_timeout() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+
+    query_timeout(&mut connection)?;
+    cancel(&mut connection)?;
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
+
+fn query_timeout(connection: &mut Connection) -> HdbResult<()> {
+    info!("an expensive query is aborted by the query timeout of the connection");
+    connection.set_query_timeout(Some(Duration::from_secs(1)))?;
+    assert_eq!(connection.query_timeout()?, Some(Duration::from_secs(1)));
+    assert!(connection.query(EXPENSIVE_QUERY).is_err());
+
+    info!("a prepared statement can override the query timeout");
+    let mut stmt = connection.prepare("select count(*) from dummy where 1 = ?")?;
+    stmt.set_query_timeout(Some(Duration::from_secs(0)));
+    let count: usize = stmt.execute(&1)?.into_resultset()?.try_into()?;
+    assert_eq!(count, 1);
+
+    connection.set_query_timeout(None)?;
+    Ok(())
+}
+
+fn cancel(connection: &mut Connection) -> HdbResult<()> {
+    info!("an expensive query is cancelled from another thread");
+    let cancel_handle = connection.cancel_handle()?;
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_secs(1));
+        cancel_handle.cancel()
+    });
+    assert!(connection.query(EXPENSIVE_QUERY).is_err());
+    canceller.join().unwrap()?;
+
+    info!("the connection is still usable");
+    let count: usize = connection.query("select count(*) from dummy")?.try_into()?;
+    assert_eq!(count, 1);
+    Ok(())
+}
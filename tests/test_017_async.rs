@@ -0,0 +1,63 @@
+#![cfg(feature = "async")]
+
+mod test_utils;
+
+use futures::StreamExt;
+use hdbconnect::{AsyncConnection, HdbResult};
+use log::info;
+use serde_derive::Deserialize;
+
+// cargo test --features async --test test_017_async -- --nocapture
+#[tokio::test(flavor = "multi_thread")]
+async fn test_017_async() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let mut connection = AsyncConnection::new(test_utils::get_std_connect_params()?).await?;
+
+    info!("prepare the db table");
+    let _ = connection.exec("drop table TEST_ASYNC").await;
+    connection
+        .exec("create table TEST_ASYNC (f1 INT primary key, f2 NVARCHAR(20))")
+        .await?;
+
+    info!("insert with a prepared statement");
+    let mut stmt = connection
+        .prepare("insert into TEST_ASYNC (f1, f2) values(?, ?)")
+        .await?;
+    for i in 0..50 {
+        stmt.add_batch(&(i, format!("row {}", i))).await?;
+    }
+    stmt.execute_batch().await?;
+
+    info!("stream the rows with a small fetch size, so that several fetches are necessary");
+    connection.set_fetch_size(7).await?;
+    let mut rs = connection
+        .query("select f1, f2 from TEST_ASYNC order by f1")
+        .await?;
+    let mut count = 0;
+    while let Some(row) = rs.next().await {
+        let mut row = row?;
+        let f1: i32 = row.next_value().unwrap().try_into()?;
+        assert_eq!(f1, count);
+        count += 1;
+    }
+    assert_eq!(count, 50);
+
+    info!("deserialize a complete resultset");
+    #[derive(Deserialize)]
+    struct TestData {
+        #[serde(rename = "F1")]
+        f1: i32,
+        #[serde(rename = "F2")]
+        f2: String,
+    }
+    let data: Vec<TestData> = connection
+        .query("select f1, f2 from TEST_ASYNC order by f1")
+        .await?
+        .try_into()
+        .await?;
+    assert_eq!(data.len(), 50);
+    assert_eq!(data[49].f1, 49);
+    assert_eq!(data[49].f2, "row 49");
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+mod test_utils;
+
+use hdbconnect::{Connection, HdbResult, ResultSet};
+use log::info;
+
+// cargo test --test test_018_scrollable_cursor -- --nocapture
+#[test]
+pub fn test_018_scrollable_cursor() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+
+    prepare_table(&mut connection)?;
+    scroll_with_query(&mut connection)?;
+    scroll_with_prepared_statement(&mut connection)?;
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
+
+fn prepare_table(connection: &mut Connection) -> HdbResult<()> {
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_SCROLLABLE"]);
+    connection.exec("create table TEST_SCROLLABLE (f1 INT primary key)")?;
+    let mut stmt = connection.prepare("insert into TEST_SCROLLABLE (f1) values(?)")?;
+    for i in 1..=100 {
+        stmt.add_batch(&i)?;
+    }
+    stmt.execute_batch()?;
+    connection.set_fetch_size(10)?;
+    Ok(())
+}
+
+fn next_f1(rs: &mut ResultSet) -> HdbResult<i32> {
+    let mut row = rs.next_row()?.expect("row expected");
+    row.next_value().unwrap().try_into()
+}
+
+fn scroll_with_query(connection: &mut Connection) -> HdbResult<()> {
+    info!("scroll through a resultset from a direct query");
+    let mut rs = connection.query_scrollable("select f1 from TEST_SCROLLABLE order by f1")?;
+    assert_eq!(next_f1(&mut rs)?, 1);
+
+    rs.fetch_absolute(50)?;
+    assert_eq!(next_f1(&mut rs)?, 50);
+    assert_eq!(next_f1(&mut rs)?, 51);
+
+    rs.fetch_last()?;
+    assert_eq!(next_f1(&mut rs)?, 100);
+    assert!(rs.next_row()?.is_none());
+
+    rs.fetch_first()?;
+    assert_eq!(next_f1(&mut rs)?, 1);
+
+    rs.fetch_absolute(-2)?;
+    assert_eq!(next_f1(&mut rs)?, 99);
+    Ok(())
+}
+
+fn scroll_with_prepared_statement(connection: &mut Connection) -> HdbResult<()> {
+    info!("scroll through a resultset from a prepared statement");
+    let mut stmt = connection.prepare("select f1 from TEST_SCROLLABLE where f1 > ? order by f1")?;
+    stmt.set_scrollable_cursor(true);
+    let mut rs = stmt.execute(&20)?.into_resultset()?;
+    rs.fetch_absolute(10)?;
+    assert_eq!(next_f1(&mut rs)?, 30);
+
+    info!("a non-scrollable resultset refuses scrolling");
+    let mut rs = connection.query("select f1 from TEST_SCROLLABLE order by f1")?;
+    assert!(rs.fetch_first().is_err());
+    Ok(())
+}
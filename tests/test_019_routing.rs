@@ -0,0 +1,84 @@
+#![cfg(feature = "alpha_routing")]
+
+mod test_utils;
+
+use hdbconnect::{Connection, HdbResult};
+use log::info;
+
+// cargo test --features alpha_routing --test test_019_routing -- --nocapture
+//
+// On a single-host system no routing happens, but all statements must work the same way.
+#[test]
+pub fn test_019_routing() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+
+    prepare_table(&mut connection)?;
+    execute_routed(&mut connection)?;
+    execute_in_transaction(&mut connection)?;
+    execute_on_hash_partitions(&mut connection)?;
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
+
+fn prepare_table(connection: &mut Connection) -> HdbResult<()> {
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_ROUTING"]);
+    connection.exec("create table TEST_ROUTING (f1 INT primary key, f2 NVARCHAR(20))")?;
+    Ok(())
+}
+
+fn execute_routed(connection: &mut Connection) -> HdbResult<()> {
+    info!("insert and select with auto-commit, i.e. with routing");
+    let mut stmt = connection.prepare("insert into TEST_ROUTING (f1, f2) values(?, ?)")?;
+    info!("table location: {:?}", stmt.table_location());
+    for i in 1..=10 {
+        stmt.add_batch(&(i, format!("value {}", i)))?;
+    }
+    stmt.execute_batch()?;
+
+    let mut stmt = connection.prepare("select count(*) from TEST_ROUTING where f1 > ?")?;
+    let count: usize = stmt.execute(&5)?.into_resultset()?.try_into()?;
+    assert_eq!(count, 5);
+    Ok(())
+}
+
+fn execute_in_transaction(connection: &mut Connection) -> HdbResult<()> {
+    info!("insert without auto-commit, i.e. on the anchor connection, and rollback");
+    let mut stmt = connection.prepare("insert into TEST_ROUTING (f1, f2) values(?, ?)")?;
+    connection.set_auto_commit(false)?;
+    stmt.execute(&(11, "value 11"))?;
+    connection.rollback()?;
+    connection.set_auto_commit(true)?;
+
+    let count: usize = connection
+        .query("select count(*) from TEST_ROUTING")?
+        .try_into()?;
+    assert_eq!(count, 10);
+    Ok(())
+}
+
+fn execute_on_hash_partitions(connection: &mut Connection) -> HdbResult<()> {
+    info!("insert and select on a hash partitioned table, routed by its table location");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_ROUTING_HASH"]);
+    connection.exec(
+        "create table TEST_ROUTING_HASH (f1 INT primary key, f2 NVARCHAR(20)) \
+         partition by hash (f1) partitions 3",
+    )?;
+
+    let mut stmt = connection.prepare("insert into TEST_ROUTING_HASH (f1, f2) values(?, ?)")?;
+    for i in 1..=10 {
+        stmt.execute(&(i, format!("value {}", i)))?;
+    }
+    for i in 11..=20 {
+        stmt.add_batch(&(i, format!("value {}", i)))?;
+    }
+    stmt.execute_batch()?;
+
+    let mut stmt = connection.prepare("select f2 from TEST_ROUTING_HASH where f1 = ?")?;
+    for i in 1..=20 {
+        let f2: String = stmt.execute(&i)?.into_resultset()?.try_into()?;
+        assert_eq!(f2, format!("value {}", i));
+    }
+    Ok(())
+}
@@ -11,6 +11,8 @@ pub fn test_021_longdate() -> HdbResult<()> {
     let mut connection = test_utils::get_authenticated_connection()?;
 
     test_longdate(&mut loghandle, &mut connection)?;
+    #[cfg(feature = "time")]
+    test_chrono_and_time(&mut loghandle, &mut connection)?;
 
     info!("{} calls to DB were executed", connection.get_call_count()?);
     Ok(())
@@ -141,3 +143,91 @@ fn test_longdate(
 
     Ok(())
 }
+
+// Test that chrono and time values can be bound and read back side by side
+#[cfg(feature = "time")]
+fn test_chrono_and_time(
+    _loghandle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    use serde_derive::Deserialize;
+
+    info!("bind and read back chrono and time values");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_CHRONO_AND_TIME"]);
+    connection.multiple_statements(vec![
+        "create table TEST_CHRONO_AND_TIME (number INT primary key, \
+         ld LONGDATE, sd SECONDDATE, dd DAYDATE, st SECONDTIME)",
+    ])?;
+
+    let chrono_ndt = NaiveDate::from_ymd(2012, 2, 29).and_hms_nano(23, 59, 58, 123_456_700);
+    let time_date = time::Date::from_calendar_date(2012, time::Month::February, 29).unwrap();
+    let time_pdt = time_date.with_hms_nano(23, 59, 58, 123_456_700).unwrap();
+    let time_odt = time_pdt.assume_offset(time::UtcOffset::from_hms(2, 0, 0).unwrap());
+
+    let mut insert_stmt = connection.prepare(
+        "insert into TEST_CHRONO_AND_TIME (number, ld, sd, dd, st) values(?, ?, ?, ?, ?)",
+    )?;
+    insert_stmt.add_batch(&(
+        1,
+        chrono_ndt,
+        chrono_ndt,
+        chrono_ndt.date(),
+        chrono_ndt.time(),
+    ))?;
+    insert_stmt.add_batch(&(2, time_pdt, time_pdt, time_date, time_pdt.time()))?;
+    insert_stmt.add_batch(&(3, time_odt, time_odt, time_date, time_pdt.time()))?;
+    insert_stmt.execute_batch()?;
+
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct ChronoRow {
+        LD: NaiveDateTime,
+        SD: NaiveDateTime,
+        DD: NaiveDate,
+        ST: chrono::NaiveTime,
+    }
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct TimeRow {
+        #[serde(with = "hdbconnect::time_serde::primitive_date_time")]
+        LD: time::PrimitiveDateTime,
+        #[serde(with = "hdbconnect::time_serde::primitive_date_time")]
+        SD: time::PrimitiveDateTime,
+        #[serde(with = "hdbconnect::time_serde::date")]
+        DD: time::Date,
+        #[serde(with = "hdbconnect::time_serde::time")]
+        ST: time::Time,
+    }
+
+    let q = "select ld, sd, dd, st from TEST_CHRONO_AND_TIME where number < 3 order by number";
+    let chrono_rows: Vec<ChronoRow> = connection.query(q)?.try_into()?;
+    let time_rows: Vec<TimeRow> = connection.query(q)?.try_into()?;
+    assert_eq!(chrono_rows.len(), 2);
+    assert_eq!(time_rows.len(), 2);
+    for row in chrono_rows {
+        assert_eq!(row.LD, chrono_ndt);
+        assert_eq!(row.SD, NaiveDate::from_ymd(2012, 2, 29).and_hms(23, 59, 58));
+        assert_eq!(row.DD, chrono_ndt.date());
+        assert_eq!(row.ST, chrono::NaiveTime::from_hms(23, 59, 58));
+    }
+    for row in time_rows {
+        assert_eq!(row.LD, time_pdt);
+        assert_eq!(row.SD, time_date.with_hms(23, 59, 58).unwrap());
+        assert_eq!(row.DD, time_date);
+        assert_eq!(row.ST, time::Time::from_hms(23, 59, 58).unwrap());
+    }
+
+    info!("the offset of an OffsetDateTime is applied when binding it");
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct OffsetRow {
+        #[serde(with = "hdbconnect::time_serde::offset_date_time")]
+        LD: time::OffsetDateTime,
+    }
+    let row: OffsetRow = connection
+        .query("select ld from TEST_CHRONO_AND_TIME where number = 3")?
+        .try_into()?;
+    assert_eq!(row.LD, time_odt);
+
+    Ok(())
+}
@@ -13,8 +13,8 @@ fn test_025_decimals() -> HdbResult<()> {
     let mut log_handle = test_utils::init_logger();
     let mut connection = test_utils::get_authenticated_connection()?;
 
-    match connection.data_format_version_2()? {
-        Some(version) if version > 7 => {
+    match connection.data_format_version()? {
+        version if version > 7 => {
             info!("=== run test for FIXED8 ===");
             test_025_decimals_impl(TS::FIXED8, &mut log_handle, &mut connection)?;
 
@@ -104,7 +104,7 @@ fn test_025_decimals_impl(
         if let HdbValue::DECIMAL(ref bd) = &row[1] {
             assert_eq!(format!("{}", &row[0]), format!("{}", bd));
         } else {
-            assert!(false, "Unexpected value type");
+            panic!("Unexpected value type");
         }
     }
 
@@ -147,5 +147,56 @@ fn test_025_decimals_impl(
         .try_into()?;
     assert_eq!(mydata, 481);
 
+    #[cfg(feature = "rust_decimal")]
+    test_rust_decimal(connection)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "rust_decimal")]
+fn test_rust_decimal(connection: &mut Connection) -> HdbResult<()> {
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    info!("Write and read rust_decimal::Decimal");
+    let mut insert_stmt = connection.prepare("insert into TEST_DECIMALS (F1, F2) values(?, ?)")?;
+    insert_stmt.add_batch(&("12.34560", Decimal::from_str("12.3456").unwrap()))?;
+    insert_stmt.execute_batch()?;
+
+    let mydata: Decimal = connection
+        .query("select F2 from TEST_DECIMALS where F1 = '12.34560'")?
+        .try_into()?;
+    assert_eq!(mydata, Decimal::from_str("12.3456").unwrap());
+    assert_eq!(mydata.to_string(), "12.34560");
+
+    let result: Vec<(String, Decimal)> = connection
+        .query("select F1, F2 from TEST_DECIMALS")?
+        .try_into()?;
+    for (f1, f2) in result {
+        assert_eq!(f1, f2.to_string());
+    }
+
+    info!("Parse decimals directly into rust_decimal::Decimal");
+    let url = test_utils::get_std_connect_url()?;
+    let url = url.trim();
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let mut rd_connection = Connection::new(hdbconnect::IntoConnectParams::into_connect_params(
+        format!("{}{}rust_decimal=1", url, separator),
+    )?)?;
+    let resultset = rd_connection.query("select f1, f2 from TEST_DECIMALS order by f2")?;
+    for row in resultset {
+        let row = row?;
+        if let HdbValue::RUST_DECIMAL(ref dec) = &row[1] {
+            assert_eq!(format!("{}", &row[0]), format!("{}", dec));
+        } else {
+            panic!("Unexpected value type");
+        }
+    }
+    let result: Vec<(String, Decimal)> = rd_connection
+        .query("select F1, F2 from TEST_DECIMALS")?
+        .try_into()?;
+    for (f1, f2) in result {
+        assert_eq!(f1, f2.to_string());
+    }
     Ok(())
 }
@@ -0,0 +1,41 @@
+#![cfg(feature = "compression")]
+
+mod test_utils;
+
+use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+use log::info;
+
+// cargo test --features compression --test test_029_compression -- --nocapture
+#[test]
+pub fn test_029_compression() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+
+    let mut url = test_utils::get_std_connect_url()?.trim().to_string();
+    url.push_str(if url.contains('?') { "&" } else { "?" });
+    url.push_str("compression=true");
+    let conn_params = url.into_connect_params()?;
+    assert!(conn_params.compression());
+    let mut connection = Connection::new(conn_params)?;
+
+    info!("prepare the db table");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_COMPRESSION"]);
+    connection.exec("create table TEST_COMPRESSION (f1 INT primary key, f2 NVARCHAR(5000))")?;
+
+    info!("insert a batch that is large enough to be sent compressed");
+    let text = "compressible text ".repeat(250);
+    let mut stmt = connection.prepare("insert into TEST_COMPRESSION (f1, f2) values(?, ?)")?;
+    for i in 0..100 {
+        stmt.add_batch(&(i, &text))?;
+    }
+    stmt.execute_batch()?;
+
+    info!("read a wide resultset, which the server can send compressed");
+    let rows: Vec<(i32, String)> = connection
+        .query("select f1, f2 from TEST_COMPRESSION order by f1")?
+        .try_into()?;
+    assert_eq!(rows.len(), 100);
+    assert!(rows.iter().all(|(_, f2)| *f2 == text));
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
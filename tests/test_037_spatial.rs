@@ -12,6 +12,8 @@ fn test_046_spatial() -> HdbResult<()> {
 
     test_geometries(&mut loghandle, &mut connection)?;
     test_points(&mut loghandle, &mut connection)?;
+    #[cfg(feature = "geo")]
+    test_spatial_values(&mut loghandle, &mut connection)?;
 
     info!("{} calls to DB were executed", connection.get_call_count()?);
     Ok(())
@@ -124,3 +126,37 @@ fn test_points(
     // assert_eq!(count, 3);
     Ok(())
 }
+
+#[cfg(feature = "geo")]
+fn test_spatial_values(
+    _loghandle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    use hdbconnect::types::Spatial;
+    info!("write and read geometries and WKT (feature geo)");
+
+    connection.multiple_statements_ignore_err(vec!["drop table SpatialValues"]);
+    connection.multiple_statements(vec![
+        "CREATE COLUMN TABLE SpatialValues(ID integer, SHAPE ST_GEOMETRY)",
+    ])?;
+
+    debug!("insert via parameters, as WKT and as Spatial");
+    let mut stmt = connection.prepare("insert into SpatialValues VALUES(?,?)")?;
+    stmt.add_batch(&(1, "POINT(2.5 3)"))?;
+    stmt.add_batch(&(2, "LINESTRING(3 3,5 4,6 3)"))?;
+    stmt.add_batch(&(3, Spatial::from_wkt("POLYGON((1 1,1 6,6 6,6 1,1 1))")?))?;
+    stmt.execute_batch()?;
+
+    debug!("select and deserialize into Spatial and into WKT");
+    let shapes: Vec<Spatial> = connection
+        .query("select SHAPE from SpatialValues order by ID")?
+        .try_into()?;
+    assert_eq!(shapes[0], Spatial::from_wkt("POINT(2.5 3)")?);
+    assert_eq!(shapes[2].to_wkt(), "POLYGON((1 1,1 6,6 6,6 1,1 1))");
+
+    let wkts: Vec<String> = connection
+        .query("select SHAPE from SpatialValues order by ID")?
+        .try_into()?;
+    assert_eq!(wkts[1], "LINESTRING(3 3,5 4,6 3)");
+    Ok(())
+}
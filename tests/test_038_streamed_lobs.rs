@@ -0,0 +1,92 @@
+mod test_utils;
+
+use hdbconnect::{Connection, HdbResult, HdbValue};
+use log::info;
+use rand::{thread_rng, RngCore};
+use serde_bytes::ByteBuf;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+// cargo test --test test_038_streamed_lobs -- --nocapture
+#[test]
+pub fn test_038_streamed_lobs() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+
+    test_streamed_blob(&mut connection)?;
+    test_streamed_nclob(&mut connection)?;
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
+
+fn test_streamed_blob(connection: &mut Connection) -> HdbResult<()> {
+    info!("stream a 5MB BLOB into the database, in chunks of 100kB");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_STREAMED_BLOBS"]);
+    connection.exec("create table TEST_STREAMED_BLOBS (id INT, bindata BLOB)")?;
+    connection.set_lob_write_length(100_000)?;
+
+    const SIZE: usize = 5 * 1024 * 1024;
+    let mut raw_data = vec![0_u8; SIZE];
+    thread_rng().fill_bytes(&mut *raw_data);
+
+    let mut insert_stmt =
+        connection.prepare("insert into TEST_STREAMED_BLOBS (id, bindata) values (?,?)")?;
+    let before = connection.get_call_count()?;
+    insert_stmt.execute_row(vec![
+        HdbValue::INT(1),
+        HdbValue::LOBSTREAM(Arc::new(Mutex::new(Cursor::new(raw_data.clone())))),
+    ])?;
+    info!(
+        "streaming 5MB BLOB required {} roundtrips",
+        connection.get_call_count()? - before
+    );
+
+    let bytes: ByteBuf = connection
+        .query("select bindata from TEST_STREAMED_BLOBS where id = 1")?
+        .try_into()?;
+    assert_eq!(bytes.len(), SIZE);
+    assert!(bytes[..] == raw_data[..]);
+
+    info!("stream BLOBs in a batch");
+    for id in 2..4 {
+        insert_stmt.add_row_to_batch(vec![
+            HdbValue::INT(id),
+            HdbValue::LOBSTREAM(Arc::new(Mutex::new(Cursor::new(raw_data.clone())))),
+        ])?;
+    }
+    insert_stmt.execute_batch()?;
+    let blobs: Vec<ByteBuf> = connection
+        .query("select bindata from TEST_STREAMED_BLOBS where id > 1 order by id")?
+        .try_into()?;
+    assert_eq!(blobs.len(), 2);
+    for bytes in blobs {
+        assert!(bytes[..] == raw_data[..]);
+    }
+    Ok(())
+}
+
+fn test_streamed_nclob(connection: &mut Connection) -> HdbResult<()> {
+    info!("stream an NCLOB with multi-byte characters across chunk borders");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_STREAMED_NCLOBS"]);
+    connection.exec("create table TEST_STREAMED_NCLOBS (id INT, text NCLOB)")?;
+    connection.set_lob_write_length(1_001)?;
+
+    let mut text = String::new();
+    for i in 0..2_000 {
+        text.push_str(&format!("{}: Grüße aus 😀 Überall\n", i));
+    }
+
+    let mut insert_stmt =
+        connection.prepare("insert into TEST_STREAMED_NCLOBS (id, text) values (?,?)")?;
+    insert_stmt.execute_row(vec![
+        HdbValue::INT(1),
+        HdbValue::LOBSTREAM(Arc::new(Mutex::new(Cursor::new(text.clone().into_bytes())))),
+    ])?;
+
+    let read_text: String = connection
+        .query("select text from TEST_STREAMED_NCLOBS where id = 1")?
+        .try_into()?;
+    assert_eq!(read_text, text);
+    Ok(())
+}
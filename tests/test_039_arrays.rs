@@ -0,0 +1,121 @@
+mod test_utils;
+
+use flexi_logger::ReconfigurationHandle;
+use hdbconnect::{Connection, HdbResult, HdbValue, IntoConnectParams, TypeId};
+use log::{debug, info};
+use std::fs;
+
+// cargo test test_039_arrays -- --nocapture
+#[test]
+pub fn test_039_arrays() -> HdbResult<()> {
+    let mut log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+
+    test_arrays(&mut log_handle, &mut connection)?;
+    test_wire_format(&mut log_handle)?;
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
+
+fn test_arrays(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("write and read ARRAY data");
+
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_ARRAYS"]);
+    connection.multiple_statements(vec![
+        "create column table TEST_ARRAYS (ID INT, INTS INTEGER ARRAY, NAMES NVARCHAR(20) ARRAY)",
+        "insert into TEST_ARRAYS values (1, ARRAY(1, -2, 3), ARRAY('Hugo', 'Otto'))",
+    ])?;
+
+    debug!("insert via parameters");
+    let mut stmt = connection.prepare("insert into TEST_ARRAYS values (?, ?, ?)")?;
+    stmt.execute_row(vec![
+        HdbValue::INT(2),
+        HdbValue::ARRAY(vec![HdbValue::INT(4), HdbValue::INT(5)]),
+        HdbValue::ARRAY(vec![HdbValue::STRING("Anna".to_string())]),
+    ])?;
+    stmt.execute_row(vec![
+        HdbValue::INT(3),
+        HdbValue::ARRAY(vec![]),
+        HdbValue::NULL(TypeId::ARRAY),
+    ])?;
+
+    debug!("select and convert the array values");
+    let resultset = connection.query("select * from TEST_ARRAYS order by ID")?;
+    assert_eq!(resultset.metadata().type_id(1)?, TypeId::ARRAY);
+    let mut arrays = Vec::<(Vec<i32>, Option<Vec<String>>)>::new();
+    for row in resultset {
+        let mut row = row?;
+        let _id = row.next_value().unwrap();
+        let ints: Vec<i32> = row.next_value().unwrap().try_into()?;
+        let names = row.next_value().unwrap();
+        let names: Option<Vec<String>> = if names.is_null() {
+            None
+        } else {
+            Some(names.try_into()?)
+        };
+        arrays.push((ints, names));
+    }
+    assert_eq!(
+        arrays,
+        vec![
+            (
+                vec![1, -2, 3],
+                Some(vec!["Hugo".to_string(), "Otto".to_string()])
+            ),
+            (vec![4, 5], Some(vec!["Anna".to_string()])),
+            (vec![], None),
+        ]
+    );
+
+    debug!("a single array value");
+    let ints: Vec<i64> = connection
+        .query("select INTS from TEST_ARRAYS where ID = 1")?
+        .next_row()?
+        .unwrap()
+        .next_value()
+        .unwrap()
+        .try_into()?;
+    assert_eq!(ints, vec![1, -2, 3]);
+    Ok(())
+}
+
+// The array format is not publicly documented; check the bytes the server sends
+// against the format that hdbconnect parses and emits.
+fn test_wire_format(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
+    info!("check the wire format of arrays against the server");
+    let trace_file = std::env::temp_dir().join(format!("test_039_{}.trace", std::process::id()));
+    let url = test_utils::get_std_connect_url()?;
+    let url = url.trim();
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let params =
+        format!("{}{}trace_file={}", url, separator, trace_file.display()).into_connect_params()?;
+    {
+        let mut connection = Connection::new(params)?;
+        let ints: Vec<i32> = connection
+            .query("select ARRAY(1, -2, 3) from DUMMY")?
+            .next_row()?
+            .unwrap()
+            .next_value()
+            .unwrap()
+            .try_into()?;
+        assert_eq!(ints, vec![1, -2, 3]);
+    }
+
+    // length indicator, number of elements, and each element with its type code (INT)
+    #[rustfmt::skip]
+    let expected: &[u8] = &[
+        19,
+        3, 0, 0, 0,
+        3, 1, 0, 0, 0,
+        3, 0xFE, 0xFF, 0xFF, 0xFF,
+        3, 3, 0, 0, 0,
+    ];
+    let trace = fs::read(&trace_file)?;
+    fs::remove_file(&trace_file)?;
+    assert!(trace.windows(expected.len()).any(|w| w == expected));
+    Ok(())
+}
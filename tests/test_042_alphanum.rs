@@ -0,0 +1,74 @@
+mod test_utils;
+
+use flexi_logger::ReconfigurationHandle;
+use hdbconnect::{Connection, HdbResult, TypeId};
+use log::{debug, info};
+use serde_derive::Deserialize;
+
+// cargo test test_042_alphanum -- --nocapture
+#[test]
+pub fn test_042_alphanum() -> HdbResult<()> {
+    let mut log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+
+    test_alphanum(&mut log_handle, &mut connection)?;
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
+
+fn test_alphanum(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("write and read ALPHANUM data");
+
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_ALPHANUM"]);
+    connection.multiple_statements(vec![
+        "create table TEST_ALPHANUM (ID INT, CODE ALPHANUM(6))",
+        "insert into TEST_ALPHANUM values (1, '42')",
+    ])?;
+
+    debug!("insert via parameters (use serde)");
+    let mut stmt = connection.prepare("insert into TEST_ALPHANUM values (?, ?)")?;
+    stmt.add_batch(&(2, 4711))?;
+    stmt.add_batch(&(3, "4711-A"))?;
+    stmt.add_batch(&(4, None::<String>))?;
+    stmt.execute_batch()?;
+
+    debug!("select and deserialize (use serde)");
+    let resultset = connection.query("select CODE from TEST_ALPHANUM order by ID")?;
+    assert_eq!(resultset.metadata().type_id(0)?, TypeId::ALPHANUM);
+    let codes: Vec<Option<String>> = resultset.try_into()?;
+    assert_eq!(
+        codes,
+        vec![
+            Some("000042".to_string()),
+            Some("004711".to_string()),
+            Some("4711-A".to_string()),
+            None,
+        ]
+    );
+
+    debug!("numeric values can be deserialized into numbers");
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Numeric {
+        ID: i32,
+        CODE: u32,
+    }
+    let numerics: Vec<Numeric> = connection
+        .query("select ID, CODE from TEST_ALPHANUM where ID < 3 order by ID")?
+        .try_into()?;
+    assert_eq!(
+        numerics,
+        vec![Numeric { ID: 1, CODE: 42 }, Numeric { ID: 2, CODE: 4711 }]
+    );
+
+    debug!("non-numeric values cannot be deserialized into numbers");
+    let result: HdbResult<u32> = connection
+        .query("select CODE from TEST_ALPHANUM where ID = 3")?
+        .try_into();
+    assert!(result.is_err());
+    Ok(())
+}
@@ -0,0 +1,98 @@
+mod test_utils;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use flexi_logger::ReconfigurationHandle;
+use hdbconnect::{Connection, HdbResult, TypeId};
+use log::{debug, info};
+use serde_derive::Deserialize;
+
+// cargo test test_043_data_format_version -- --nocapture
+#[test]
+pub fn test_043_data_format_version() -> HdbResult<()> {
+    let mut log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+
+    test_date_types(&mut log_handle, &mut connection)?;
+
+    info!("{} calls to DB were executed", connection.get_call_count()?);
+    Ok(())
+}
+
+// Servers with a data format version below 4 use the legacy types DATE, TIME, and TIMESTAMP,
+// newer servers use DAYDATE, SECONDTIME, and LONGDATE; both must work the same way.
+fn test_date_types(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    let data_format_version = connection.data_format_version()?;
+    info!(
+        "write and read dates with data format version {}",
+        data_format_version
+    );
+    assert!(data_format_version > 0 && data_format_version <= 8);
+
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_DATE_TYPES"]);
+    connection.multiple_statements(vec![
+        "create table TEST_DATE_TYPES (ID INT, D DATE, T TIME, TS TIMESTAMP)",
+        "insert into TEST_DATE_TYPES values (1, '2012-02-02', '02:02:02', \
+         '2012-02-02 02:02:02.200000000')",
+    ])?;
+
+    debug!("insert via parameters (use serde)");
+    let date = NaiveDate::from_ymd(2013, 3, 3);
+    let time = NaiveTime::from_hms(3, 3, 3);
+    let timestamp = date.and_hms_nano(3, 3, 3, 300_000_000);
+    let mut stmt = connection.prepare("insert into TEST_DATE_TYPES values (?, ?, ?, ?)")?;
+    stmt.add_batch(&(2, date, time, timestamp))?;
+    stmt.add_batch(&(
+        3,
+        None::<NaiveDate>,
+        None::<NaiveTime>,
+        None::<NaiveDateTime>,
+    ))?;
+    stmt.execute_batch()?;
+
+    debug!("select and deserialize (use serde)");
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Dates {
+        ID: i32,
+        D: Option<NaiveDate>,
+        T: Option<NaiveTime>,
+        TS: Option<NaiveDateTime>,
+    }
+    let resultset = connection.query("select * from TEST_DATE_TYPES order by ID")?;
+    let expected_type_ids = if data_format_version < 4 {
+        [TypeId::DATE, TypeId::TIME, TypeId::TIMESTAMP]
+    } else {
+        [TypeId::DAYDATE, TypeId::SECONDTIME, TypeId::LONGDATE]
+    };
+    for (i, type_id) in expected_type_ids.iter().enumerate() {
+        assert_eq!(resultset.metadata().type_id(i + 1)?, *type_id);
+    }
+    let dates: Vec<Dates> = resultset.try_into()?;
+    assert_eq!(
+        dates,
+        vec![
+            Dates {
+                ID: 1,
+                D: Some(NaiveDate::from_ymd(2012, 2, 2)),
+                T: Some(NaiveTime::from_hms(2, 2, 2)),
+                TS: Some(NaiveDate::from_ymd(2012, 2, 2).and_hms_nano(2, 2, 2, 200_000_000)),
+            },
+            Dates {
+                ID: 2,
+                D: Some(date),
+                T: Some(time),
+                TS: Some(timestamp),
+            },
+            Dates {
+                ID: 3,
+                D: None,
+                T: None,
+                TS: None,
+            },
+        ]
+    );
+    Ok(())
+}
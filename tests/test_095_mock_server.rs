@@ -0,0 +1,344 @@
+#![cfg(feature = "mock_server")]
+
+mod test_utils;
+
+use hdbconnect::types::{DayDate, LongDate, SecondTime};
+use hdbconnect::{
+    ConnectParams, Connection, HdbError, HdbResult, HdbValue, MockReply, MockServer, TypeId,
+};
+use log::info;
+use serde_derive::Deserialize;
+
+// cargo test --features mock_server --test test_095_mock_server -- --nocapture
+#[test]
+fn test_095_mock_server() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+
+    let server = MockServer::start("MEIER", "schLau")?;
+    logon(&server)?;
+    failover(&server)?;
+    queries(&server)?;
+    lobs(&server)?;
+    arrays(&server)?;
+    alphanums(&server)?;
+    legacy_dates()?;
+    #[cfg(feature = "geo")]
+    spatial(&server)?;
+    Ok(())
+}
+
+fn logon(server: &MockServer) -> HdbResult<()> {
+    info!("logon with correct and with wrong password");
+    let connection = Connection::new(server.connect_params())?;
+    assert_eq!(connection.id()?, 1);
+    assert_eq!(connection.data_format_version()?, 8);
+
+    let params = server.connect_params_builder().password("wrong").build()?;
+    match Connection::new(params) {
+        Err(HdbError::DbError(server_error)) => assert_eq!(server_error.code(), 10),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("logon with wrong password succeeded"),
+    }
+    Ok(())
+}
+
+fn failover(server: &MockServer) -> HdbResult<()> {
+    info!("failover hosts are only tried after I/O errors");
+    let unused_port = std::net::TcpListener::bind("127.0.0.1:0")?
+        .local_addr()?
+        .port();
+    let params = ConnectParams::builder()
+        .hostname("127.0.0.1")
+        .port(unused_port)
+        .failover_host(server.addr().ip().to_string(), server.addr().port())
+        .dbuser("MEIER")
+        .password("schLau")
+        .build()?;
+    Connection::new(params)?;
+
+    // the other server would accept the wrong password
+    let other_server = MockServer::start("MEIER", "wrong")?;
+    let params = server
+        .connect_params_builder()
+        .password("wrong")
+        .failover_host(
+            other_server.addr().ip().to_string(),
+            other_server.addr().port(),
+        )
+        .build()?;
+    match Connection::new(params) {
+        Err(HdbError::DbError(server_error)) => assert_eq!(server_error.code(), 10),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("failover after failed authentication"),
+    }
+
+    info!("if all addresses fail, the error kind of the last one is kept");
+    let params = ConnectParams::builder()
+        .hostname("127.0.0.1")
+        .port(unused_port)
+        .failover_host("127.0.0.1", unused_port)
+        .dbuser("MEIER")
+        .password("schLau")
+        .build()?;
+    match Connection::new(params) {
+        Err(HdbError::Io(io_error)) => {
+            assert_eq!(io_error.kind(), std::io::ErrorKind::ConnectionRefused)
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("connect to unused port succeeded"),
+    }
+    Ok(())
+}
+
+fn queries(server: &MockServer) -> HdbResult<()> {
+    info!("scripted resultset, affected rows, success, and errors");
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Data {
+        ID: i32,
+        NAME: Option<String>,
+        SCORE: f64,
+        ACTIVE: bool,
+    }
+
+    server.push_reply(MockReply::ResultSet {
+        columns: vec![
+            ("ID".to_string(), TypeId::INT),
+            ("NAME".to_string(), TypeId::NVARCHAR),
+            ("SCORE".to_string(), TypeId::DOUBLE),
+            ("ACTIVE".to_string(), TypeId::BOOLEAN),
+        ],
+        rows: vec![
+            vec![
+                HdbValue::INT(1),
+                HdbValue::STRING("Hügo".to_string()),
+                HdbValue::DOUBLE(1.5),
+                HdbValue::BOOLEAN(true),
+            ],
+            vec![
+                HdbValue::INT(2),
+                HdbValue::NULL(TypeId::NVARCHAR),
+                HdbValue::DOUBLE(-3.0),
+                HdbValue::BOOLEAN(false),
+            ],
+        ],
+    });
+    server.push_reply(MockReply::RowsAffected(vec![3]));
+    server.push_reply(MockReply::Success);
+    server.push_reply(MockReply::Error(259, "invalid table name".to_string()));
+
+    let mut connection = Connection::new(server.connect_params())?;
+    let data: Vec<Data> = connection.query("select * from DATA")?.try_into()?;
+    assert_eq!(
+        data,
+        vec![
+            Data {
+                ID: 1,
+                NAME: Some("Hügo".to_string()),
+                SCORE: 1.5,
+                ACTIVE: true,
+            },
+            Data {
+                ID: 2,
+                NAME: None,
+                SCORE: -3.0,
+                ACTIVE: false,
+            },
+        ]
+    );
+    assert_eq!(connection.dml("delete from DATA")?, 3);
+    connection.exec("drop table DATA")?;
+    match connection.query("select * from NODATA") {
+        Err(HdbError::DbError(server_error)) => {
+            assert_eq!(server_error.code(), 259);
+            assert_eq!(server_error.text(), "invalid table name");
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("query succeeded unexpectedly"),
+    }
+
+    // the script is exhausted
+    assert_eq!(server.pending_replies(), 0);
+    assert!(connection.query("select * from DATA").is_err());
+
+    assert_eq!(
+        server.commands(),
+        vec![
+            "select * from DATA",
+            "delete from DATA",
+            "drop table DATA",
+            "select * from NODATA",
+            "select * from DATA",
+        ]
+    );
+    Ok(())
+}
+
+fn lobs(server: &MockServer) -> HdbResult<()> {
+    info!("LOBs are read in chunks");
+    let bytes: Vec<u8> = (0..1000).map(|i| (i % 256) as u8).collect();
+    let text: String = "Grüße aus 🌍! ".repeat(50);
+    server.push_reply(MockReply::ResultSet {
+        columns: vec![
+            ("B".to_string(), TypeId::BLOB),
+            ("N".to_string(), TypeId::NCLOB),
+            ("C".to_string(), TypeId::CLOB),
+        ],
+        rows: vec![vec![
+            HdbValue::BINARY(bytes.clone()),
+            HdbValue::STRING(text.clone()),
+            HdbValue::NULL(TypeId::CLOB),
+        ]],
+    });
+
+    let mut connection = Connection::new(server.connect_params())?;
+    connection.set_lob_read_length(100)?;
+    let mut row = connection
+        .query("select B, N, C from LOBS")?
+        .next_row()?
+        .unwrap();
+    let blob = row.next_value().unwrap().try_into_blob()?;
+    assert_eq!(blob.into_bytes()?, bytes);
+    let nclob = row.next_value().unwrap().try_into_nclob()?;
+    assert_eq!(nclob.into_string()?, text);
+    assert!(row.next_value().unwrap().is_null());
+    Ok(())
+}
+
+fn arrays(server: &MockServer) -> HdbResult<()> {
+    info!("arrays are converted into Vecs");
+    let reply = MockReply::ResultSet {
+        columns: vec![
+            ("I".to_string(), TypeId::ARRAY),
+            ("S".to_string(), TypeId::ARRAY),
+            ("N".to_string(), TypeId::ARRAY),
+        ],
+        rows: vec![vec![
+            HdbValue::ARRAY(vec![HdbValue::INT(1), HdbValue::INT(-2), HdbValue::INT(3)]),
+            HdbValue::ARRAY(vec![
+                HdbValue::STRING("Hugo".to_string()),
+                HdbValue::NULL(TypeId::NVARCHAR),
+            ]),
+            HdbValue::NULL(TypeId::ARRAY),
+        ]],
+    };
+
+    let mut row = server
+        .query("select I, S, N from ARRAYS", reply)?
+        .next_row()?
+        .unwrap();
+    let ints: Vec<i64> = row.next_value().unwrap().try_into()?;
+    assert_eq!(ints, vec![1, -2, 3]);
+    let strings: Vec<Option<String>> = row.next_value().unwrap().try_into()?;
+    assert_eq!(strings, vec![Some("Hugo".to_string()), None]);
+    assert!(row.next_value().unwrap().is_null());
+    Ok(())
+}
+
+fn alphanums(server: &MockServer) -> HdbResult<()> {
+    info!("numeric alphanums are padded with zeros and can be converted into numbers");
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Material {
+        NUMBER: u32,
+        TEXT: String,
+        CODE: Option<String>,
+    }
+
+    let reply = MockReply::ResultSet {
+        columns: vec![
+            ("NUMBER".to_string(), TypeId::ALPHANUM),
+            ("TEXT".to_string(), TypeId::ALPHANUM),
+            ("CODE".to_string(), TypeId::ALPHANUM),
+        ],
+        rows: vec![vec![
+            HdbValue::ALPHANUM("000042".to_string()),
+            HdbValue::ALPHANUM("000042".to_string()),
+            HdbValue::ALPHANUM("4711-A".to_string()),
+        ]],
+    };
+    let material: Material = server
+        .query("select NUMBER, NUMBER as TEXT, CODE from MATERIALS", reply)?
+        .try_into()?;
+    assert_eq!(
+        material,
+        Material {
+            NUMBER: 42,
+            TEXT: "000042".to_string(),
+            CODE: Some("4711-A".to_string()),
+        }
+    );
+
+    let reply = MockReply::ResultSet {
+        columns: vec![("CODE".to_string(), TypeId::ALPHANUM)],
+        rows: vec![vec![HdbValue::ALPHANUM("4711-A".to_string())]],
+    };
+    let result: HdbResult<u32> = server
+        .query("select CODE from MATERIALS", reply)?
+        .try_into();
+    assert!(result.is_err());
+    Ok(())
+}
+
+fn legacy_dates() -> HdbResult<()> {
+    info!("a server with data format version 4 uses the legacy date types");
+    let server = MockServer::start("MEIER", "schLau")?;
+    server.set_data_format_version(4);
+    let reply = MockReply::ResultSet {
+        columns: vec![
+            ("D".to_string(), TypeId::DATE),
+            ("T".to_string(), TypeId::TIME),
+            ("TS".to_string(), TypeId::TIMESTAMP),
+            ("N".to_string(), TypeId::TIMESTAMP),
+        ],
+        rows: vec![vec![
+            HdbValue::DAYDATE(DayDate::from_ymd(2012, 2, 29)?),
+            HdbValue::SECONDTIME(SecondTime::from_hms(23, 59, 58)?),
+            HdbValue::LONGDATE(LongDate::from_ymd_hms(2012, 2, 29, 23, 59, 58)?),
+            HdbValue::NULL(TypeId::TIMESTAMP),
+        ]],
+    };
+
+    assert_eq!(
+        Connection::new(server.connect_params())?.data_format_version()?,
+        4
+    );
+    let (d, t, ts, n): (String, String, String, Option<String>) = server
+        .query("select D, T, TS, N from EVENTS", reply)?
+        .try_into()?;
+    assert_eq!(d, "2012-02-29");
+    assert_eq!(t, "23:59:58");
+    assert_eq!(ts, "2012-02-29T23:59:58.0000000");
+    assert_eq!(n, None);
+    Ok(())
+}
+
+#[cfg(feature = "geo")]
+fn spatial(server: &MockServer) -> HdbResult<()> {
+    use hdbconnect::types::Spatial;
+    info!("spatial values are decoded into geometries, or into WKT");
+    let shape = Spatial::from_wkt("SRID=4326;POLYGON((1 1,1 6,6 6,1 1))")?;
+    let reply = MockReply::ResultSet {
+        columns: vec![
+            ("SHAPE".to_string(), TypeId::GEOMETRY),
+            ("P".to_string(), TypeId::POINT),
+        ],
+        rows: vec![vec![
+            HdbValue::GEOMETRY(shape.to_ewkb()),
+            HdbValue::NULL(TypeId::POINT),
+        ]],
+    };
+
+    let (geometry, point): (Spatial, Option<Spatial>) = server
+        .query("select SHAPE, P from SHAPES", reply.clone())?
+        .try_into()?;
+    assert_eq!(geometry, shape);
+    assert_eq!(geometry.srid(), Some(4326));
+    assert_eq!(point, None);
+
+    let (wkt, _): (String, Option<String>) = server
+        .query("select SHAPE, P from SHAPES", reply)?
+        .try_into()?;
+    assert_eq!(wkt, "SRID=4326;POLYGON((1 1,1 6,6 6,1 1))");
+    Ok(())
+}
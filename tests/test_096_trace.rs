@@ -0,0 +1,95 @@
+#![cfg(feature = "mock_server")]
+
+mod test_utils;
+
+use hdbconnect::{Connection, HdbResult, HdbValue, MockReply, MockServer, TraceReplay, TypeId};
+use log::info;
+use std::fs;
+
+// cargo test --features mock_server --test test_096_trace -- --nocapture
+#[test]
+fn test_096_trace() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+
+    let trace_file = std::env::temp_dir().join(format!("test_096_{}.trace", std::process::id()));
+    record(&trace_file)?;
+    check_redaction(&trace_file)?;
+    replay(&trace_file)?;
+    dump(&trace_file)?;
+    fs::remove_file(&trace_file)?;
+    Ok(())
+}
+
+fn record(trace_file: &std::path::Path) -> HdbResult<()> {
+    info!("record the traffic of two connections");
+    let server = MockServer::start("MEIER", "schLau")?;
+    server.push_reply(MockReply::ResultSet {
+        columns: vec![("NAME".to_string(), TypeId::NVARCHAR)],
+        rows: vec![
+            vec![HdbValue::STRING("Hugo".to_string())],
+            vec![HdbValue::STRING("Fritz".to_string())],
+        ],
+    });
+    server.push_reply(MockReply::RowsAffected(vec![2]));
+
+    let params = server
+        .connect_params_builder()
+        .trace_file(trace_file)
+        .build()?;
+    assert_eq!(params.trace_file(), Some(trace_file));
+    {
+        let mut connection = Connection::new(params.clone())?;
+        let names: Vec<String> = connection.query("select name from persons")?.try_into()?;
+        assert_eq!(names, vec!["Hugo", "Fritz"]);
+    }
+    {
+        let mut connection = Connection::new(params)?;
+        assert_eq!(connection.dml("delete from persons")?, 2);
+    }
+    Ok(())
+}
+
+fn check_redaction(trace_file: &std::path::Path) -> HdbResult<()> {
+    info!("the trace contains the user, but no authentication data");
+    let trace = fs::read(trace_file)?;
+    assert_eq!(&trace[..9], b"HDBTRACE\x01");
+    let contains = |pattern: &[u8]| trace.windows(pattern.len()).any(|w| w == pattern);
+    assert!(contains(b"MEIER"));
+    assert!(contains(b"SCRAMSHA256"));
+    assert!(contains(b"select name from persons"));
+    // SCRAMSHA256 is proposed with its 64 byte client challenge, which is removed
+    assert!(!contains(b"SCRAMSHA256\x40"));
+    assert!(contains(b"SCRAMSHA256\x00"));
+    Ok(())
+}
+
+fn replay(trace_file: &std::path::Path) -> HdbResult<()> {
+    info!("replay both connections without server");
+    let replay = TraceReplay::load(trace_file)?;
+    assert_eq!(replay.connections(), vec![1, 2]);
+
+    let mut connection = Connection::new(replay.connect_params(1)?)?;
+    let names: Vec<String> = connection.query("select name from persons")?.try_into()?;
+    assert_eq!(names, vec!["Hugo", "Fritz"]);
+    // the trace contains no further replies
+    assert!(connection.query("select name from persons").is_err());
+
+    let mut connection = Connection::new(replay.connect_params(2)?)?;
+    assert_eq!(connection.dml("delete from persons")?, 2);
+
+    assert!(replay.connect_params(3).is_err());
+    Ok(())
+}
+
+fn dump(trace_file: &std::path::Path) -> HdbResult<()> {
+    info!("dump the trace in readable form");
+    let mut dump = Vec::<u8>::new();
+    hdbconnect::dump_packets(&fs::read(trace_file)?, &mut dump)?;
+    let dump = String::from_utf8(dump).unwrap();
+    assert!(dump.starts_with("=== connection 1: request ===\ninitial request:"));
+    assert!(dump.contains("=== connection 2: reply ==="));
+    assert!(dump.contains("message_type = 65 (Authenticate)"));
+    assert!(dump.contains("    \"select name from persons\"\n"));
+    assert!(dump.contains("kind = 48 (ResultSetMetadata)"));
+    Ok(())
+}